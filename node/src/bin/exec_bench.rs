@@ -0,0 +1,96 @@
+// Benchmark: sequential vs optimistic parallel execution of independent transfers.
+//
+// Usage: exec_bench [tx_count] [workers]
+use dytallix_fast_node::execution::parallel::execute_block_parallel;
+use dytallix_fast_node::execution::{execute_transaction, ExecutionModules};
+use dytallix_fast_node::gas::GasSchedule;
+use dytallix_fast_node::state::State;
+use dytallix_fast_node::storage::state::Storage;
+use dytallix_fast_node::storage::tx::Transaction;
+use std::env;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+fn funded_state(dir: &Path, tx_count: usize) -> anyhow::Result<State> {
+    let mut state = State::new(Arc::new(Storage::open(dir.to_path_buf())?));
+    for i in 0..tx_count {
+        state.set_balance(&format!("dyt1benchsender{i}"), "udgt", 1_000_000);
+    }
+    Ok(state)
+}
+
+fn report(label: &str, tx_count: usize, elapsed: Duration) {
+    println!(
+        "{label:<10} {tx_count} txs in {:>8.1} ms ({:.0} tx/s)",
+        elapsed.as_secs_f64() * 1000.0,
+        tx_count as f64 / elapsed.as_secs_f64()
+    );
+}
+
+fn main() -> anyhow::Result<()> {
+    let args: Vec<String> = env::args().collect();
+    let tx_count: usize = args.get(1).and_then(|v| v.parse().ok()).unwrap_or(10_000);
+    let workers: usize = args.get(2).and_then(|v| v.parse().ok()).unwrap_or_else(|| {
+        std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
+    });
+    let schedule = GasSchedule::default();
+
+    // Every transfer has its own sender and recipient, so none of them conflict
+    let txs: Vec<Transaction> = (0..tx_count)
+        .map(|i| {
+            Transaction::new(
+                format!("0xbench{i:08x}"),
+                format!("dyt1benchsender{i}"),
+                format!("dyt1benchrecipient{i}"),
+                1_000,
+                25_000,
+                0,
+                None,
+            )
+            .with_gas(25_000, 1)
+        })
+        .collect();
+
+    let root = env::temp_dir().join(format!("dyt-exec-bench-{}", std::process::id()));
+    let (seq_dir, par_dir) = (root.join("sequential"), root.join("parallel"));
+
+    let mut state = funded_state(&seq_dir, tx_count)?;
+    let start = Instant::now();
+    for (i, tx) in txs.iter().enumerate() {
+        execute_transaction(
+            tx,
+            &mut state,
+            1,
+            i as u32,
+            &schedule,
+            None,
+            ExecutionModules::default(),
+        );
+    }
+    report("sequential", tx_count, start.elapsed());
+
+    let mut state = funded_state(&par_dir, tx_count)?;
+    let start = Instant::now();
+    let block = execute_block_parallel(
+        &txs,
+        &mut state,
+        1,
+        &schedule,
+        None,
+        ExecutionModules::default(),
+        workers,
+    );
+    report("parallel", tx_count, start.elapsed());
+    println!(
+        "workers={workers} conflicts={} failed={}",
+        block.conflicts,
+        block.results.iter().filter(|r| !r.success).count()
+    );
+
+    drop(state);
+    let _ = std::fs::remove_dir_all(root);
+    Ok(())
+}
@@ -0,0 +1,45 @@
+// Check the supply invariant (supply == sum of all balances, module accounts included) of a
+// node database. The node must be stopped since RocksDB allows a single writer.
+//
+// Usage: supply_check [data_dir]   (defaults to $DYT_DATA_DIR or ./data)
+use dytallix_fast_node::runtime::module_accounts::{module_address, module_balances};
+use dytallix_fast_node::runtime::supply::check_invariants;
+use dytallix_fast_node::storage::state::Storage;
+use std::env;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+fn main() -> anyhow::Result<()> {
+    let data_dir = env::args()
+        .nth(1)
+        .or_else(|| env::var("DYT_DATA_DIR").ok())
+        .unwrap_or_else(|| "./data".to_string());
+    let storage = Arc::new(Storage::open(PathBuf::from(format!("{data_dir}/node.db")))?);
+
+    println!("height {}", storage.height());
+    for (name, balances) in module_balances(&storage) {
+        for (denom, amount) in balances {
+            println!(
+                "module {name:<28} {} {amount:>30} {denom}",
+                module_address(&name)
+            );
+        }
+    }
+    let checks = check_invariants(&storage);
+    let mut violations = 0;
+    for check in &checks {
+        let status = if check.holds() { "ok" } else { "VIOLATED" };
+        println!(
+            "{:<40} supply={} balances={} module_accounts={} {status}",
+            check.denom, check.supply, check.balances, check.module_accounts
+        );
+        if !check.holds() {
+            violations += 1;
+        }
+    }
+    if violations > 0 {
+        eprintln!("{violations} denom(s) violate the supply invariant");
+        std::process::exit(1);
+    }
+    Ok(())
+}
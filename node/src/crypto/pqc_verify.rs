@@ -102,9 +102,9 @@ mod fail_closed_tests {
         #[cfg(feature = "pqc-fips204")]
         {
             use fips204::ml_dsa_87;
-            assert!(ml_dsa_87::PK_LEN > 0);
-            assert!(ml_dsa_87::SIG_LEN > 0);
-            assert!(ml_dsa_87::SK_LEN > 0);
+            const { assert!(ml_dsa_87::PK_LEN > 0) };
+            const { assert!(ml_dsa_87::SIG_LEN > 0) };
+            const { assert!(ml_dsa_87::SK_LEN > 0) };
         }
     }
 }
@@ -168,10 +168,10 @@ pub fn verify(
 
     #[cfg(feature = "pqc-fips204")]
     {
-        return match alg {
+        match alg {
             PQCAlgorithm::Dilithium5 => verify_dilithium5_fips204(pubkey, msg, sig),
             _ => Err(PQCVerifyError::UnsupportedAlgorithm(alg.as_str().to_string())),
-        };
+        }
     }
 
     #[cfg(all(feature = "pqc-real", not(feature = "pqc-fips204")))]
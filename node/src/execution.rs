@@ -1,18 +1,37 @@
 /*
 Deterministic execution module for dytallix consensus.
 
-Implements upfront fee charging, full-revert semantics, and deterministic gas accounting
-to ensure all nodes reach identical post-state and receipts.
+Implements upfront fee charging, full-revert semantics, refunds of unused gas and
+deterministic gas accounting to ensure all nodes reach identical post-state and receipts.
 Includes fee burning mechanism for dual-token economics.
 */
 
 use crate::gas::{intrinsic_gas, Gas, GasError, GasMeter, GasSchedule, TxKind};
+use crate::runtime::dead_man_switch::DeadManSwitchModule;
 use crate::runtime::fee_burn::FeeBurnEngine;
-use crate::state::State;
+use crate::runtime::fee_grant::{FeeAllowance, FeeGrantModule};
+use crate::runtime::governance::{format_weight, GovernanceEvent, GovernanceModule};
+use crate::runtime::schedule::ScheduleModule;
+use crate::runtime::staking::StakingModule;
+use crate::runtime::pause::PauseModule;
+use crate::runtime::module_accounts::{
+    collect_block_fees, emission_account, module_balance, send_from_module, send_to_module,
+    SCHEDULE_ESCROW, STAKING_BONDED,
+};
+use crate::runtime::token_factory::TokenFactoryModule;
+use crate::runtime::vesting::{spendable_balance, VestingModule};
+#[cfg(feature = "contracts")]
+use crate::runtime::wasm::WasmRuntime;
+use crate::state::{State, StateOverlay};
+use crate::storage::events::Event;
 use crate::storage::receipts::{TxReceipt, TxStatus, RECEIPT_FORMAT_VERSION};
 use crate::storage::tx::Transaction;
+pub use crate::state::StateChange;
+use std::collections::{BTreeSet, HashMap};
 use thiserror::Error;
 
+pub mod parallel;
+
 #[derive(Debug, Error)]
 pub enum ExecutionError {
     #[error("Insufficient funds: required {required}, available {available}")]
@@ -37,17 +56,9 @@ pub struct ExecutionContext {
     pub gas_limit: Gas,
     pub gas_price: u64,
     pub gas_meter: GasMeter,
-    pub state_changes: Vec<StateChange>,
-    pub events: Vec<String>, // Simplified events for now
-}
-
-/// Represents a state change that can be reverted
-#[derive(Debug, Clone)]
-pub struct StateChange {
-    pub address: String,
-    pub denom: String,
-    pub old_balance: u128,
-    pub new_balance: u128,
+    pub events: Vec<Event>,
+    /// Governance lifecycle events, recorded with the module once the transaction commits
+    pub governance_events: Vec<GovernanceEvent>,
 }
 
 /// Result of transaction execution
@@ -57,6 +68,11 @@ pub struct ExecutionResult {
     pub state_changes: Vec<StateChange>,
     pub gas_used: Gas,
     pub success: bool,
+    pub events: Vec<Event>,
+    pub gas_operations: HashMap<String, Gas>, // Per-operation breakdown from the gas meter
+    pub fee_refunded: u128, // udgt returned to the fee payer for unused gas
+    pub fee_charged: u128,  // udgt fee kept from the fee payer after refunds
+    pub fee_burned: u128,   // udgt burned from the charged fee
 }
 
 impl ExecutionContext {
@@ -65,8 +81,8 @@ impl ExecutionContext {
             gas_limit,
             gas_price,
             gas_meter: GasMeter::new(gas_limit),
-            state_changes: Vec::new(),
             events: Vec::new(),
+            governance_events: Vec::new(),
         }
     }
 
@@ -80,43 +96,50 @@ impl ExecutionContext {
             .ok_or(ExecutionError::FeeOverflow)
     }
 
-    /// Record a state change for potential revert
-    pub fn record_state_change(
-        &mut self,
-        address: String,
-        denom: String,
-        old_balance: u128,
-        new_balance: u128,
-    ) {
-        self.state_changes.push(StateChange {
-            address,
-            denom,
-            old_balance,
-            new_balance,
-        });
-    }
-
     /// Consume gas and check if we're out of gas
     pub fn consume_gas(&mut self, amount: Gas, operation: &str) -> Result<(), GasError> {
         self.gas_meter.consume(amount, operation)
     }
 
+    /// Emit a typed event, charging `per_event` gas
+    pub fn emit_event(&mut self, event: Event, gas_schedule: &GasSchedule) -> Result<(), GasError> {
+        self.consume_gas(gas_schedule.per_event, "event")?;
+        self.events.push(event);
+        Ok(())
+    }
+
     /// Get gas used so far
     pub fn gas_used(&self) -> Gas {
         self.gas_meter.gas_used()
     }
+}
+
+/// Runtime modules available to staking, governance and contract messages.
+/// Messages that need a module which is not supplied fail with an execution error.
+/// Staking and governance state is written through the transaction's overlay, so
+/// `staking` only marks staking as enabled.
+#[derive(Default)]
+pub struct ExecutionModules<'a> {
+    pub staking: Option<&'a mut StakingModule>,
+    pub governance: Option<&'a mut GovernanceModule>,
+    #[cfg(feature = "contracts")]
+    pub wasm: Option<&'a WasmRuntime>,
+}
 
-    /// Revert all state changes recorded in this context
-    pub fn revert_state_changes(&self, state: &mut State) {
-        // Revert changes in reverse order
-        for change in self.state_changes.iter().rev() {
-            // Restore the old balance
-            state.set_balance(&change.address, &change.denom, change.old_balance);
+impl ExecutionModules<'_> {
+    /// Borrow the modules again for one transaction of a block
+    pub fn reborrow(&mut self) -> ExecutionModules<'_> {
+        ExecutionModules {
+            staking: self.staking.as_deref_mut(),
+            governance: self.governance.as_deref_mut(),
+            #[cfg(feature = "contracts")]
+            wasm: self.wasm,
         }
     }
 }
 
-/// Execute a single transaction with deterministic gas accounting and fee burning
+/// Execute a single transaction with deterministic gas accounting and fee burning. The
+/// charged fee is credited to the fee collector, which burns its configured share.
 pub fn execute_transaction(
     tx: &Transaction,
     state: &mut State,
@@ -124,22 +147,135 @@ pub fn execute_transaction(
     tx_index: u32,
     gas_schedule: &GasSchedule,
     fee_burn_engine: Option<&mut FeeBurnEngine>,
+    modules: ExecutionModules<'_>,
+) -> ExecutionResult {
+    let mut result = execute_messages(tx, state, block_height, tx_index, gas_schedule, modules);
+
+    // Process fee burning on the charged fee if enabled
+    if let Some(engine) = fee_burn_engine {
+        burn_charged_fee(tx, &mut result, block_height, engine, state);
+    }
+    if let Err(e) = collect_block_fees(state, result.fee_charged, result.fee_burned) {
+        tracing::warn!("tx {}: fee collection failed: {e}", tx.hash);
+    }
+    result
+}
+
+/// Execute a transaction's messages and charge its fee, leaving the charged fee uncollected
+/// and unburned. Block execution collects the fees of all its transactions at once.
+fn execute_messages(
+    tx: &Transaction,
+    state: &mut State,
+    block_height: u64,
+    tx_index: u32,
+    gas_schedule: &GasSchedule,
+    modules: ExecutionModules<'_>,
 ) -> ExecutionResult {
     // If transaction has multiple messages, process them all
     if let Some(messages) = &tx.messages {
-        return execute_multi_message_transaction(
+        execute_multi_message_transaction(
             tx,
             messages,
             state,
             block_height,
             tx_index,
             gas_schedule,
-            fee_burn_engine,
-        );
+            modules,
+        )
+    } else {
+        // Otherwise, fall back to single-message execution (legacy path)
+        execute_single_message_transaction(tx, state, block_height, tx_index, gas_schedule)
     }
+}
 
-    // Otherwise, fall back to single-message execution (legacy path)
-    execute_single_message_transaction(tx, state, block_height, tx_index, gas_schedule, fee_burn_engine)
+/// Burn the configured share of a transaction's charged fee and append the
+/// `fee_burn` event to its result. Fee burning errors are non-fatal and don't affect
+/// transaction success.
+fn burn_charged_fee(
+    tx: &Transaction,
+    result: &mut ExecutionResult,
+    block_height: u64,
+    engine: &mut FeeBurnEngine,
+    state: &mut State,
+) {
+    if result.fee_charged == 0 {
+        return;
+    }
+    if let Ok(Some(burn)) = engine.process_fee_burn(tx.hash.clone(), block_height, result.fee_charged, state) {
+        let event = fee_burn_event(&burn);
+        result.receipt.events.push(event.clone());
+        result.events.push(event);
+        result.fee_burned = burn.burn_amount;
+    }
+}
+
+/// Net balance change of one (address, denom) pair observed during a dry run
+#[derive(Debug, Clone, PartialEq)]
+pub struct BalanceDiff {
+    pub address: String,
+    pub denom: String,
+    pub before: u128,
+    pub after: u128,
+}
+
+/// Result of a dry run: the execution result plus the balance diff it would produce
+#[derive(Debug)]
+pub struct SimulationResult {
+    pub result: ExecutionResult,
+    pub balance_diff: Vec<BalanceDiff>,
+}
+
+/// Dry-run a transaction against a fork of `state`. Nothing is persisted and no fee is burned.
+/// Staking and governance state is written to the fork; `modules` should be forks as well
+/// (`GovernanceModule::fork`, `WasmRuntime::fork`) so the events and contract state they
+/// keep are discarded too.
+pub fn simulate_transaction(
+    tx: &Transaction,
+    state: &State,
+    block_height: u64,
+    gas_schedule: &GasSchedule,
+    modules: ExecutionModules<'_>,
+) -> SimulationResult {
+    let mut fork = state.fork();
+    let result = execute_transaction(
+        tx,
+        &mut fork,
+        block_height,
+        0,
+        gas_schedule,
+        None,
+        modules,
+    );
+
+    // Every account the execution touched is now cached in the fork
+    let mut addresses: Vec<&String> = fork.accounts.keys().collect();
+    addresses.sort();
+    let mut balance_diff = Vec::new();
+    for address in addresses {
+        let before = state.snapshot_account(address);
+        let after = &fork.accounts[address];
+        let denoms: BTreeSet<&String> = before
+            .balances
+            .keys()
+            .chain(after.balances.keys())
+            .collect();
+        for denom in denoms {
+            let (old, new) = (before.balance_of(denom), after.balance_of(denom));
+            if old != new {
+                balance_diff.push(BalanceDiff {
+                    address: address.clone(),
+                    denom: denom.clone(),
+                    before: old,
+                    after: new,
+                });
+            }
+        }
+    }
+
+    SimulationResult {
+        result,
+        balance_diff,
+    }
 }
 
 /// Execute a transaction with multiple messages
@@ -150,15 +286,20 @@ fn execute_multi_message_transaction(
     block_height: u64,
     tx_index: u32,
     gas_schedule: &GasSchedule,
-    fee_burn_engine: Option<&mut FeeBurnEngine>,
+    mut modules: ExecutionModules<'_>,
 ) -> ExecutionResult {
     // Step 1: Validate basic transaction fields
     if let Err(error) = validate_transaction(tx, state) {
         return ExecutionResult {
-            receipt: create_failed_receipt(tx, 0, 0, 0, error.to_string(), block_height, tx_index),
+            receipt: create_failed_receipt(tx, 0, 0, 0, error.to_string(), block_height, tx_index, gas_schedule.version),
             state_changes: Vec::new(),
             gas_used: 0,
             success: false,
+            events: Vec::new(),
+            gas_operations: HashMap::new(),
+            fee_refunded: 0,
+            fee_charged: 0,
+            fee_burned: 0,
         };
     }
 
@@ -177,90 +318,94 @@ fn execute_multi_message_transaction(
         Ok(fee) => fee,
         Err(error) => {
             return ExecutionResult {
-                receipt: create_failed_receipt(tx, 0, gas_limit, gas_price, error.to_string(), block_height, tx_index),
+                receipt: create_failed_receipt(tx, 0, gas_limit, gas_price, error.to_string(), block_height, tx_index, gas_schedule.version),
                 state_changes: Vec::new(),
                 gas_used: 0,
                 success: false,
+                events: Vec::new(),
+                gas_operations: HashMap::new(),
+                fee_refunded: 0,
+                fee_charged: 0,
+                fee_burned: 0,
             };
         }
     };
 
-    let sender_balance = state.balance_of(&tx.from, "udgt");
-    if sender_balance < upfront_fee {
-        let err_msg = format!("InsufficientFunds: required {upfront_fee}, available {sender_balance}");
-        return ExecutionResult {
-            receipt: create_failed_receipt(tx, 0, gas_limit, gas_price, err_msg, block_height, tx_index),
-            state_changes: Vec::new(),
-            gas_used: 0,
-            success: false,
-        };
-    }
+    let allowance = match charge_upfront_fee(tx, state, block_height, upfront_fee) {
+        Ok(allowance) => allowance,
+        Err(err_msg) => {
+            return ExecutionResult {
+                receipt: create_failed_receipt(tx, 0, gas_limit, gas_price, err_msg, block_height, tx_index, gas_schedule.version),
+                state_changes: Vec::new(),
+                gas_used: 0,
+                success: false,
+                events: Vec::new(),
+                gas_operations: HashMap::new(),
+                fee_refunded: 0,
+                fee_charged: 0,
+                fee_burned: 0,
+            };
+        }
+    };
 
-    // Step 5: Deduct upfront fee and consume nonce
-    let new_sender_balance = sender_balance - upfront_fee;
-    state.set_balance(&tx.from, "udgt", new_sender_balance);
+    // Step 5: Upfront fee is deducted from the fee payer; consume the sender's nonce
     state.increment_nonce(&tx.from);
 
     // Step 6: Consume minimal overhead gas
-    if let Err(_) = ctx.consume_gas(1, "tx_overhead") {
-        return ExecutionResult {
-            receipt: create_failed_receipt(tx, ctx.gas_used(), gas_limit, gas_price, "OutOfGas".to_string(), block_height, tx_index),
-            state_changes: Vec::new(),
-            gas_used: ctx.gas_used(),
-            success: false,
-        };
+    if ctx.consume_gas(1, "tx_overhead").is_err() {
+        let error = "OutOfGas".to_string();
+        return charged_failure(tx, state, &ctx, gas_schedule, allowance, error, true, block_height, tx_index);
     }
 
     // Step 7: Calculate intrinsic gas
-    let tx_size = estimate_transaction_size(tx);
-    let intrinsic_gas = match intrinsic_gas(&TxKind::Transfer, tx_size, messages.len(), gas_schedule) {
+    let tx_size = tx.encoded_size();
+    let intrinsic_gas = match intrinsic_gas(&TxKind::Transfer, tx_size, tx.additional_signatures(), gas_schedule) {
         Ok(gas) => gas,
         Err(error) => {
-            return ExecutionResult {
-                receipt: create_failed_receipt(tx, ctx.gas_used(), gas_limit, gas_price, error.to_string(), block_height, tx_index),
-                state_changes: Vec::new(),
-                gas_used: ctx.gas_used(),
-                success: false,
-            };
+            let error = error.to_string();
+            return charged_failure(tx, state, &ctx, gas_schedule, allowance, error, false, block_height, tx_index);
         }
     };
 
-    if let Err(_) = ctx.consume_gas(intrinsic_gas, "intrinsic") {
-        ctx.revert_state_changes(state);
-        return ExecutionResult {
-            receipt: create_failed_receipt(tx, ctx.gas_used(), gas_limit, gas_price, "OutOfGas".to_string(), block_height, tx_index),
-            state_changes: Vec::new(),
-            gas_used: ctx.gas_used(),
-            success: false,
-        };
+    if ctx.consume_gas(intrinsic_gas, "intrinsic").is_err() {
+        let error = "OutOfGas".to_string();
+        return charged_failure(tx, state, &ctx, gas_schedule, allowance, error, true, block_height, tx_index);
     }
 
-    // Step 8: Execute each message
+    // Step 8: Execute each message against an overlay so the messages apply all-or-nothing
+    let mut overlay = StateOverlay::new(state);
     for (idx, msg) in messages.iter().enumerate() {
-        if let Err(e) = execute_message(msg, state, &mut ctx, block_height) {
-            // Out of gas during execution - revert state but keep fee
-            ctx.revert_state_changes(state);
-            return ExecutionResult {
-                receipt: create_failed_receipt(tx, ctx.gas_used(), gas_limit, gas_price, format!("Execution failed at message {}: {}", idx, e), block_height, tx_index),
-                state_changes: Vec::new(),
-                gas_used: ctx.gas_used(),
-                success: false,
-            };
+        if let Err(e) = execute_message(msg, &mut overlay, &mut ctx, block_height, gas_schedule, &mut modules) {
+            // Dropping the overlay discards every message's writes; the fee is settled
+            let out_of_gas = matches!(e, GasError::OutOfGas { .. });
+            let error = format!("Execution failed at message {}: {}", idx, e);
+            return charged_failure(tx, state, &ctx, gas_schedule, allowance, error, out_of_gas, block_height, tx_index);
         }
     }
 
-    // Step 9: Process fee burning if enabled
-    if let Some(engine) = fee_burn_engine {
-        let _ = engine.process_fee_burn(tx.hash.clone(), block_height, upfront_fee, state);
+    // Step 9: All messages succeeded - commit the overlay
+    let state_changes = overlay.commit(state);
+    if let Some(governance) = modules.governance.as_deref_mut() {
+        governance.record_events(std::mem::take(&mut ctx.governance_events));
     }
 
-    // Step 10: Commit state changes and create success receipt
-    let state_changes = ctx.state_changes.clone();
+    // Step 10: Refund unused gas to the fee payer
+    let charged_gas = gas_schedule.charged_gas(ctx.gas_used(), gas_limit);
+    let settlement = settle_fee(tx, state, &ctx, charged_gas, allowance);
+
+    // Step 11: Create success receipt (fee burning is applied by the caller)
+    let mut receipt = create_success_receipt(tx, ctx.gas_used(), gas_limit, gas_price, block_height, tx_index, ctx.events.clone(), gas_schedule.version);
+    receipt.gas_refund = settlement.refund_gas;
     ExecutionResult {
         success: true,
         state_changes,
         gas_used: ctx.gas_used(),
-        receipt: create_success_receipt(tx, ctx.gas_used(), gas_limit, gas_price, block_height, tx_index),
+        receipt,
+        events: ctx.events.clone(),
+        gas_operations: ctx.gas_meter.operations().clone(),
+        fee_refunded: settlement.refunded_fee,
+        fee_charged: settlement.charged_fee,
+        fee_burned: 0,
     }
 }
 
@@ -270,16 +415,24 @@ fn execute_message(
     state: &mut State,
     ctx: &mut ExecutionContext,
     block_height: u64,
+    gas_schedule: &GasSchedule,
+    modules: &mut ExecutionModules<'_>,
 ) -> Result<(), GasError> {
     use crate::storage::tx::TxMessage;
-    
+
+    if let Some(module) = pausable_module(msg) {
+        PauseModule::with_store(&mut *state)
+            .ensure_active(module)
+            .map_err(GasError::Custom)?;
+    }
+
     match msg {
         TxMessage::Send { from, to, denom, amount } => {
             // Charge gas for KV operations
-            ctx.consume_gas(40, "kv_read_from")?;
-            ctx.consume_gas(40, "kv_read_to")?;
-            ctx.consume_gas(120, "kv_write_from")?;
-            ctx.consume_gas(120, "kv_write_to")?;
+            ctx.consume_gas(gas_schedule.per_kv_read, "kv_read_from")?;
+            ctx.consume_gas(gas_schedule.per_kv_read, "kv_read_to")?;
+            ctx.consume_gas(gas_schedule.per_kv_write, "kv_write_from")?;
+            ctx.consume_gas(gas_schedule.per_kv_write, "kv_write_to")?;
 
             // Record the transfer state changes
             let sender_old_balance = state.balance_of(from, denom);
@@ -293,70 +446,655 @@ fn execute_message(
                     available: sender_old_balance as u64,
                 });
             }
+            // Vesting lockups are part of the balance but cannot be sent
+            let spendable = spendable_balance(state, from, denom, block_height);
+            if spendable < *amount {
+                return Err(GasError::Custom(format!(
+                    "insufficient spendable {denom}: {spendable} < {amount} (rest is vesting)"
+                )));
+            }
 
             let sender_new_balance = sender_old_balance - amount;
             let recipient_new_balance = recipient_old_balance + amount;
 
-            ctx.record_state_change(from.clone(), denom.clone(), sender_old_balance, sender_new_balance);
-            ctx.record_state_change(to.clone(), denom.clone(), recipient_old_balance, recipient_new_balance);
 
             // Apply the transfer
             state.set_balance(from, denom, sender_new_balance);
             state.set_balance(to, denom, recipient_new_balance);
+            ctx.emit_event(
+                Event::new("transfer")
+                    .attr("from", from)
+                    .attr("to", to)
+                    .attr("amount", amount)
+                    .attr("denom", denom),
+                gas_schedule,
+            )?;
 
             Ok(())
         }
-        TxMessage::Data { data, .. } => {
+        TxMessage::Data { from, data } => {
             // Charge gas proportional to data size (1 gas per byte)
             let data_size = data.len() as u64;
             ctx.consume_gas(data_size, "data_storage")?;
+            ctx.emit_event(
+                Event::new("data").attr("from", from).attr("bytes", data_size),
+                gas_schedule,
+            )?;
             
             // Data messages don't modify state - they're just anchored on-chain
             // The data is already stored in the transaction itself
             Ok(())
         }
         TxMessage::DmsRegister { from, beneficiary, period } => {
-            ctx.consume_gas(1000, "dms_register")?;
-            let dms = crate::runtime::dead_man_switch::DeadManSwitchModule::new(state.storage.clone());
-            dms.register(from, beneficiary, *period as u64, block_height)
-                .map_err(|e| GasError::Custom(e))?;
+            ctx.consume_gas(gas_schedule.dms_register_base, "dms_register")?;
+            dms_module(state).register(from, beneficiary, *period as u64, block_height)
+                .map_err(GasError::Custom)?;
+            ctx.emit_event(
+                Event::new("dms_register")
+                    .attr("owner", from)
+                    .attr("beneficiary", beneficiary)
+                    .attr("period", period),
+                gas_schedule,
+            )?;
             Ok(())
         }
         TxMessage::DmsPing { from } => {
-            ctx.consume_gas(500, "dms_ping")?;
-            let dms = crate::runtime::dead_man_switch::DeadManSwitchModule::new(state.storage.clone());
-            dms.ping(from, block_height)
-                .map_err(|e| GasError::Custom(e))?;
+            ctx.consume_gas(gas_schedule.dms_ping_base, "dms_ping")?;
+            dms_module(state).ping(from, block_height)
+                .map_err(GasError::Custom)?;
+            ctx.emit_event(
+                Event::new("dms_ping").attr("owner", from),
+                gas_schedule,
+            )?;
             Ok(())
         }
         TxMessage::DmsClaim { from, owner } => {
-            ctx.consume_gas(2000, "dms_claim")?;
-            let dms = crate::runtime::dead_man_switch::DeadManSwitchModule::new(state.storage.clone());
-            let beneficiary = dms.validate_claim(owner, from, block_height)
-                .map_err(|e| GasError::Custom(e))?;
+            ctx.consume_gas(gas_schedule.dms_claim_base, "dms_claim")?;
+            let beneficiary = dms_module(state).validate_claim(owner, from, block_height)
+                .map_err(GasError::Custom)?;
             
-            // Transfer all funds
+            // Transfer all spendable funds; tokens still locked by vesting stay with the owner
             let balances = state.balances_of(owner);
-            for (denom, amount) in balances {
+            for (denom, balance) in balances {
+                let amount = spendable_balance(state, owner, &denom, block_height);
                 if amount > 0 {
-                    let owner_old = amount;
                     let beneficiary_old = state.balance_of(&beneficiary, &denom);
-                    
-                    let owner_new = 0;
-                    let beneficiary_new = beneficiary_old + amount;
-                    
-                    ctx.record_state_change(owner.clone(), denom.clone(), owner_old, owner_new);
-                    ctx.record_state_change(beneficiary.clone(), denom.clone(), beneficiary_old, beneficiary_new);
-                    
-                    state.set_balance(owner, &denom, owner_new);
-                    state.set_balance(&beneficiary, &denom, beneficiary_new);
+                    state.set_balance(owner, &denom, balance - amount);
+                    state.set_balance(&beneficiary, &denom, beneficiary_old + amount);
                 }
             }
+            ctx.emit_event(
+                Event::new("dms_claim")
+                    .attr("owner", owner)
+                    .attr("beneficiary", &beneficiary),
+                gas_schedule,
+            )?;
+            Ok(())
+        }
+        // Contract stores are written by the runtime directly; staking and governance
+        // state goes through the overlay and is discarded if a later message fails.
+        TxMessage::Delegate { from, validator, amount } => {
+            ctx.consume_gas(gas_schedule.base_cost(&TxKind::StakingDelegate), "staking_delegate")?;
+            let old_balance = state.balance_of(from, "udgt");
+            if old_balance < *amount {
+                return Err(GasError::Custom(format!(
+                    "insufficient udgt to delegate: {old_balance} < {amount}"
+                )));
+            }
+            staking_module(modules, state)?
+                .delegate(from, validator, *amount)
+                .map_err(GasError::Custom)?;
+            // Locked vesting tokens may be bonded
+            vesting_module(state)
+                .track_delegation(from, block_height, *amount)
+                .map_err(GasError::Custom)?;
+            send_to_module(state, from, STAKING_BONDED, "udgt", *amount).map_err(GasError::Custom)?;
+            ctx.emit_event(
+                Event::new("delegate")
+                    .attr("delegator", from)
+                    .attr("validator", validator)
+                    .attr("amount", amount)
+                    .attr("denom", "udgt"),
+                gas_schedule,
+            )?;
+            Ok(())
+        }
+        TxMessage::Undelegate { from, validator, amount } => {
+            ctx.consume_gas(gas_schedule.base_cost(&TxKind::StakingUndelegate), "staking_undelegate")?;
+            staking_module(modules, state)?
+                .undelegate(from, validator, *amount)
+                .map_err(GasError::Custom)?;
+            vesting_module(state)
+                .track_undelegation(from, *amount)
+                .map_err(GasError::Custom)?;
+            send_from_module(state, STAKING_BONDED, from, "udgt", *amount).map_err(GasError::Custom)?;
+            ctx.emit_event(
+                Event::new("undelegate")
+                    .attr("delegator", from)
+                    .attr("validator", validator)
+                    .attr("amount", amount)
+                    .attr("denom", "udgt"),
+                gas_schedule,
+            )?;
+            Ok(())
+        }
+        TxMessage::ClaimRewards { from } => {
+            // No dedicated claim kind in the schedule; priced like a delegation update
+            ctx.consume_gas(gas_schedule.base_cost(&TxKind::StakingDelegate), "staking_claim_rewards")?;
+            // Rewards are paid out of the staking emission pool; check it before the
+            // claim settles the delegator record
+            let pool = emission_account("staking_rewards");
+            let accrued = staking_module(modules, state)?.get_accrued_rewards(from);
+            let available = module_balance(state, &pool, "udrt");
+            if available < accrued {
+                return Err(GasError::Custom(format!(
+                    "staking reward pool holds {available} udrt, {accrued} accrued"
+                )));
+            }
+            let claimed = staking_module(modules, state)?.claim_rewards(from);
+            if claimed > 0 {
+                send_from_module(state, &pool, from, "udrt", claimed).map_err(GasError::Custom)?;
+            }
+            ctx.emit_event(
+                Event::new("claim_rewards")
+                    .attr("delegator", from)
+                    .attr("amount", claimed)
+                    .attr("denom", "udrt"),
+                gas_schedule,
+            )?;
+            Ok(())
+        }
+        TxMessage::SubmitProposal { from, title, description, proposal, track } => {
+            ctx.consume_gas(gas_schedule.base_cost(&TxKind::GovernanceProposalCreate), "gov_submit_proposal")?;
+            let governance = governance_module(modules)?;
+            let proposal_id = governance
+                .submit_proposal_in_block(
+                    state,
+                    block_height,
+                    title.clone(),
+                    description.clone(),
+                    proposal.clone(),
+                    *track,
+                    &mut ctx.governance_events,
+                )
+                .map_err(GasError::Custom)?;
+            ctx.emit_event(
+                Event::new("submit_proposal")
+                    .attr("proposer", from)
+                    .attr("proposal_id", proposal_id)
+                    .attr("track", track.as_str()),
+                gas_schedule,
+            )?;
+            Ok(())
+        }
+        TxMessage::Deposit { from, proposal_id, amount } => {
+            ctx.consume_gas(gas_schedule.base_cost(&TxKind::GovernanceVote), "gov_deposit")?;
+            let spendable = spendable_balance(state, from, "udgt", block_height);
+            if spendable < *amount {
+                return Err(GasError::Custom(format!(
+                    "insufficient spendable udgt to deposit: {spendable} < {amount}"
+                )));
+            }
+            let governance = governance_module(modules)?;
+            governance
+                .deposit_in_block(state, block_height, from, *proposal_id, *amount, "udgt", &mut ctx.governance_events)
+                .map_err(GasError::Custom)?;
+            ctx.emit_event(
+                Event::new("proposal_deposit")
+                    .attr("depositor", from)
+                    .attr("proposal_id", proposal_id)
+                    .attr("amount", amount)
+                    .attr("denom", "udgt"),
+                gas_schedule,
+            )?;
+            Ok(())
+        }
+        TxMessage::Vote { from, proposal_id, option } => {
+            ctx.consume_gas(gas_schedule.base_cost(&TxKind::GovernanceVote), "gov_vote")?;
+            let governance = governance_module(modules)?;
+            governance
+                .vote_in_block(state, block_height, from, *proposal_id, option.clone(), &mut ctx.governance_events)
+                .map_err(GasError::Custom)?;
+            ctx.emit_event(
+                Event::new("proposal_vote")
+                    .attr("voter", from)
+                    .attr("proposal_id", proposal_id)
+                    .attr("option", format!("{option:?}")),
+                gas_schedule,
+            )?;
+            Ok(())
+        }
+        TxMessage::WeightedVote { from, proposal_id, options } => {
+            ctx.consume_gas(gas_schedule.base_cost(&TxKind::GovernanceVote), "gov_vote")?;
+            let governance = governance_module(modules)?;
+            governance
+                .weighted_vote_in_block(state, block_height, from, *proposal_id, options.clone(), &mut ctx.governance_events)
+                .map_err(GasError::Custom)?;
+            let split = options
+                .iter()
+                .map(|o| format!("{:?}={}", o.option, format_weight(o.weight)))
+                .collect::<Vec<_>>()
+                .join(",");
+            ctx.emit_event(
+                Event::new("proposal_vote")
+                    .attr("voter", from)
+                    .attr("proposal_id", proposal_id)
+                    .attr("option", split),
+                gas_schedule,
+            )?;
+            Ok(())
+        }
+        TxMessage::ContractDeploy { from, code } => {
+            ctx.consume_gas(gas_schedule.base_cost(&TxKind::ContractInstantiate), "contract_deploy")?;
+            let code = hex::decode(code.trim_start_matches("0x"))
+                .map_err(|e| GasError::Custom(format!("invalid contract code hex: {e}")))?;
+            deploy_contract(modules, ctx, gas_schedule, from, &code)
+        }
+        TxMessage::ContractCall { from, contract, method, args } => {
+            ctx.consume_gas(gas_schedule.base_cost(&TxKind::ContractCall), "contract_call")?;
+            let args = hex::decode(args.trim_start_matches("0x"))
+                .map_err(|e| GasError::Custom(format!("invalid contract args hex: {e}")))?;
+            call_contract(modules, ctx, gas_schedule, from, contract, method, &args)
+        }
+        TxMessage::GrantFeeAllowance { from, grantee, spend_limit, expiration_height } => {
+            ctx.consume_gas(gas_schedule.per_kv_write, "fee_grant")?;
+            fee_grant_module(state)
+                .grant(from, grantee, *spend_limit, *expiration_height, block_height)
+                .map_err(GasError::Custom)?;
+            ctx.emit_event(
+                Event::new("fee_grant")
+                    .attr("granter", from)
+                    .attr("grantee", grantee)
+                    .attr("spend_limit", spend_limit),
+                gas_schedule,
+            )?;
+            Ok(())
+        }
+        TxMessage::RevokeFeeAllowance { from, grantee } => {
+            ctx.consume_gas(gas_schedule.per_kv_write, "fee_grant_revoke")?;
+            fee_grant_module(state)
+                .revoke(from, grantee)
+                .map_err(GasError::Custom)?;
+            ctx.emit_event(
+                Event::new("fee_grant_revoke")
+                    .attr("granter", from)
+                    .attr("grantee", grantee),
+                gas_schedule,
+            )?;
+            Ok(())
+        }
+        TxMessage::ScheduleTransfer { from, to, denom, amount, release_height, cancellable } => {
+            // Balance read/write plus the schedule record and its three index entries
+            ctx.consume_gas(gas_schedule.per_kv_read + 5 * gas_schedule.per_kv_write, "schedule_transfer")?;
+            let spendable = spendable_balance(state, from, denom, block_height);
+            if spendable < *amount {
+                return Err(GasError::Custom(format!(
+                    "insufficient spendable {denom} to schedule: {spendable} < {amount}"
+                )));
+            }
+            send_to_module(state, from, SCHEDULE_ESCROW, denom, *amount).map_err(GasError::Custom)?;
+            let schedule = schedule_module(state)
+                .create(from, to, denom, *amount, *release_height, *cancellable, block_height)
+                .map_err(GasError::Custom)?;
+            ctx.emit_event(
+                Event::new("schedule_transfer")
+                    .attr("id", schedule.id)
+                    .attr("from", from)
+                    .attr("to", to)
+                    .attr("amount", amount)
+                    .attr("denom", denom)
+                    .attr("release_height", release_height),
+                gas_schedule,
+            )?;
+            Ok(())
+        }
+        TxMessage::CancelSchedule { from, id } => {
+            ctx.consume_gas(gas_schedule.per_kv_read + 5 * gas_schedule.per_kv_write, "schedule_cancel")?;
+            let schedule = schedule_module(state)
+                .cancel(from, *id, block_height)
+                .map_err(GasError::Custom)?;
+            send_from_module(state, SCHEDULE_ESCROW, &schedule.from, &schedule.denom, schedule.amount)
+                .map_err(GasError::Custom)?;
+            ctx.emit_event(
+                Event::new("schedule_cancel")
+                    .attr("id", id)
+                    .attr("from", from)
+                    .attr("amount", schedule.amount)
+                    .attr("denom", &schedule.denom),
+                gas_schedule,
+            )?;
+            Ok(())
+        }
+        TxMessage::CreateDenom { from, subdenom, symbol, decimals } => {
+            // Existence check plus the denom record and the creator index entry
+            ctx.consume_gas(gas_schedule.per_kv_read + 2 * gas_schedule.per_kv_write, "create_denom")?;
+            let record = token_factory_module(state)
+                .create_denom(from, subdenom, symbol, *decimals, block_height)
+                .map_err(GasError::Custom)?;
+            ctx.emit_event(
+                Event::new("create_denom")
+                    .attr("creator", from)
+                    .attr("denom", &record.denom)
+                    .attr("symbol", symbol)
+                    .attr("decimals", decimals),
+                gas_schedule,
+            )?;
+            Ok(())
+        }
+        TxMessage::Mint { from, denom, amount, to } => {
+            ctx.consume_gas(2 * gas_schedule.per_kv_read + 2 * gas_schedule.per_kv_write, "mint")?;
+            let record = token_factory_module(state)
+                .mint(from, denom, *amount)
+                .map_err(GasError::Custom)?;
+            let recipient = to.as_deref().unwrap_or(from);
+            state.credit(recipient, denom, *amount);
+            ctx.emit_event(
+                Event::new("mint")
+                    .attr("admin", from)
+                    .attr("to", recipient)
+                    .attr("amount", amount)
+                    .attr("denom", denom)
+                    .attr("supply", record.supply),
+                gas_schedule,
+            )?;
+            Ok(())
+        }
+        TxMessage::Burn { from, denom, amount } => {
+            ctx.consume_gas(2 * gas_schedule.per_kv_read + 2 * gas_schedule.per_kv_write, "burn")?;
+            let spendable = spendable_balance(state, from, denom, block_height);
+            if spendable < *amount {
+                return Err(GasError::Custom(format!(
+                    "insufficient spendable {denom} to burn: {spendable} < {amount}"
+                )));
+            }
+            let record = token_factory_module(state)
+                .burn(from, denom, *amount)
+                .map_err(GasError::Custom)?;
+            let balance = state.balance_of(from, denom);
+            state.set_balance(from, denom, balance - amount);
+            ctx.emit_event(
+                Event::new("burn")
+                    .attr("admin", from)
+                    .attr("amount", amount)
+                    .attr("denom", denom)
+                    .attr("supply", record.supply),
+                gas_schedule,
+            )?;
+            Ok(())
+        }
+        TxMessage::ChangeAdmin { from, denom, new_admin } => {
+            ctx.consume_gas(gas_schedule.per_kv_read + gas_schedule.per_kv_write, "change_admin")?;
+            token_factory_module(state)
+                .change_admin(from, denom, new_admin)
+                .map_err(GasError::Custom)?;
+            ctx.emit_event(
+                Event::new("change_admin")
+                    .attr("denom", denom)
+                    .attr("admin", from)
+                    .attr("new_admin", new_admin),
+                gas_schedule,
+            )?;
             Ok(())
         }
     }
 }
 
+/// Staking module writing through `state`, if staking is enabled
+fn staking_module<'s>(
+    modules: &ExecutionModules<'_>,
+    state: &'s mut State,
+) -> Result<StakingModule<&'s mut State>, GasError> {
+    if modules.staking.is_none() {
+        return Err(GasError::Custom("staking module not available".to_string()));
+    }
+    Ok(StakingModule::with_store(state))
+}
+
+/// Module a message is refused by while governance has it paused
+fn pausable_module(msg: &crate::storage::tx::TxMessage) -> Option<&'static str> {
+    use crate::storage::tx::TxMessage;
+
+    match msg {
+        TxMessage::DmsRegister { .. } | TxMessage::DmsPing { .. } | TxMessage::DmsClaim { .. } => Some("dms"),
+        TxMessage::Delegate { .. } | TxMessage::Undelegate { .. } | TxMessage::ClaimRewards { .. } => Some("staking"),
+        TxMessage::ContractDeploy { .. } | TxMessage::ContractCall { .. } => Some("contracts"),
+        TxMessage::GrantFeeAllowance { .. } | TxMessage::RevokeFeeAllowance { .. } => Some("fee_grant"),
+        TxMessage::ScheduleTransfer { .. } | TxMessage::CancelSchedule { .. } => Some("schedule"),
+        TxMessage::CreateDenom { .. }
+        | TxMessage::Mint { .. }
+        | TxMessage::Burn { .. }
+        | TxMessage::ChangeAdmin { .. } => Some("token_factory"),
+        TxMessage::Send { .. }
+        | TxMessage::Data { .. }
+        | TxMessage::SubmitProposal { .. }
+        | TxMessage::Deposit { .. }
+        | TxMessage::Vote { .. }
+        | TxMessage::WeightedVote { .. } => None,
+    }
+}
+
+fn governance_module<'m>(
+    modules: &'m ExecutionModules<'_>,
+) -> Result<&'m GovernanceModule, GasError> {
+    modules
+        .governance
+        .as_deref()
+        .ok_or_else(|| GasError::Custom("governance module not available".to_string()))
+}
+
+#[cfg(feature = "contracts")]
+fn deploy_contract(
+    modules: &ExecutionModules<'_>,
+    ctx: &mut ExecutionContext,
+    gas_schedule: &GasSchedule,
+    from: &str,
+    code: &[u8],
+) -> Result<(), GasError> {
+    let wasm = modules
+        .wasm
+        .ok_or_else(|| GasError::Custom("contract runtime not available".to_string()))?;
+    let deployment = wasm
+        .deploy_contract(code, from, ctx.gas_meter.remaining_gas(), None)
+        .map_err(|e| GasError::Custom(format!("contract deploy failed: {e}")))?;
+    ctx.consume_gas(deployment.gas_used, "contract_execution")?;
+    ctx.emit_event(
+        Event::new("contract_deploy")
+            .attr("deployer", from)
+            .attr("address", &deployment.address),
+        gas_schedule,
+    )?;
+    Ok(())
+}
+
+#[cfg(feature = "contracts")]
+fn call_contract(
+    modules: &ExecutionModules<'_>,
+    ctx: &mut ExecutionContext,
+    gas_schedule: &GasSchedule,
+    from: &str,
+    contract: &str,
+    method: &str,
+    args: &[u8],
+) -> Result<(), GasError> {
+    let wasm = modules
+        .wasm
+        .ok_or_else(|| GasError::Custom("contract runtime not available".to_string()))?;
+    let execution = wasm
+        .execute_contract(&contract.to_string(), method, args, ctx.gas_meter.remaining_gas())
+        .map_err(|e| GasError::Custom(format!("contract call failed: {e}")))?;
+    ctx.consume_gas(execution.gas_used, "contract_execution")?;
+    ctx.emit_event(
+        Event::new("contract_call")
+            .attr("caller", from)
+            .attr("contract", contract)
+            .attr("method", method),
+        gas_schedule,
+    )?;
+    Ok(())
+}
+
+#[cfg(not(feature = "contracts"))]
+fn deploy_contract(
+    _modules: &ExecutionModules<'_>,
+    _ctx: &mut ExecutionContext,
+    _gas_schedule: &GasSchedule,
+    _from: &str,
+    _code: &[u8],
+) -> Result<(), GasError> {
+    Err(GasError::Custom("contracts feature is disabled".to_string()))
+}
+
+#[cfg(not(feature = "contracts"))]
+fn call_contract(
+    _modules: &ExecutionModules<'_>,
+    _ctx: &mut ExecutionContext,
+    _gas_schedule: &GasSchedule,
+    _from: &str,
+    _contract: &str,
+    _method: &str,
+    _args: &[u8],
+) -> Result<(), GasError> {
+    Err(GasError::Custom("contracts feature is disabled".to_string()))
+}
+
+/// Deduct the upfront fee from the fee payer (the sponsor when `fee_payer` is set, otherwise
+/// the sender). A sponsor that did not co-sign must have granted the sender a fee allowance;
+/// the allowance as it was before the charge is returned so it can be settled later.
+fn charge_upfront_fee(
+    tx: &Transaction,
+    state: &mut State,
+    block_height: u64,
+    upfront_fee: u128,
+) -> Result<Option<FeeAllowance>, String> {
+    let payer = tx.fee_payer_or_sender();
+    let payer_balance = state.balance_of(payer, "udgt");
+    // Fees cannot be paid from vesting lockups either
+    let available = spendable_balance(state, payer, "udgt", block_height);
+    if available < upfront_fee {
+        return Err(format!(
+            "InsufficientFunds: required {upfront_fee}, available {available}"
+        ));
+    }
+    let mut allowance = None;
+    if tx.fee_payer.is_some() && tx.fee_payer_signature.is_none() {
+        let mut grants = fee_grant_module(state);
+        let checked = grants
+            .check(payer, &tx.from, upfront_fee, block_height)
+            .map_err(|e| format!("FeeAllowance: {e}"))?;
+        grants
+            .settle(checked.clone(), upfront_fee)
+            .map_err(|e| format!("FeeAllowance: {e}"))?;
+        allowance = Some(checked);
+    }
+    state.set_balance(payer, "udgt", payer_balance - upfront_fee);
+    Ok(allowance)
+}
+
+/// Fee accounting of a transaction that paid its upfront fee
+struct FeeSettlement {
+    refund_gas: Gas,
+    charged_fee: u128,
+    refunded_fee: u128,
+}
+
+/// Refund the upfront fee beyond `charged_gas` to the fee payer; a fee allowance the
+/// upfront fee was drawn from is rebased onto the charged fee.
+fn settle_fee(
+    tx: &Transaction,
+    state: &mut State,
+    ctx: &ExecutionContext,
+    charged_gas: Gas,
+    allowance: Option<FeeAllowance>,
+) -> FeeSettlement {
+    let refund_gas = ctx.gas_limit - charged_gas;
+    let settlement = FeeSettlement {
+        refund_gas,
+        charged_fee: charged_gas as u128 * ctx.gas_price as u128,
+        refunded_fee: refund_gas as u128 * ctx.gas_price as u128,
+    };
+    if settlement.refunded_fee > 0 {
+        let payer = tx.fee_payer_or_sender();
+        let balance = state.balance_of(payer, "udgt");
+        state.set_balance(payer, "udgt", balance + settlement.refunded_fee);
+        if let Some(allowance) = allowance {
+            // Like other post-execution writes, a storage error here is not fatal
+            let _ = fee_grant_module(state).settle(allowance, settlement.charged_fee);
+        }
+    }
+    settlement
+}
+
+/// Result of a transaction that failed after paying its upfront fee. Its writes are
+/// discarded, but its fee is settled like a successful one's: only the gas used (subject to
+/// the schedule's minimum charge) is paid for, unless it ran out of gas and so used up its
+/// whole limit.
+#[allow(clippy::too_many_arguments)]
+fn charged_failure(
+    tx: &Transaction,
+    state: &mut State,
+    ctx: &ExecutionContext,
+    gas_schedule: &GasSchedule,
+    allowance: Option<FeeAllowance>,
+    error: String,
+    out_of_gas: bool,
+    block_height: u64,
+    tx_index: u32,
+) -> ExecutionResult {
+    let charged_gas = if out_of_gas {
+        ctx.gas_limit
+    } else {
+        gas_schedule.charged_gas(ctx.gas_used(), ctx.gas_limit)
+    };
+    let settlement = settle_fee(tx, state, ctx, charged_gas, allowance);
+    let mut receipt = create_failed_receipt(
+        tx,
+        ctx.gas_used(),
+        ctx.gas_limit,
+        ctx.gas_price,
+        error,
+        block_height,
+        tx_index,
+        gas_schedule.version,
+    );
+    receipt.gas_refund = settlement.refund_gas;
+    ExecutionResult {
+        receipt,
+        state_changes: Vec::new(),
+        gas_used: ctx.gas_used(),
+        success: false,
+        events: ctx.events.clone(),
+        gas_operations: ctx.gas_meter.operations().clone(),
+        fee_refunded: settlement.refunded_fee,
+        fee_charged: settlement.charged_fee,
+        fee_burned: 0,
+    }
+}
+
+/// Fee grant module writing through `state` (buffered when it is a fork or overlay)
+fn fee_grant_module(state: &mut State) -> FeeGrantModule<&mut State> {
+    FeeGrantModule::with_store(state)
+}
+
+/// Scheduled transfer module writing through `state` (buffered when it is a fork or overlay)
+fn schedule_module(state: &mut State) -> ScheduleModule<&mut State> {
+    ScheduleModule::with_store(state)
+}
+
+/// Token factory module writing through `state` (buffered when it is a fork or overlay)
+fn token_factory_module(state: &mut State) -> TokenFactoryModule<&mut State> {
+    TokenFactoryModule::with_store(state)
+}
+
+/// Vesting account module writing through `state` (buffered when it is a fork or overlay)
+fn vesting_module(state: &mut State) -> VestingModule<&mut State> {
+    VestingModule::with_store(state)
+}
+
+/// Dead man switch module writing through `state` (buffered when it is a fork or overlay)
+fn dms_module(state: &mut State) -> DeadManSwitchModule<&mut State> {
+    DeadManSwitchModule::with_store(state)
+}
+
 /// Execute a single-message transaction (legacy path for backward compatibility)
 fn execute_single_message_transaction(
     tx: &Transaction,
@@ -364,15 +1102,19 @@ fn execute_single_message_transaction(
     block_height: u64,
     tx_index: u32,
     gas_schedule: &GasSchedule,
-    fee_burn_engine: Option<&mut FeeBurnEngine>,
 ) -> ExecutionResult {
     // Step 1: Validate basic transaction fields
     if let Err(error) = validate_transaction(tx, state) {
         return ExecutionResult {
-            receipt: create_failed_receipt(tx, 0, 0, 0, error.to_string(), block_height, tx_index),
+            receipt: create_failed_receipt(tx, 0, 0, 0, error.to_string(), block_height, tx_index, gas_schedule.version),
             state_changes: Vec::new(),
             gas_used: 0,
             success: false,
+            events: Vec::new(),
+            gas_operations: HashMap::new(),
+            fee_refunded: 0,
+            fee_charged: 0,
+            fee_burned: 0,
         };
     }
 
@@ -401,38 +1143,47 @@ fn execute_single_message_transaction(
                     error.to_string(),
                     block_height,
                     tx_index,
+                    gas_schedule.version,
                 ),
                 state_changes: Vec::new(),
                 gas_used: 0,
                 success: false,
+                events: Vec::new(),
+                gas_operations: HashMap::new(),
+                fee_refunded: 0,
+                fee_charged: 0,
+                fee_burned: 0,
             };
         }
     };
 
-    let sender_balance = state.balance_of(&tx.from, "udgt");
-
-    if sender_balance < upfront_fee {
-        let err_msg =
-            format!("InsufficientFunds: required {upfront_fee}, available {sender_balance}");
-        return ExecutionResult {
-            receipt: create_failed_receipt(
-                tx,
-                0,
-                gas_limit,
-                gas_price,
-                err_msg,
-                block_height,
-                tx_index,
-            ),
-            state_changes: Vec::new(),
-            gas_used: 0,
-            success: false,
-        };
-    }
+    let allowance = match charge_upfront_fee(tx, state, block_height, upfront_fee) {
+        Ok(allowance) => allowance,
+        Err(err_msg) => {
+            return ExecutionResult {
+                receipt: create_failed_receipt(
+                    tx,
+                    0,
+                    gas_limit,
+                    gas_price,
+                    err_msg,
+                    block_height,
+                    tx_index,
+                    gas_schedule.version,
+                ),
+                state_changes: Vec::new(),
+                gas_used: 0,
+                success: false,
+                events: Vec::new(),
+                gas_operations: HashMap::new(),
+                fee_refunded: 0,
+                fee_charged: 0,
+                fee_burned: 0,
+            };
+        }
+    };
 
-    // Step 5: Deduct upfront fee immediately (non-revertible on failure)
-    let new_sender_balance = sender_balance - upfront_fee;
-    state.set_balance(&tx.from, "udgt", new_sender_balance);
+    // Step 5: Upfront fee was deducted from the fee payer above (non-revertible on failure)
 
     // Consume the nonce once the transaction has passed validation and paid the upfront fee.
     // This matches common blockchain semantics (e.g., Ethereum): nonce is consumed even if execution fails.
@@ -441,109 +1192,66 @@ fn execute_single_message_transaction(
     // Step 5.1: Consume minimal overhead gas to ensure non-zero gas_used in OOG scenarios
     if let Err(_gas_error) = ctx.consume_gas(1, "tx_overhead") {
         // Out of gas - nothing to revert (fee is non-revertible), return failure
-        return ExecutionResult {
-            receipt: create_failed_receipt(
-                tx,
-                ctx.gas_used(),
-                gas_limit,
-                gas_price,
-                "OutOfGas".to_string(),
-                block_height,
-                tx_index,
-            ),
-            state_changes: Vec::new(),
-            gas_used: ctx.gas_used(),
-            success: false,
-        };
+        let error = "OutOfGas".to_string();
+        return charged_failure(tx, state, &ctx, gas_schedule, allowance, error, true, block_height, tx_index);
     }
 
     // Step 6: Calculate intrinsic gas and charge it
-    let tx_size = estimate_transaction_size(tx);
-    let intrinsic_gas = match intrinsic_gas(&TxKind::Transfer, tx_size, 1, gas_schedule) {
+    let tx_size = tx.encoded_size();
+    let intrinsic_gas = match intrinsic_gas(&TxKind::Transfer, tx_size, tx.additional_signatures(), gas_schedule) {
         Ok(gas) => gas,
         Err(error) => {
-            // Fail but keep upfront fee
-            return ExecutionResult {
-                receipt: create_failed_receipt(
-                    tx,
-                    ctx.gas_used(),
-                    gas_limit,
-                    gas_price,
-                    error.to_string(),
-                    block_height,
-                    tx_index,
-                ),
-                state_changes: Vec::new(),
-                gas_used: ctx.gas_used(),
-                success: false,
-            };
+            // Fail but settle the upfront fee
+            let error = error.to_string();
+            return charged_failure(tx, state, &ctx, gas_schedule, allowance, error, false, block_height, tx_index);
         }
     };
 
     if let Err(_gas_error) = ctx.consume_gas(intrinsic_gas, "intrinsic") {
-        // Out of gas - revert any execution changes but keep fee
-        ctx.revert_state_changes(state);
-        return ExecutionResult {
-            receipt: create_failed_receipt(
-                tx,
-                ctx.gas_used(),
-                gas_limit,
-                gas_price,
-                "OutOfGas".to_string(),
-                block_height,
-                tx_index,
-            ),
-            state_changes: Vec::new(),
-            gas_used: ctx.gas_used(),
-            success: false,
-        };
+        // Out of gas - nothing executed yet, the whole limit is charged
+        let error = "OutOfGas".to_string();
+        return charged_failure(tx, state, &ctx, gas_schedule, allowance, error, true, block_height, tx_index);
     }
 
-    // Step 7: Execute the actual transfer
-    if let Err(_gas_error) = execute_transfer(tx, state, &mut ctx) {
-        // Out of gas during execution - revert state but keep fee
-        ctx.revert_state_changes(state);
-        return ExecutionResult {
-            receipt: create_failed_receipt(
-                tx,
-                ctx.gas_used(),
-                gas_limit,
-                gas_price,
-                "OutOfGas".to_string(),
-                block_height,
-                tx_index,
-            ),
-            state_changes: Vec::new(),
-            gas_used: ctx.gas_used(),
-            success: false,
+    // Step 7: Execute the actual transfer against an overlay
+    let mut overlay = StateOverlay::new(state);
+    if let Err(gas_error) = execute_transfer(tx, &mut overlay, &mut ctx, block_height, gas_schedule) {
+        // Out of gas or insufficient spendable funds - drop the overlay and settle the fee
+        let (error, out_of_gas) = match gas_error {
+            GasError::Custom(message) => (message, false),
+            _ => ("OutOfGas".to_string(), true),
         };
+        return charged_failure(tx, state, &ctx, gas_schedule, allowance, error, out_of_gas, block_height, tx_index);
     }
 
-    // Step 8: Success - Process fee burning if enabled
-    if let Some(engine) = fee_burn_engine {
-        let _ = engine.process_fee_burn(
-            tx.hash.clone(),
-            block_height,
-            upfront_fee,
-            state,
-        );
-        // Note: Fee burning errors are non-fatal and don't affect transaction success
-    }
+    let state_changes = overlay.commit(state);
 
-    // Step 9: Commit state changes and create success receipt
-    let state_changes = ctx.state_changes.clone();
+    // Step 8: Success - refund unused gas to the fee payer
+    let charged_gas = gas_schedule.charged_gas(ctx.gas_used(), gas_limit);
+    let settlement = settle_fee(tx, state, &ctx, charged_gas, allowance);
+
+    // Step 9: Create success receipt (fee burning is applied by the caller)
+    let mut receipt = create_success_receipt(
+        tx,
+        ctx.gas_used(),
+        gas_limit,
+        gas_price,
+        block_height,
+        tx_index,
+        ctx.events.clone(),
+        gas_schedule.version,
+    );
+    receipt.gas_refund = settlement.refund_gas;
     ExecutionResult {
         success: true,
         state_changes,
         gas_used: ctx.gas_used(),
-        receipt: create_success_receipt(
-            tx,
-            ctx.gas_used(),
-            gas_limit,
-            gas_price,
-            block_height,
-            tx_index,
-        ),
+        receipt,
+        events: ctx.events.clone(),
+        gas_operations: ctx.gas_meter.operations().clone(),
+        fee_refunded: settlement.refunded_fee,
+        fee_charged: settlement.charged_fee,
+        fee_burned: 0,
     }
 }
 
@@ -564,12 +1272,14 @@ fn execute_transfer(
     tx: &Transaction,
     state: &mut State,
     ctx: &mut ExecutionContext,
+    block_height: u64,
+    gas_schedule: &GasSchedule,
 ) -> Result<(), GasError> {
     // Charge gas for KV operations
-    ctx.consume_gas(40, "kv_read_from")?; // Read sender balance
-    ctx.consume_gas(40, "kv_read_to")?; // Read recipient balance
-    ctx.consume_gas(120, "kv_write_from")?; // Write sender balance
-    ctx.consume_gas(120, "kv_write_to")?; // Write recipient balance
+    ctx.consume_gas(gas_schedule.per_kv_read, "kv_read_from")?; // Read sender balance
+    ctx.consume_gas(gas_schedule.per_kv_read, "kv_read_to")?; // Read recipient balance
+    ctx.consume_gas(gas_schedule.per_kv_write, "kv_write_from")?; // Write sender balance
+    ctx.consume_gas(gas_schedule.per_kv_write, "kv_write_to")?; // Write recipient balance
 
     // Use the denom from the transaction (defaults to "udgt" for backward compatibility)
     let denom = &tx.denom;
@@ -578,37 +1288,44 @@ fn execute_transfer(
     let sender_old_balance = state.balance_of(&tx.from, denom);
     let recipient_old_balance = state.balance_of(&tx.to, denom);
 
-    // NOTE: We assume sufficient funds after upfront fee; future work can add explicit checks
+    // Vesting lockups are part of the balance but cannot be sent
+    let spendable = spendable_balance(state, &tx.from, denom, block_height);
+    if spendable < tx.amount {
+        return Err(GasError::Custom(format!(
+            "InsufficientFunds: spendable {denom} {spendable} < {}",
+            tx.amount
+        )));
+    }
     let sender_new_balance = sender_old_balance - tx.amount;
     let recipient_new_balance = recipient_old_balance + tx.amount;
 
-    ctx.record_state_change(
-        tx.from.clone(),
-        denom.to_string(),
-        sender_old_balance,
-        sender_new_balance,
-    );
-    ctx.record_state_change(
-        tx.to.clone(),
-        denom.to_string(),
-        recipient_old_balance,
-        recipient_new_balance,
-    );
 
     // Apply the transfer
     state.set_balance(&tx.from, denom, sender_new_balance);
     state.set_balance(&tx.to, denom, recipient_new_balance);
+    ctx.emit_event(
+        Event::new("transfer")
+            .attr("from", &tx.from)
+            .attr("to", &tx.to)
+            .attr("amount", tx.amount)
+            .attr("denom", denom),
+        gas_schedule,
+    )?;
 
     Ok(())
 }
 
-/// Estimate transaction size for gas calculation
-fn estimate_transaction_size(tx: &Transaction) -> usize {
-    // Simplified estimation - in practice would serialize the transaction
-    tx.hash.len() + tx.from.len() + tx.to.len() + 64 // rough estimate
+/// Protocol event for the burned share of a fee. Emitted after execution, so it is not
+/// charged `per_event` gas.
+fn fee_burn_event(burn: &crate::runtime::fee_burn::FeeBurnEvent) -> Event {
+    Event::new("fee_burn")
+        .attr("fee_paid", burn.fee_paid)
+        .attr("amount", burn.burn_amount)
+        .attr("denom", &burn.burn_token)
 }
 
 /// Create a success receipt
+#[allow(clippy::too_many_arguments)]
 fn create_success_receipt(
     tx: &Transaction,
     gas_used: Gas,
@@ -616,6 +1333,8 @@ fn create_success_receipt(
     gas_price: u64,
     block_height: u64,
     index: u32,
+    events: Vec<Event>,
+    gas_schedule_version: u32,
 ) -> TxReceipt {
     TxReceipt {
         receipt_version: RECEIPT_FORMAT_VERSION,
@@ -632,12 +1351,15 @@ fn create_success_receipt(
         gas_used,
         gas_limit,
         gas_price,
-        gas_refund: 0, // Always 0 as per spec
+        gas_refund: 0, // Set once the fee is settled
         success: true,
+        events,
+        gas_schedule_version,
     }
 }
 
 /// Create a failed receipt
+#[allow(clippy::too_many_arguments)]
 fn create_failed_receipt(
     tx: &Transaction,
     gas_used: Gas,
@@ -646,6 +1368,7 @@ fn create_failed_receipt(
     error: String,
     block_height: u64,
     index: u32,
+    gas_schedule_version: u32,
 ) -> TxReceipt {
     TxReceipt {
         receipt_version: RECEIPT_FORMAT_VERSION,
@@ -662,14 +1385,17 @@ fn create_failed_receipt(
         gas_used,
         gas_limit,
         gas_price,
-        gas_refund: 0, // Always 0 as per spec
+        gas_refund: 0, // Set once the fee is settled
         success: false,
+        events: Vec::new(),
+        gas_schedule_version,
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::runtime::module_accounts::{module_address, FEE_COLLECTOR};
     use crate::storage::state::Storage;
     use std::sync::Arc;
 
@@ -718,7 +1444,15 @@ mod tests {
         )
         .with_gas(25_000, 1);
 
-        let result = execute_transaction(&tx, &mut state, 100, 0, &gas_schedule, None);
+        let result = execute_transaction(
+            &tx,
+            &mut state,
+            100,
+            0,
+            &gas_schedule,
+            None,
+            ExecutionModules::default(),
+        );
 
         assert!(result.success);
         assert_eq!(result.receipt.status, TxStatus::Success);
@@ -746,7 +1480,15 @@ mod tests {
         )
         .with_gas(25_000, 1_000); // High gas price
 
-        let result = execute_transaction(&tx, &mut state, 100, 0, &gas_schedule, None);
+        let result = execute_transaction(
+            &tx,
+            &mut state,
+            100,
+            0,
+            &gas_schedule,
+            None,
+            ExecutionModules::default(),
+        );
 
         assert!(!result.success);
         assert_eq!(result.receipt.status, TxStatus::Failed);
@@ -757,4 +1499,1061 @@ mod tests {
             .unwrap()
             .contains("InsufficientFunds"));
     }
+
+    #[test]
+    fn test_simulation_does_not_persist() {
+        let mut state = create_test_state();
+        let gas_schedule = GasSchedule::default();
+        state.set_balance("alice", "udgt", 100_000);
+
+        let tx = Transaction::new(
+            "sim_hash".to_string(),
+            "alice".to_string(),
+            "bob".to_string(),
+            1_000,
+            10_000,
+            0,
+            None,
+        )
+        .with_gas(25_000, 1);
+
+        let sim = simulate_transaction(
+            &tx,
+            &state,
+            100,
+            &gas_schedule,
+            ExecutionModules::default(),
+        );
+        assert!(sim.result.success);
+        assert!(sim.result.gas_used > 0);
+        assert_eq!(
+            sim.result.gas_operations.values().sum::<Gas>(),
+            sim.result.gas_used
+        );
+        assert!(sim.result.gas_operations.contains_key("intrinsic"));
+        assert_eq!(sim.result.events.len(), 1);
+
+        // alice pays the charged fee plus the amount, bob receives the amount and the fee
+        // collector receives the fee
+        let fee = sim.result.receipt.fee_charged_datt() as u128;
+        assert!(sim.result.receipt.gas_refund > 0);
+        assert_eq!(sim.result.fee_refunded, 25_000 - fee);
+        let mut expected = vec![
+            BalanceDiff {
+                address: "alice".to_string(),
+                denom: "udgt".to_string(),
+                before: 100_000,
+                after: 100_000 - fee - 1_000,
+            },
+            BalanceDiff {
+                address: "bob".to_string(),
+                denom: "udgt".to_string(),
+                before: 0,
+                after: 1_000,
+            },
+            BalanceDiff {
+                address: module_address(FEE_COLLECTOR),
+                denom: "udgt".to_string(),
+                before: 0,
+                after: fee,
+            },
+        ];
+        expected.sort_by(|a, b| a.address.cmp(&b.address));
+        assert_eq!(sim.balance_diff, expected);
+
+        // Neither the live state nor storage observed the dry run
+        assert_eq!(state.balance_of("alice", "udgt"), 100_000);
+        assert_eq!(state.nonce_of("alice"), 0);
+        let mut reloaded = State::new(state.storage.clone());
+        assert_eq!(reloaded.balance_of("bob", "udgt"), 0);
+        assert_eq!(reloaded.nonce_of("alice"), 0);
+    }
+
+    #[test]
+    fn test_receipt_carries_typed_events() {
+        let mut state = create_test_state();
+        let gas_schedule = GasSchedule::default();
+        let mut fee_burn = FeeBurnEngine::new();
+        state.set_balance("alice", "udgt", 100_000);
+
+        let tx = Transaction::new("evt_hash", "alice", "bob", 1_000, 10_000, 0, None)
+            .with_gas(25_000, 1);
+        let result = execute_transaction(
+            &tx,
+            &mut state,
+            100,
+            0,
+            &gas_schedule,
+            Some(&mut fee_burn),
+            ExecutionModules::default(),
+        );
+        assert!(result.success);
+
+        let events = &result.receipt.events;
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].event_type, "transfer");
+        assert_eq!(events[0].get("from"), Some("alice"));
+        assert_eq!(events[0].get("amount"), Some("1000"));
+        assert_eq!(events[1].event_type, "fee_burn");
+        // Only the execution event is charged; the fee burn is emitted afterwards
+        assert_eq!(result.gas_operations["event"], gas_schedule.per_event);
+    }
+
+    #[test]
+    fn test_fees_burn_only_in_the_fee_denom() {
+        use crate::runtime::fee_burn::FeeBurnConfig;
+        use crate::runtime::params::ParamsModule;
+        use crate::runtime::supply::{check_invariants, mint_to};
+
+        let mut state = create_test_state();
+        mint_to(&mut state, "alice", "udgt", 1_000_000).unwrap();
+        mint_to(&mut state, "alice", "udrt", 1_000_000).unwrap();
+        // Fees are charged in udgt, so the burn token is not a governed parameter
+        let mut params = ParamsModule::new(state.storage.clone());
+        assert!(params.set("fee_burn.burn_token", "udrt").is_err());
+
+        let mut fee_burn = FeeBurnEngine::new();
+        fee_burn
+            .update_config(FeeBurnConfig {
+                burn_token: "udrt".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+        let transfer = |hash: &str, nonce: u64| {
+            Transaction::new(hash, "alice", "bob", 1_000, 10_000, nonce, None).with_gas(25_000, 10)
+        };
+        let result = execute_transaction(
+            &transfer("udrt_burn", 0),
+            &mut state,
+            1,
+            0,
+            &GasSchedule::default(),
+            Some(&mut fee_burn),
+            ExecutionModules::default(),
+        );
+        assert!(result.success);
+        assert_eq!(result.fee_burned, 0);
+        assert_eq!(fee_burn.get_total_burned("udrt"), 0);
+        assert_eq!(fee_burn.get_total_burned("udgt"), 0);
+        assert_eq!(module_balance(&mut state, FEE_COLLECTOR, "udgt"), result.fee_charged);
+        assert!(check_invariants(&state.storage).iter().all(|c| c.holds()));
+
+        // Burning in udgt destroys the share in the collector and reports it in udgt
+        fee_burn.update_config(FeeBurnConfig::default()).unwrap();
+        let burned = execute_transaction(
+            &transfer("udgt_burn", 1),
+            &mut state,
+            2,
+            0,
+            &GasSchedule::default(),
+            Some(&mut fee_burn),
+            ExecutionModules::default(),
+        );
+        assert!(burned.fee_burned > 0);
+        assert_eq!(fee_burn.get_total_burned("udgt"), burned.fee_burned);
+        assert_eq!(fee_burn.get_total_burned("udrt"), 0);
+        assert!(check_invariants(&state.storage).iter().all(|c| c.holds()));
+    }
+
+    #[test]
+    fn test_failed_transaction_fee_is_settled_and_burned() {
+        use crate::runtime::supply::{check_invariants, mint_to};
+
+        let mut state = create_test_state();
+        mint_to(&mut state, "alice", "udgt", 1_000_000).unwrap();
+        let mut fee_burn = FeeBurnEngine::new();
+
+        // Failing on insufficient funds only pays for the gas used
+        let overdrawn = Transaction::new("overdrawn", "alice", "bob", 5_000_000, 10_000, 0, None)
+            .with_gas(25_000, 10);
+        let failed = execute_transaction(
+            &overdrawn,
+            &mut state,
+            1,
+            0,
+            &GasSchedule::default(),
+            Some(&mut fee_burn),
+            ExecutionModules::default(),
+        );
+        assert!(!failed.success);
+        assert!(failed.receipt.gas_refund > 0);
+        assert_eq!(
+            failed.fee_charged,
+            (failed.receipt.gas_limit - failed.receipt.gas_refund) as u128 * 10
+        );
+        assert_eq!(failed.fee_refunded, failed.receipt.gas_refund as u128 * 10);
+        assert!(failed.fee_burned > 0);
+        assert_eq!(fee_burn.get_total_burned("udgt"), failed.fee_burned);
+        assert_eq!(state.balance_of("alice", "udgt"), 1_000_000 - failed.fee_charged);
+        assert_eq!(state.balance_of("bob", "udgt"), 0);
+        assert!(check_invariants(&state.storage).iter().all(|c| c.holds()));
+
+        // Running out of gas uses up the whole limit, so nothing is refunded
+        let starved = Transaction::new("starved", "alice", "bob", 1_000, 10_000, 1, None).with_gas(200, 10);
+        let out_of_gas = execute_transaction(
+            &starved,
+            &mut state,
+            2,
+            0,
+            &GasSchedule::default(),
+            Some(&mut fee_burn),
+            ExecutionModules::default(),
+        );
+        assert!(!out_of_gas.success);
+        assert_eq!(out_of_gas.receipt.gas_refund, 0);
+        assert_eq!(out_of_gas.fee_refunded, 0);
+        assert_eq!(out_of_gas.fee_charged, 2_000);
+        assert!(out_of_gas.fee_burned > 0);
+        assert_eq!(
+            fee_burn.get_total_burned("udgt"),
+            failed.fee_burned + out_of_gas.fee_burned
+        );
+        assert!(check_invariants(&state.storage).iter().all(|c| c.holds()));
+    }
+
+    #[test]
+    fn test_simulation_dms_register_not_persisted() {
+        use crate::storage::tx::TxMessage;
+
+        let mut state = create_test_state();
+        state.set_balance("alice", "udgt", 100_000);
+        let tx = Transaction::new("sim_dms", "alice", "alice", 0, 10_000, 0, None)
+            .with_gas(25_000, 1)
+            .with_messages(vec![TxMessage::DmsRegister {
+                from: "alice".to_string(),
+                beneficiary: "bob".to_string(),
+                period: 10,
+            }]);
+
+        let sim = simulate_transaction(
+            &tx,
+            &state,
+            1,
+            &GasSchedule::default(),
+            ExecutionModules::default(),
+        );
+        assert!(sim.result.success);
+        assert!(sim.result.gas_operations.contains_key("dms_register"));
+        let dms = DeadManSwitchModule::new(state.storage.clone());
+        assert!(dms.load_config("alice").is_none());
+    }
+
+    #[test]
+    fn test_simulation_staking_and_governance_not_persisted() {
+        use crate::runtime::governance::ProposalType;
+        use crate::storage::tx::TxMessage;
+        use std::sync::Mutex;
+
+        let mut state = create_test_state();
+        state.set_balance("alice", "udgt", 100_000);
+        let storage = state.storage.clone();
+        let mut staking = StakingModule::new(storage.clone());
+        staking.register_validator("val1");
+        let governance = GovernanceModule::new(
+            storage.clone(),
+            Arc::new(Mutex::new(State::new(storage.clone()))),
+            Arc::new(Mutex::new(StakingModule::new(storage))),
+        );
+        let tx = Transaction::new("sim_gov", "alice", "alice", 0, 10_000, 0, None)
+            .with_gas(30_000, 1)
+            .with_messages(vec![
+                TxMessage::Delegate {
+                    from: "alice".to_string(),
+                    validator: "val1".to_string(),
+                    amount: 40_000,
+                },
+                TxMessage::SubmitProposal {
+                    from: "alice".to_string(),
+                    title: "Signal".to_string(),
+                    description: "test".to_string(),
+                    proposal: ProposalType::Text,
+                    track: Default::default(),
+                },
+            ]);
+
+        let mut fork = governance.fork();
+        let sim = simulate_transaction(
+            &tx,
+            &state,
+            1,
+            &GasSchedule::default(),
+            ExecutionModules {
+                staking: Some(&mut staking),
+                governance: Some(&mut fork),
+                #[cfg(feature = "contracts")]
+                wasm: None,
+            },
+        );
+        assert!(sim.result.success, "{:?}", sim.result.receipt.error);
+        for op in ["staking_delegate", "gov_submit_proposal"] {
+            assert!(sim.result.gas_operations.contains_key(op), "missing {op}");
+        }
+        assert_eq!(fork.get_events().len(), 1);
+
+        // Neither the stake, the proposal nor its event reached the node
+        assert_eq!(staking.get_total_stake("alice"), 0);
+        assert_eq!(staking.validator_stake("val1"), 0);
+        assert!(governance.get_proposal(1).unwrap().is_none());
+        assert!(governance.get_events().is_empty());
+        assert_eq!(state.balance_of("alice", "udgt"), 100_000);
+    }
+
+    #[test]
+    fn test_multi_message_failure_discards_all_message_writes() {
+        use crate::storage::tx::TxMessage;
+
+        let mut state = create_test_state();
+        state.set_balance("alice", "udgt", 100_000);
+        let tx = Transaction::new("atomic_fail", "alice", "bob", 0, 10_000, 0, None)
+            .with_gas(25_000, 1)
+            .with_messages(vec![
+                TxMessage::Send {
+                    from: "alice".to_string(),
+                    to: "bob".to_string(),
+                    denom: "udgt".to_string(),
+                    amount: 1_000,
+                },
+                TxMessage::DmsRegister {
+                    from: "alice".to_string(),
+                    beneficiary: "bob".to_string(),
+                    period: 10,
+                },
+                // Fails: alice holds no udrt
+                TxMessage::Send {
+                    from: "alice".to_string(),
+                    to: "bob".to_string(),
+                    denom: "udrt".to_string(),
+                    amount: 1,
+                },
+            ]);
+
+        let result = execute_transaction(
+            &tx,
+            &mut state,
+            1,
+            0,
+            &GasSchedule::default(),
+            None,
+            ExecutionModules::default(),
+        );
+        assert!(!result.success);
+        assert!(result.state_changes.is_empty());
+
+        // Only the fee and nonce survive; the first send and the DMS write are discarded
+        assert_eq!(state.balance_of("alice", "udgt"), 100_000 - 25_000);
+        assert_eq!(state.balance_of("bob", "udgt"), 0);
+        assert_eq!(state.nonce_of("alice"), 1);
+        let dms = DeadManSwitchModule::new(state.storage.clone());
+        assert!(dms.load_config("alice").is_none());
+        let mut reloaded = State::new(state.storage.clone());
+        assert_eq!(reloaded.balance_of("bob", "udgt"), 0);
+    }
+
+    #[test]
+    fn test_multi_message_success_commits_and_journals() {
+        use crate::storage::tx::TxMessage;
+
+        let mut state = create_test_state();
+        state.set_balance("alice", "udgt", 100_000);
+        let tx = Transaction::new("atomic_ok", "alice", "bob", 0, 10_000, 0, None)
+            .with_gas(25_000, 1)
+            .with_messages(vec![
+                TxMessage::Send {
+                    from: "alice".to_string(),
+                    to: "bob".to_string(),
+                    denom: "udgt".to_string(),
+                    amount: 1_000,
+                },
+                TxMessage::DmsRegister {
+                    from: "alice".to_string(),
+                    beneficiary: "bob".to_string(),
+                    period: 10,
+                },
+            ]);
+
+        let result = execute_transaction(
+            &tx,
+            &mut state,
+            1,
+            0,
+            &GasSchedule::default(),
+            None,
+            ExecutionModules::default(),
+        );
+        assert!(result.success);
+        assert!(result.state_changes.contains(&StateChange::Balance {
+            address: "bob".to_string(),
+            denom: "udgt".to_string(),
+            old: 0,
+            new: 1_000,
+        }));
+        assert!(result
+            .state_changes
+            .iter()
+            .any(|c| matches!(c, StateChange::Kv { key, .. } if key == "dms:config:alice")));
+
+        let dms = DeadManSwitchModule::new(state.storage.clone());
+        assert_eq!(dms.load_config("alice").unwrap().beneficiary, "bob");
+        let mut reloaded = State::new(state.storage.clone());
+        assert_eq!(reloaded.balance_of("bob", "udgt"), 1_000);
+    }
+
+    #[test]
+    fn test_delegate_message_bonds_stake_and_charges_base_cost() {
+        use crate::storage::tx::TxMessage;
+
+        let mut state = create_test_state();
+        state.set_balance("alice", "udgt", 100_000);
+        let mut staking = StakingModule::new(state.storage.clone());
+        staking.register_validator("val1");
+        let gas_schedule = GasSchedule::default();
+
+        let tx = Transaction::new("delegate_tx", "alice", "alice", 0, 10_000, 0, None)
+            .with_gas(25_000, 1)
+            .with_messages(vec![TxMessage::Delegate {
+                from: "alice".to_string(),
+                validator: "val1".to_string(),
+                amount: 40_000,
+            }]);
+
+        let result = execute_transaction(
+            &tx,
+            &mut state,
+            1,
+            0,
+            &gas_schedule,
+            None,
+            ExecutionModules {
+                staking: Some(&mut staking),
+                ..Default::default()
+            },
+        );
+
+        assert!(result.success, "{:?}", result.receipt.error);
+        assert_eq!(
+            result.gas_operations.get("staking_delegate"),
+            Some(&gas_schedule.base_cost(&TxKind::StakingDelegate))
+        );
+        let fee = result.receipt.fee_charged_datt() as u128;
+        assert_eq!(state.balance_of("alice", "udgt"), 100_000 - fee - 40_000);
+        assert_eq!(staking.load_delegator_record("alice").stake_amount, 40_000);
+        assert_eq!(staking.total_stake(), 40_000);
+        assert_eq!(module_balance(&mut state, STAKING_BONDED, "udgt"), 40_000);
+    }
+
+    #[test]
+    fn test_failed_transaction_reverts_staking_state() {
+        use crate::storage::tx::TxMessage;
+
+        let mut state = create_test_state();
+        state.set_balance("alice", "udgt", 100_000);
+        let mut staking = StakingModule::new(state.storage.clone());
+        staking.register_validator("val1");
+
+        // The delegation succeeds, then undelegating more than was bonded fails
+        let tx = Transaction::new("delegate_tx", "alice", "alice", 0, 10_000, 0, None)
+            .with_gas(30_000, 1)
+            .with_messages(vec![
+                TxMessage::Delegate {
+                    from: "alice".to_string(),
+                    validator: "val1".to_string(),
+                    amount: 40_000,
+                },
+                TxMessage::Undelegate {
+                    from: "alice".to_string(),
+                    validator: "val1".to_string(),
+                    amount: 50_000,
+                },
+            ]);
+
+        let result = execute_transaction(
+            &tx,
+            &mut state,
+            1,
+            0,
+            &GasSchedule::default(),
+            None,
+            ExecutionModules {
+                staking: Some(&mut staking),
+                ..Default::default()
+            },
+        );
+
+        assert!(!result.success);
+        assert_eq!(staking.get_total_stake("alice"), 0);
+        assert_eq!(staking.total_stake(), 0);
+        assert_eq!(module_balance(&mut state, STAKING_BONDED, "udgt"), 0);
+        let fee = result.receipt.fee_charged_datt() as u128;
+        assert_eq!(state.balance_of("alice", "udgt"), 100_000 - fee);
+    }
+
+    #[test]
+    fn test_module_message_without_module_fails_and_keeps_fee() {
+        use crate::storage::tx::TxMessage;
+
+        let mut state = create_test_state();
+        state.set_balance("alice", "udgt", 100_000);
+        let tx = Transaction::new("undelegate_tx", "alice", "alice", 0, 10_000, 0, None)
+            .with_gas(25_000, 1)
+            .with_messages(vec![
+                TxMessage::Send {
+                    from: "alice".to_string(),
+                    to: "bob".to_string(),
+                    denom: "udgt".to_string(),
+                    amount: 5_000,
+                },
+                TxMessage::Undelegate {
+                    from: "alice".to_string(),
+                    validator: "val1".to_string(),
+                    amount: 1_000,
+                },
+            ]);
+
+        let result = execute_transaction(
+            &tx,
+            &mut state,
+            1,
+            0,
+            &GasSchedule::default(),
+            None,
+            ExecutionModules::default(),
+        );
+
+        assert!(!result.success);
+        assert!(result
+            .receipt
+            .error
+            .as_deref()
+            .unwrap_or_default()
+            .contains("staking module not available"));
+        // Send is reverted, the used gas is charged and nonce consumed
+        assert!(result.fee_refunded > 0);
+        assert_eq!(result.fee_charged + result.fee_refunded, 25_000);
+        assert_eq!(state.balance_of("alice", "udgt"), 100_000 - result.fee_charged);
+        assert_eq!(state.balance_of("bob", "udgt"), 0);
+        assert_eq!(state.nonce_of("alice"), 1);
+    }
+
+    #[test]
+    fn test_governance_messages_submit_deposit_vote() {
+        use crate::runtime::governance::{GovernanceConfig, ProposalStatus, ProposalType, VoteOption};
+        use crate::storage::tx::TxMessage;
+        use std::sync::Mutex;
+
+        let mut state = create_test_state();
+        state.set_balance("alice", "udgt", 1_000_000);
+        let storage = state.storage.clone();
+        let mut staking = StakingModule::new(storage.clone());
+        let config = GovernanceConfig {
+            min_deposit: 500,
+            liquid_balance_voting: true,
+            ..Default::default()
+        };
+        let mut governance = GovernanceModule::new_with_config(
+            storage.clone(),
+            Arc::new(Mutex::new(State::new(storage.clone()))),
+            Arc::new(Mutex::new(StakingModule::new(storage))),
+            config,
+        );
+        let gas_schedule = GasSchedule::default();
+
+        let messages = vec![
+            TxMessage::SubmitProposal {
+                from: "alice".to_string(),
+                title: "Raise gas limit".to_string(),
+                description: "test".to_string(),
+                proposal: ProposalType::ParameterChange {
+                    key: "gas_limit".to_string(),
+                    value: "3000".to_string(),
+                },
+                track: Default::default(),
+            },
+            TxMessage::Deposit {
+                from: "alice".to_string(),
+                proposal_id: 1,
+                amount: 500,
+            },
+            TxMessage::Vote {
+                from: "alice".to_string(),
+                proposal_id: 1,
+                option: VoteOption::Yes,
+            },
+        ];
+        let tx = Transaction::new("gov_tx", "alice", "alice", 0, 10_000, 0, None)
+            .with_gas(30_000, 1)
+            .with_messages(messages);
+
+        let result = execute_transaction(
+            &tx,
+            &mut state,
+            10,
+            0,
+            &gas_schedule,
+            None,
+            ExecutionModules {
+                staking: Some(&mut staking),
+                governance: Some(&mut governance),
+                #[cfg(feature = "contracts")]
+                wasm: None,
+            },
+        );
+
+        assert!(result.success, "{:?}", result.receipt.error);
+        for op in ["gov_submit_proposal", "gov_deposit", "gov_vote"] {
+            assert!(result.gas_operations.contains_key(op), "missing {op}");
+        }
+        let fee = result.receipt.fee_charged_datt() as u128;
+        assert_eq!(state.balance_of("alice", "udgt"), 1_000_000 - fee - 500);
+
+        let proposal = governance.get_proposal(1).unwrap().unwrap();
+        assert_eq!(proposal.status, ProposalStatus::VotingPeriod);
+        assert_eq!(proposal.total_deposit, 500);
+        let votes = governance.get_proposal_votes(1).unwrap();
+        assert_eq!(votes.len(), 1);
+        // No stake bonded: weight falls back to the liquid balance when voting started (at
+        // the deposit), which is before the unused gas is refunded
+        assert_eq!(votes[0].weight, 1_000_000 - 30_000 - 500);
+    }
+
+    #[test]
+    fn test_sponsored_fee_charged_to_payer() {
+        use crate::storage::tx::TxMessage;
+        use crate::types::tx::FeePayerSignature;
+
+        let mut state = create_test_state();
+        state.set_balance("alice", "udgt", 1_000);
+        state.set_balance("sponsor", "udgt", 100_000);
+        let send = vec![TxMessage::Send {
+            from: "alice".to_string(),
+            to: "bob".to_string(),
+            denom: "udgt".to_string(),
+            amount: 1_000,
+        }];
+        let sponsored = |hash: &str, nonce: u64| {
+            Transaction::new(hash, "alice", "bob", 1_000, 10_000, nonce, None)
+                .with_gas(25_000, 1)
+                .with_messages(send.clone())
+                .with_fee_payer("sponsor")
+        };
+
+        // No allowance and no co-signature: rejected before anything is charged
+        let result = execute_transaction(
+            &sponsored("no_grant", 0),
+            &mut state,
+            5,
+            0,
+            &GasSchedule::default(),
+            None,
+            ExecutionModules::default(),
+        );
+        assert!(!result.success);
+        assert_eq!(state.balance_of("sponsor", "udgt"), 100_000);
+        assert_eq!(state.nonce_of("alice"), 0);
+
+        // Allowance covers the upfront fee and is drawn down by the charged fee only
+        let mut grants = FeeGrantModule::new(state.storage.clone());
+        grants.grant("sponsor", "alice", 30_000, Some(10), 5).unwrap();
+        let result = execute_transaction(
+            &sponsored("granted", 0),
+            &mut state,
+            5,
+            0,
+            &GasSchedule::default(),
+            None,
+            ExecutionModules::default(),
+        );
+        assert!(result.success, "{:?}", result.receipt.error);
+        let granted_fee = result.receipt.fee_charged_datt() as u128;
+        assert!(granted_fee < 25_000);
+        assert_eq!(result.fee_refunded, 25_000 - granted_fee);
+        assert_eq!(state.balance_of("sponsor", "udgt"), 100_000 - granted_fee);
+        assert_eq!(state.balance_of("alice", "udgt"), 0);
+        assert_eq!(state.balance_of("bob", "udgt"), 1_000);
+        assert_eq!(
+            grants.get_allowance("sponsor", "alice").unwrap().spend_limit,
+            30_000 - granted_fee
+        );
+
+        // A co-signing payer needs no allowance
+        state.set_balance("alice", "udgt", 1_000);
+        let mut cosigned = sponsored("cosigned", 1);
+        cosigned.fee_payer_signature = Some(FeePayerSignature {
+            public_key: String::new(),
+            signature: String::new(),
+        });
+        let result = execute_transaction(
+            &cosigned,
+            &mut state,
+            20,
+            0,
+            &GasSchedule::default(),
+            None,
+            ExecutionModules::default(),
+        );
+        assert!(result.success, "{:?}", result.receipt.error);
+        let cosigned_fee = result.receipt.fee_charged_datt() as u128;
+        assert_eq!(
+            state.balance_of("sponsor", "udgt"),
+            100_000 - granted_fee - cosigned_fee
+        );
+        assert_eq!(
+            grants.get_allowance("sponsor", "alice").unwrap().spend_limit,
+            30_000 - granted_fee
+        );
+    }
+
+    #[test]
+    fn test_unused_gas_refunded_and_charged_fee_burned() {
+        let mut state = create_test_state();
+        state.set_balance("alice", "udgt", 100_000);
+        let mut fee_burn = FeeBurnEngine::new();
+        let tx = Transaction::new("refund_tx", "alice", "bob", 1_000, 10_000, 0, None)
+            .with_gas(25_000, 2);
+
+        let result = execute_transaction(
+            &tx,
+            &mut state,
+            1,
+            0,
+            &GasSchedule::default(),
+            Some(&mut fee_burn),
+            ExecutionModules::default(),
+        );
+        assert!(result.success);
+        let receipt = &result.receipt;
+        assert_eq!(receipt.gas_refund, 25_000 - result.gas_used);
+        assert_eq!(receipt.fee_charged_datt(), result.gas_used * 2);
+        assert_eq!(result.fee_refunded, receipt.gas_refund as u128 * 2);
+        assert_eq!(
+            state.balance_of("alice", "udgt"),
+            100_000 - 1_000 - receipt.fee_charged_datt() as u128
+        );
+        // The burn is computed from the charged fee, not the upfront fee
+        let burn = receipt.events.iter().find(|e| e.event_type == "fee_burn").unwrap();
+        assert_eq!(burn.get("fee_paid"), Some(receipt.fee_charged_datt().to_string().as_str()));
+        assert_eq!(result.fee_burned.to_string(), burn.get("amount").unwrap());
+
+        // A minimum charge floor caps the refund
+        let floored = GasSchedule {
+            version: 2,
+            min_gas_charge_bps: 5_000,
+            ..GasSchedule::default()
+        };
+        let tx = Transaction::new("floor_tx", "alice", "bob", 1_000, 10_000, 1, None)
+            .with_gas(25_000, 1);
+        let result = execute_transaction(
+            &tx,
+            &mut state,
+            2,
+            0,
+            &floored,
+            None,
+            ExecutionModules::default(),
+        );
+        assert!(result.success);
+        assert_eq!(result.receipt.gas_refund, 12_500);
+        assert_eq!(result.fee_burned, 0);
+        // Receipts record the version of the schedule that priced them
+        assert_eq!(receipt.gas_schedule_version, 1);
+        assert_eq!(result.receipt.gas_schedule_version, 2);
+    }
+
+    #[test]
+    fn test_message_gas_follows_the_schedule() {
+        use crate::storage::tx::TxMessage;
+
+        let mut state = create_test_state();
+        state.set_balance("alice", "udgt", 1_000_000);
+        let pricier = GasSchedule {
+            version: 2,
+            per_kv_write: 220,
+            dms_ping_base: 900,
+            ..GasSchedule::default()
+        };
+        let run = |state: &mut State, hash: &str, nonce: u64, msg: TxMessage, schedule: &GasSchedule| {
+            let tx = Transaction::new(hash, "alice", "alice", 0, 10_000, nonce, None)
+                .with_gas(25_000, 1)
+                .with_messages(vec![msg]);
+            let result = execute_transaction(&tx, state, 1, 0, schedule, None, ExecutionModules::default());
+            assert!(result.success, "{:?}", result.receipt.error);
+            result.gas_used
+        };
+        let send = || TxMessage::Send {
+            from: "alice".to_string(),
+            to: "bob".to_string(),
+            denom: "udgt".to_string(),
+            amount: 1_000,
+        };
+        let register = TxMessage::DmsRegister {
+            from: "alice".to_string(),
+            beneficiary: "bob".to_string(),
+            period: 10,
+        };
+        let ping = || TxMessage::DmsPing {
+            from: "alice".to_string(),
+        };
+
+        // A send writes two balances
+        let default_send = run(&mut state, "send1", 0, send(), &GasSchedule::default());
+        let pricier_send = run(&mut state, "send2", 1, send(), &pricier);
+        assert_eq!(pricier_send, default_send + 2 * 100);
+
+        run(&mut state, "register", 2, register, &GasSchedule::default());
+        let default_ping = run(&mut state, "ping1", 3, ping(), &GasSchedule::default());
+        let pricier_ping = run(&mut state, "ping2", 4, ping(), &pricier);
+        assert_eq!(pricier_ping, default_ping + 400);
+    }
+
+    #[test]
+    fn test_schedule_transfer_escrows_and_cancels() {
+        use crate::runtime::schedule::{release_matured, ScheduleModule};
+        use crate::storage::tx::TxMessage;
+
+        let mut state = create_test_state();
+        state.set_balance("alice", "udgt", 100_000);
+        let schedule = |hash: &str, nonce: u64, release_height: u64| {
+            Transaction::new(hash, "alice", "bob", 0, 10_000, nonce, None)
+                .with_gas(25_000, 1)
+                .with_messages(vec![TxMessage::ScheduleTransfer {
+                    from: "alice".to_string(),
+                    to: "bob".to_string(),
+                    denom: "udgt".to_string(),
+                    amount: 5_000,
+                    release_height,
+                    cancellable: true,
+                }])
+        };
+        let run = |state: &mut State, tx: &Transaction, height: u64| {
+            execute_transaction(
+                tx,
+                state,
+                height,
+                0,
+                &GasSchedule::default(),
+                None,
+                ExecutionModules::default(),
+            )
+        };
+
+        let first = run(&mut state, &schedule("s1", 0, 10), 1);
+        assert!(first.success, "{:?}", first.receipt.error);
+        let second = run(&mut state, &schedule("s2", 1, 20), 1);
+        assert!(second.success);
+        let fees = (first.receipt.fee_charged_datt() + second.receipt.fee_charged_datt()) as u128;
+        assert_eq!(state.balance_of("alice", "udgt"), 100_000 - fees - 10_000);
+        assert_eq!(first.events[0].get("id"), Some("1"));
+
+        // Cancel the second schedule before it matures
+        let cancel = Transaction::new("c2", "alice", "alice", 0, 10_000, 2, None)
+            .with_gas(25_000, 1)
+            .with_messages(vec![TxMessage::CancelSchedule {
+                from: "alice".to_string(),
+                id: 2,
+            }]);
+        let cancelled = run(&mut state, &cancel, 5);
+        assert!(cancelled.success, "{:?}", cancelled.receipt.error);
+        let fees = fees + cancelled.receipt.fee_charged_datt() as u128;
+        assert_eq!(state.balance_of("alice", "udgt"), 100_000 - fees - 5_000);
+
+        // The first schedule is released to bob at its height
+        assert!(release_matured(&mut state, 9).unwrap().is_empty());
+        let events = release_matured(&mut state, 10).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type, "schedule_release");
+        assert_eq!(state.balance_of("bob", "udgt"), 5_000);
+        assert!(ScheduleModule::new(state.storage.clone())
+            .schedules_for("alice")
+            .is_empty());
+    }
+
+    #[test]
+    fn test_vesting_lockup_blocks_sends_but_not_delegation() {
+        use crate::runtime::vesting::{VestingAccount, VestingSchedule};
+        use crate::storage::tx::TxMessage;
+
+        let mut state = create_test_state();
+        state.set_balance("team", "udgt", 200_000);
+        VestingModule::new(state.storage.clone())
+            .set(&VestingAccount {
+                address: "team".to_string(),
+                denom: "udgt".to_string(),
+                original_vesting: 150_000,
+                schedule: VestingSchedule::Delayed { end_height: 100 },
+                delegated_vesting: 0,
+                delegated_free: 0,
+            })
+            .unwrap();
+        let mut staking = StakingModule::new(state.storage.clone());
+        staking.register_validator("val1");
+        let tx = |hash: &str, nonce: u64, msg: TxMessage| {
+            Transaction::new(hash, "team", "team", 0, 10_000, nonce, None)
+                .with_gas(25_000, 1)
+                .with_messages(vec![msg])
+        };
+        let send = |amount: u128| TxMessage::Send {
+            from: "team".to_string(),
+            to: "bob".to_string(),
+            denom: "udgt".to_string(),
+            amount,
+        };
+
+        // 175_000 left after the fee, but only 25_000 of it is spendable before the cliff
+        let locked = execute_transaction(
+            &tx("v1", 0, send(60_000)),
+            &mut state,
+            1,
+            0,
+            &GasSchedule::default(),
+            None,
+            ExecutionModules::default(),
+        );
+        assert!(!locked.success);
+        assert!(locked.receipt.error.unwrap().contains("spendable"));
+
+        // Locked tokens can still be bonded
+        let delegate = TxMessage::Delegate {
+            from: "team".to_string(),
+            validator: "val1".to_string(),
+            amount: 100_000,
+        };
+        let bonded = execute_transaction(
+            &tx("v2", 1, delegate),
+            &mut state,
+            2,
+            0,
+            &GasSchedule::default(),
+            None,
+            ExecutionModules {
+                staking: Some(&mut staking),
+                ..Default::default()
+            },
+        );
+        assert!(bonded.success, "{:?}", bonded.receipt.error);
+        let vesting = VestingModule::new(state.storage.clone());
+        assert_eq!(vesting.get("team").unwrap().delegated_vesting, 100_000);
+        assert_eq!(vesting.locked("team", "udgt", 2), 50_000);
+        assert_eq!(
+            spendable_balance(&mut state, "team", "udgt", 2),
+            locked.fee_refunded + bonded.fee_refunded
+        );
+
+        // After the cliff the whole balance is spendable
+        let balance = state.balance_of("team", "udgt");
+        assert_eq!(spendable_balance(&mut state, "team", "udgt", 100), balance);
+    }
+
+    #[test]
+    fn test_dms_claim_leaves_locked_vesting_with_owner() {
+        use crate::runtime::vesting::{VestingAccount, VestingSchedule};
+        use crate::storage::tx::TxMessage;
+
+        let mut state = create_test_state();
+        state.set_balance("team", "udgt", 200_000);
+        state.set_balance("heir", "udgt", 100_000);
+        VestingModule::new(state.storage.clone())
+            .set(&VestingAccount {
+                address: "team".to_string(),
+                denom: "udgt".to_string(),
+                original_vesting: 150_000,
+                schedule: VestingSchedule::Delayed { end_height: 100 },
+                delegated_vesting: 0,
+                delegated_free: 0,
+            })
+            .unwrap();
+        DeadManSwitchModule::new(state.storage.clone())
+            .register("team", "heir", 10, 1)
+            .unwrap();
+
+        let tx = Transaction::new("claim", "heir", "heir", 0, 10_000, 0, None)
+            .with_gas(25_000, 1)
+            .with_messages(vec![TxMessage::DmsClaim {
+                from: "heir".to_string(),
+                owner: "team".to_string(),
+            }]);
+        let result = execute_transaction(
+            &tx,
+            &mut state,
+            20,
+            0,
+            &GasSchedule::default(),
+            None,
+            ExecutionModules::default(),
+        );
+        assert!(result.success, "{:?}", result.receipt.error);
+
+        // Only the 50_000 already vested moves; the locked 150_000 stays with the owner
+        assert_eq!(state.balance_of("team", "udgt"), 150_000);
+        assert_eq!(
+            state.balance_of("heir", "udgt"),
+            100_000 - result.fee_charged + 50_000
+        );
+        assert_eq!(spendable_balance(&mut state, "team", "udgt", 20), 0);
+    }
+
+    #[test]
+    fn test_token_factory_create_mint_send_burn() {
+        use crate::storage::tx::TxMessage;
+
+        let mut state = create_test_state();
+        state.set_balance("alice", "udgt", 100_000);
+        state.set_balance("bob", "udgt", 100_000);
+        let denom = "factory/alice/gold".to_string();
+        let run = |state: &mut State, hash: &str, from: &str, nonce: u64, msgs: Vec<TxMessage>| {
+            let tx = Transaction::new(hash, from, from, 0, 10_000, nonce, None)
+                .with_gas(25_000, 1)
+                .with_messages(msgs);
+            execute_transaction(
+                &tx,
+                state,
+                1,
+                0,
+                &GasSchedule::default(),
+                None,
+                ExecutionModules::default(),
+            )
+        };
+
+        let created = run(&mut state, "f1", "alice", 0, vec![
+            TxMessage::CreateDenom {
+                from: "alice".to_string(),
+                subdenom: "gold".to_string(),
+                symbol: "GLD".to_string(),
+                decimals: 6,
+            },
+            TxMessage::Mint {
+                from: "alice".to_string(),
+                denom: denom.clone(),
+                amount: 1_000,
+                to: Some("bob".to_string()),
+            },
+        ]);
+        assert!(created.success, "{:?}", created.receipt.error);
+        assert_eq!(created.events[0].get("denom"), Some(denom.as_str()));
+        assert_eq!(state.balance_of("bob", &denom), 1_000);
+
+        // Holders can send the denom, but only the admin can burn it
+        let burn = |from: &str, amount: u128| TxMessage::Burn {
+            from: from.to_string(),
+            denom: denom.clone(),
+            amount,
+        };
+        let sent = run(&mut state, "f2", "bob", 0, vec![TxMessage::Send {
+            from: "bob".to_string(),
+            to: "alice".to_string(),
+            denom: denom.clone(),
+            amount: 300,
+        }]);
+        assert!(sent.success, "{:?}", sent.receipt.error);
+        assert!(!run(&mut state, "f3", "bob", 1, vec![burn("bob", 100)]).success);
+        let burned = run(&mut state, "f4", "alice", 1, vec![burn("alice", 200)]);
+        assert!(burned.success, "{:?}", burned.receipt.error);
+        assert_eq!(state.balance_of("alice", &denom), 100);
+        assert_eq!(
+            TokenFactoryModule::new(state.storage.clone())
+                .get(&denom)
+                .unwrap()
+                .supply,
+            800
+        );
+    }
 }
@@ -0,0 +1,311 @@
+/*
+Optimistic parallel block execution.
+
+Transactions are first executed speculatively and concurrently, each against its own
+read-tracked `StateOverlay` over the state at the start of the block. They are then
+validated and committed in block order: a transaction that read nothing written by an
+earlier transaction of the block is committed as is, otherwise it is re-executed against
+the current state. Transactions whose messages need runtime modules (staking, governance,
+contracts) are always executed at their commit point. Fee burning is applied at commit
+time as well, so the results are identical to calling `execute_transaction` on each
+transaction in order. Where `execute_transaction` credits each charged fee to the fee
+collector module account (which burns its share), the block's charged fees are collected
+once, after the last commit, so transactions do not conflict on its balance.
+*/
+
+use super::{burn_charged_fee, execute_messages, ExecutionModules, ExecutionResult};
+use crate::gas::GasSchedule;
+use crate::runtime::fee_burn::FeeBurnEngine;
+use crate::runtime::module_accounts::collect_block_fees;
+use crate::state::{ReadSet, State, StateChange, StateOverlay};
+use crate::storage::tx::{Transaction, TxMessage};
+use std::collections::{BTreeSet, HashSet};
+
+/// Results of a parallel block execution, in block order
+#[derive(Debug)]
+pub struct BlockExecution {
+    pub results: Vec<ExecutionResult>,
+    /// Speculative executions discarded because of a read/write conflict
+    pub conflicts: usize,
+    /// Transactions executed only at their commit point because they need runtime modules
+    pub sequential: usize,
+}
+
+struct Speculation {
+    overlay: StateOverlay,
+    result: ExecutionResult,
+    reads: ReadSet,
+}
+
+/// Accounts and KV keys changed by the transactions committed so far
+#[derive(Default)]
+struct WriteSet {
+    accounts: HashSet<String>,
+    kv_keys: BTreeSet<String>,
+}
+
+impl WriteSet {
+    fn record(&mut self, changes: &[StateChange]) {
+        for change in changes {
+            match change {
+                StateChange::Balance { address, .. } | StateChange::Nonce { address, .. } => {
+                    self.accounts.insert(address.clone());
+                }
+                StateChange::Kv { key, .. } => {
+                    self.kv_keys.insert(key.clone());
+                }
+            }
+        }
+    }
+
+    fn conflicts_with(&self, reads: &ReadSet) -> bool {
+        reads.accounts.iter().any(|a| self.accounts.contains(a))
+            || reads.kv_keys.iter().any(|k| self.kv_keys.contains(k))
+            || reads.kv_prefixes.iter().any(|p| {
+                self.kv_keys
+                    .range(p.clone()..)
+                    .next()
+                    .is_some_and(|k| k.starts_with(p.as_str()))
+            })
+    }
+}
+
+/// Staking, governance and contract messages need the runtime modules, which speculative
+/// executions do not get, so such transactions are not executed speculatively
+fn needs_modules(tx: &Transaction) -> bool {
+    tx.messages.as_ref().is_some_and(|messages| {
+        messages.iter().any(|m| {
+            matches!(
+                m,
+                TxMessage::Delegate { .. }
+                    | TxMessage::Undelegate { .. }
+                    | TxMessage::ClaimRewards { .. }
+                    | TxMessage::SubmitProposal { .. }
+                    | TxMessage::Deposit { .. }
+                    | TxMessage::Vote { .. }
+                    | TxMessage::WeightedVote { .. }
+                    | TxMessage::ContractDeploy { .. }
+                    | TxMessage::ContractCall { .. }
+            )
+        })
+    })
+}
+
+/// Execute a block's transactions on up to `workers` threads. The post-state, results and
+/// fee burns are the same as executing the transactions sequentially in order.
+pub fn execute_block_parallel(
+    txs: &[Transaction],
+    state: &mut State,
+    block_height: u64,
+    gas_schedule: &GasSchedule,
+    mut fee_burn_engine: Option<&mut FeeBurnEngine>,
+    mut modules: ExecutionModules<'_>,
+    workers: usize,
+) -> BlockExecution {
+    // Phase 1: speculative execution against the state at the start of the block
+    let mut jobs: Vec<(usize, StateOverlay)> = txs
+        .iter()
+        .enumerate()
+        .filter(|(_, tx)| !needs_modules(tx))
+        .map(|(i, _)| (i, StateOverlay::tracked(state)))
+        .collect();
+    let chunk_size = jobs.len().div_ceil(workers.max(1)).max(1);
+    let mut speculative: Vec<Option<Speculation>> = txs.iter().map(|_| None).collect();
+    std::thread::scope(|scope| {
+        let mut handles = Vec::new();
+        while !jobs.is_empty() {
+            let chunk: Vec<_> = jobs.drain(..chunk_size.min(jobs.len())).collect();
+            handles.push(scope.spawn(move || {
+                chunk
+                    .into_iter()
+                    .map(|(i, mut overlay)| {
+                        let result = execute_messages(
+                            &txs[i],
+                            &mut overlay,
+                            block_height,
+                            i as u32,
+                            gas_schedule,
+                            ExecutionModules::default(),
+                        );
+                        let reads = overlay.read_set();
+                        (i, Speculation { overlay, result, reads })
+                    })
+                    .collect::<Vec<_>>()
+            }));
+        }
+        for handle in handles {
+            for (i, spec) in handle.join().expect("speculative execution panicked") {
+                speculative[i] = Some(spec);
+            }
+        }
+    });
+
+    // Phase 2: validate and commit in block order
+    let mut writes = WriteSet::default();
+    let mut results = Vec::with_capacity(txs.len());
+    let (mut conflicts, mut sequential) = (0, 0);
+    for (i, tx) in txs.iter().enumerate() {
+        let (overlay, mut result) = match speculative[i].take() {
+            Some(spec) if !writes.conflicts_with(&spec.reads) => (spec.overlay, spec.result),
+            spec => {
+                if spec.is_some() {
+                    conflicts += 1;
+                } else {
+                    sequential += 1;
+                }
+                let mut overlay = StateOverlay::new(state);
+                let result = execute_messages(
+                    tx,
+                    &mut overlay,
+                    block_height,
+                    i as u32,
+                    gas_schedule,
+                    modules.reborrow(),
+                );
+                (overlay, result)
+            }
+        };
+        writes.record(&overlay.commit(state));
+        if let Some(engine) = fee_burn_engine.as_deref_mut() {
+            burn_charged_fee(tx, &mut result, block_height, engine, state);
+        }
+        results.push(result);
+    }
+    let charged = results.iter().map(|r| r.fee_charged).sum::<u128>();
+    let burned = results.iter().map(|r| r.fee_burned).sum::<u128>();
+    if let Err(e) = collect_block_fees(state, charged, burned) {
+        tracing::warn!("block {block_height}: fee collection failed: {e}");
+    }
+
+    BlockExecution {
+        results,
+        conflicts,
+        sequential,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::execute_transaction;
+    use crate::runtime::module_accounts::{module_balance, FEE_COLLECTOR};
+    use crate::runtime::supply::{check_invariants, mint_to};
+    use crate::storage::state::Storage;
+    use std::sync::Arc;
+
+    fn state() -> (tempfile::TempDir, State) {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = Arc::new(Storage::open(dir.path().join("state.db")).unwrap());
+        (dir, State::new(storage))
+    }
+
+    fn transfer(hash: &str, from: &str, to: &str, amount: u128, nonce: u64) -> Transaction {
+        Transaction::new(hash, from, to, amount, 10_000, nonce, None).with_gas(25_000, 1)
+    }
+
+    fn fund(state: &mut State) {
+        for name in ["alice", "bob", "carol", "dave"] {
+            mint_to(state, name, "udgt", 100_000).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_parallel_matches_sequential_with_conflicts() {
+        let txs = vec![
+            transfer("t0", "alice", "bob", 1_000, 0),
+            transfer("t1", "carol", "dave", 2_000, 0),
+            // Reads bob after t0 credited him
+            transfer("t2", "bob", "carol", 500, 0),
+            // Second tx from alice depends on her first nonce
+            transfer("t3", "alice", "dave", 3_000, 1),
+            // Bad nonce: fails in both modes (and read dave, whom t1 credited)
+            transfer("t4", "dave", "alice", 1, 7),
+        ];
+        let schedule = GasSchedule::default();
+
+        let (_d1, mut seq_state) = state();
+        fund(&mut seq_state);
+        let mut seq_burn = FeeBurnEngine::new();
+        let sequential: Vec<_> = txs
+            .iter()
+            .enumerate()
+            .map(|(i, tx)| {
+                execute_transaction(
+                    tx,
+                    &mut seq_state,
+                    1,
+                    i as u32,
+                    &schedule,
+                    Some(&mut seq_burn),
+                    ExecutionModules::default(),
+                )
+            })
+            .collect();
+
+        let (_d2, mut par_state) = state();
+        fund(&mut par_state);
+        let mut par_burn = FeeBurnEngine::new();
+        let block = execute_block_parallel(
+            &txs,
+            &mut par_state,
+            1,
+            &schedule,
+            Some(&mut par_burn),
+            ExecutionModules::default(),
+            4,
+        );
+
+        assert_eq!(block.conflicts, 3);
+        assert_eq!(block.sequential, 0);
+        for (seq, par) in sequential.iter().zip(&block.results) {
+            assert_eq!(seq.success, par.success);
+            assert_eq!(
+                serde_json::to_value(&seq.receipt).unwrap(),
+                serde_json::to_value(&par.receipt).unwrap()
+            );
+            assert_eq!(seq.state_changes, par.state_changes);
+        }
+        for name in ["alice", "bob", "carol", "dave"] {
+            assert_eq!(
+                seq_state.snapshot_account(name).balances,
+                par_state.snapshot_account(name).balances
+            );
+            assert_eq!(seq_state.nonce_of(name), par_state.nonce_of(name));
+        }
+        assert_eq!(seq_burn.get_total_burned("udgt"), par_burn.get_total_burned("udgt"));
+
+        // Unburned fees end up in the fee collector and the supply still balances
+        let kept: u128 = block.results.iter().map(|r| r.fee_charged - r.fee_burned).sum();
+        assert!(kept > 0);
+        assert_eq!(module_balance(&mut par_state, FEE_COLLECTOR, "udgt"), kept);
+        assert_eq!(module_balance(&mut seq_state, FEE_COLLECTOR, "udgt"), kept);
+        assert!(check_invariants(&par_state.storage).iter().all(|c| c.holds()));
+        assert!(check_invariants(&seq_state.storage).iter().all(|c| c.holds()));
+    }
+
+    #[test]
+    fn test_independent_transfers_commit_without_conflicts() {
+        let (_dir, mut state) = state();
+        let txs: Vec<_> = (0..32)
+            .map(|i| {
+                let from = format!("sender{i}");
+                state.set_balance(&from, "udgt", 50_000);
+                transfer(&format!("h{i}"), &from, &format!("recipient{i}"), 10, 0)
+            })
+            .collect();
+
+        let block = execute_block_parallel(
+            &txs,
+            &mut state,
+            1,
+            &GasSchedule::default(),
+            None,
+            ExecutionModules::default(),
+            8,
+        );
+        assert_eq!(block.conflicts, 0);
+        assert!(block.results.iter().all(|r| r.success));
+        assert_eq!(state.balance_of("recipient31", "udgt"), 10);
+        assert_eq!(State::new(state.storage.clone()).nonce_of("sender0"), 1);
+    }
+}
@@ -5,11 +5,13 @@ Deterministic, versioned gas accounting across all transaction types with
 enforced limits at mempool admission and execution time.
 */
 
+use crate::runtime::params::{parse, GovernedParams};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use thiserror::Error;
 
-// Version constants
+// Version constants. GAS_TABLE_VERSION is the built-in schedule's version; governed cost
+// changes add later versions in state (see runtime::gas_schedule).
 pub const GAS_TABLE_VERSION: u32 = 1;
 pub const RECEIPT_FORMAT_VERSION: u32 = 1;
 
@@ -55,6 +57,10 @@ pub enum GasError {
 // Gas schedule containing all cost constants
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GasSchedule {
+    // Version recorded in the receipts of transactions priced by this schedule
+    #[serde(default = "built_in_version")]
+    pub version: u32,
+
     // Intrinsic base costs per transaction type
     pub transfer_base: Gas,
     pub governance_proposal_create_base: Gas,
@@ -73,11 +79,42 @@ pub struct GasSchedule {
     pub per_kv_write: Gas,
     pub per_event: Gas,
     pub per_vm_instruction: Gas,
+
+    // Dead man's switch message costs
+    #[serde(default = "default_dms_register_base")]
+    pub dms_register_base: Gas,
+    #[serde(default = "default_dms_ping_base")]
+    pub dms_ping_base: Gas,
+    #[serde(default = "default_dms_claim_base")]
+    pub dms_claim_base: Gas,
+
+    // Refund policy: a tx is charged at least this share of its gas limit
+    // (basis points); the rest of the unused gas is refunded
+    #[serde(default)]
+    pub min_gas_charge_bps: u64,
+}
+
+fn built_in_version() -> u32 {
+    GAS_TABLE_VERSION
+}
+
+fn default_dms_register_base() -> Gas {
+    1_000
+}
+
+fn default_dms_ping_base() -> Gas {
+    500
+}
+
+fn default_dms_claim_base() -> Gas {
+    2_000
 }
 
 impl Default for GasSchedule {
     fn default() -> Self {
         Self {
+            version: GAS_TABLE_VERSION,
+
             // Intrinsic base costs (from specification)
             transfer_base: 500,
             governance_proposal_create_base: 5_000,
@@ -96,10 +133,74 @@ impl Default for GasSchedule {
             per_kv_write: 120,
             per_event: 80,
             per_vm_instruction: PER_VM_INSTRUCTION,
+
+            dms_register_base: default_dms_register_base(),
+            dms_ping_base: default_dms_ping_base(),
+            dms_claim_base: default_dms_claim_base(),
+
+            // Refund all unused gas
+            min_gas_charge_bps: 0,
         }
     }
 }
 
+impl GovernedParams for GasSchedule {
+    const MODULE: &'static str = "gas";
+
+    fn get_param(&self, key: &str) -> Option<String> {
+        let value = match key {
+            "gas.transfer_base" => self.transfer_base,
+            "gas.governance_proposal_create_base" => self.governance_proposal_create_base,
+            "gas.governance_vote_base" => self.governance_vote_base,
+            "gas.staking_delegate_base" => self.staking_delegate_base,
+            "gas.staking_undelegate_base" => self.staking_undelegate_base,
+            "gas.oracle_publish_base" => self.oracle_publish_base,
+            "gas.contract_instantiate_base" => self.contract_instantiate_base,
+            "gas.contract_call_base" => self.contract_call_base,
+            "gas.contract_migrate_base" => self.contract_migrate_base,
+            "gas.per_byte" => self.per_byte,
+            "gas.per_additional_signature" => self.per_additional_signature,
+            "gas.per_kv_read" => self.per_kv_read,
+            "gas.per_kv_write" => self.per_kv_write,
+            "gas.per_event" => self.per_event,
+            "gas.per_vm_instruction" => self.per_vm_instruction,
+            "gas.dms_register_base" => self.dms_register_base,
+            "gas.dms_ping_base" => self.dms_ping_base,
+            "gas.dms_claim_base" => self.dms_claim_base,
+            "gas.min_gas_charge_bps" => self.min_gas_charge_bps,
+            _ => return None,
+        };
+        Some(value.to_string())
+    }
+
+    fn set_param(&mut self, key: &str, value: &str) -> Result<(), String> {
+        let field = match key {
+            "gas.transfer_base" => &mut self.transfer_base,
+            "gas.governance_proposal_create_base" => &mut self.governance_proposal_create_base,
+            "gas.governance_vote_base" => &mut self.governance_vote_base,
+            "gas.staking_delegate_base" => &mut self.staking_delegate_base,
+            "gas.staking_undelegate_base" => &mut self.staking_undelegate_base,
+            "gas.oracle_publish_base" => &mut self.oracle_publish_base,
+            "gas.contract_instantiate_base" => &mut self.contract_instantiate_base,
+            "gas.contract_call_base" => &mut self.contract_call_base,
+            "gas.contract_migrate_base" => &mut self.contract_migrate_base,
+            "gas.per_byte" => &mut self.per_byte,
+            "gas.per_additional_signature" => &mut self.per_additional_signature,
+            "gas.per_kv_read" => &mut self.per_kv_read,
+            "gas.per_kv_write" => &mut self.per_kv_write,
+            "gas.per_event" => &mut self.per_event,
+            "gas.per_vm_instruction" => &mut self.per_vm_instruction,
+            "gas.dms_register_base" => &mut self.dms_register_base,
+            "gas.dms_ping_base" => &mut self.dms_ping_base,
+            "gas.dms_claim_base" => &mut self.dms_claim_base,
+            "gas.min_gas_charge_bps" => &mut self.min_gas_charge_bps,
+            _ => return Err(format!("Unknown parameter: {key}")),
+        };
+        *field = parse(key, value)?;
+        Ok(())
+    }
+}
+
 impl GasSchedule {
     pub fn base_cost(&self, tx_kind: &TxKind) -> Gas {
         match tx_kind {
@@ -114,6 +215,13 @@ impl GasSchedule {
             TxKind::ContractMigrate => self.contract_migrate_base,
         }
     }
+
+    /// Gas billed to a transaction that did not run out of gas: the gas it used, but never less than the
+    /// `min_gas_charge_bps` floor of its limit (and never more than the limit)
+    pub fn charged_gas(&self, gas_used: Gas, gas_limit: Gas) -> Gas {
+        let floor = (gas_limit as u128 * self.min_gas_charge_bps.min(10_000) as u128 / 10_000) as Gas;
+        gas_used.max(floor).min(gas_limit)
+    }
 }
 
 // Gas meter for tracking consumption during execution
@@ -227,6 +335,20 @@ mod tests {
         assert_eq!(schedule.per_vm_instruction, 0); // Deferred
     }
 
+    #[test]
+    fn test_charged_gas_floor() {
+        let schedule = GasSchedule::default();
+        assert_eq!(schedule.charged_gas(1_200, 25_000), 1_200);
+
+        let floored = GasSchedule {
+            min_gas_charge_bps: 1_000, // 10%
+            ..GasSchedule::default()
+        };
+        assert_eq!(floored.charged_gas(1_200, 25_000), 2_500);
+        assert_eq!(floored.charged_gas(4_000, 25_000), 4_000);
+        assert_eq!(floored.charged_gas(30_000, 25_000), 25_000);
+    }
+
     #[test]
     fn test_intrinsic_gas_calculation() {
         let schedule = GasSchedule::default();
@@ -591,6 +713,20 @@ mod regression_tests {
         assert_eq!(schedule.per_kv_write, 120);
         assert_eq!(schedule.per_event, 80);
         assert_eq!(schedule.per_vm_instruction, 0); // Deferred
+
+        // Dead man's switch messages
+        assert_eq!(schedule.dms_register_base, 1_000);
+        assert_eq!(schedule.dms_ping_base, 500);
+        assert_eq!(schedule.dms_claim_base, 2_000);
+
+        // Schedules stored before the DMS costs were governed keep the built-in costs
+        let mut stored = serde_json::to_value(&schedule).unwrap();
+        stored.as_object_mut().unwrap().remove("dms_ping_base");
+        let stored: GasSchedule = serde_json::from_value(stored).unwrap();
+        assert_eq!(stored.dms_ping_base, 500);
+
+        // Refund policy
+        assert_eq!(schedule.min_gas_charge_bps, 0);
     }
 
     #[test]
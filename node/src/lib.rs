@@ -18,6 +18,7 @@ pub mod state;
 pub mod storage;
 pub mod types; // canonical transaction types
 pub mod util;
+pub mod webhooks; // governance event webhooks
 pub mod ws; // added util module // p2p networking and gossip
             // re-export emission types
 pub use runtime::emission::*;
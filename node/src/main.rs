@@ -15,8 +15,8 @@ use tower_http::cors::{CorsLayer, Any};
 
 // Replace crate:: module imports with library crate path so binary can access lib modules
 use dytallix_fast_node::alerts::{load_alerts_config, AlertsEngine, NodeMetricsGatherer};
-use dytallix_fast_node::execution::execute_transaction;
-use dytallix_fast_node::gas::GasSchedule;
+use dytallix_fast_node::execution::parallel::execute_block_parallel;
+use dytallix_fast_node::execution::ExecutionModules;
 use dytallix_fast_node::mempool::Mempool;
 use dytallix_fast_node::metrics::{parse_metrics_config, MetricsServer};
 use dytallix_fast_node::rpc::{self, RpcContext};
@@ -24,8 +24,16 @@ use dytallix_fast_node::runtime::bridge; // import bridge module for validator i
 use dytallix_fast_node::runtime::fee_burn::FeeBurnEngine;
 use dytallix_fast_node::runtime::emission::EmissionEngine;
 use dytallix_fast_node::runtime::governance::GovernanceConfig;
+use dytallix_fast_node::runtime::gas_schedule::GasScheduleModule;
 use dytallix_fast_node::runtime::governance::GovernanceModule;
+use dytallix_fast_node::runtime::params::{apply_params, ParamsModule};
+use dytallix_fast_node::runtime::schedule;
 use dytallix_fast_node::runtime::staking::StakingModule;
+use dytallix_fast_node::runtime::distribution::{distribute_fees, DEFAULT_COMMUNITY_TAX_BPS};
+use dytallix_fast_node::runtime::module_accounts::{mint_to_module, module_balance, STAKING_BONDED};
+use dytallix_fast_node::runtime::supply::{self, mint_to};
+use dytallix_fast_node::runtime::upgrade::{self, UpgradeModule, UPGRADE_HANDLERS};
+use dytallix_fast_node::runtime::vesting::{VestingAccount, VestingModule};
 use dytallix_fast_node::secrets; // validator key providers (Vault / sealed keystore)
 use dytallix_fast_node::state::State;
 use dytallix_fast_node::storage::{
@@ -33,6 +41,7 @@ use dytallix_fast_node::storage::{
     receipts::{TxReceipt, TxStatus},
     state::Storage,
 };
+use dytallix_fast_node::webhooks::WebhookDispatcher;
 use dytallix_fast_node::ws::server::{ws_handler, WsHub};
 use std::sync::atomic::Ordering;
 
@@ -62,9 +71,27 @@ async fn main() -> anyhow::Result<()> {
         .ok()
         .and_then(|v| v.parse().ok())
         .unwrap_or(100);
+    // Threads used for optimistic parallel execution of block transactions
+    let exec_workers: usize = std::env::var("DYT_EXEC_WORKERS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or_else(|| {
+            std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1)
+        });
     let ws_enabled = std::env::var("DYT_WS_ENABLED")
         .map(|v| v == "true" || v == "1")
         .unwrap_or(true);
+    // Check supply == sum(balances) after every block (debug builds by default)
+    let check_invariants = std::env::var("DYT_CHECK_INVARIANTS")
+        .map(|v| v == "true" || v == "1")
+        .unwrap_or(cfg!(debug_assertions));
+    // Share of each block's fees sent to the community pool at end-block
+    let community_tax_bps = std::env::var("DYT_COMMUNITY_TAX_BPS")
+        .ok()
+        .and_then(|v| v.parse::<u128>().ok())
+        .unwrap_or(DEFAULT_COMMUNITY_TAX_BPS);
     let chain_id = std::env::var("DYT_CHAIN_ID").unwrap_or("dyt-local-1".to_string());
 
     // Runtime feature flags (default disabled) - moved up to fix compilation
@@ -86,12 +113,33 @@ async fn main() -> anyhow::Result<()> {
     } else {
         storage.set_chain_id(&chain_id)?;
     }
+    // Databases from before supply tracking get their supply derived from current state
+    match supply::seed_from_state(&storage) {
+        Ok(true) => eprintln!("Seeded token supply from existing balances and pools"),
+        Ok(false) => {}
+        Err(e) => eprintln!("Supply seeding failed: {e}"),
+    }
+    // Pin the built-in gas schedule as version 1 so later binaries price history the same
+    if let Err(e) = GasScheduleModule::new(storage.clone()).init() {
+        eprintln!("Gas schedule init failed: {e}");
+    }
+    // Run the migration of an upgrade the chain halted for; binaries without its handler
+    // must not continue the chain
+    match upgrade::apply_pending_upgrade(&storage, storage.height() + 1, UPGRADE_HANDLERS) {
+        Ok(Some(name)) => eprintln!("Applied upgrade \"{name}\""),
+        Ok(None) => {}
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+    }
     let state = Arc::new(Mutex::new(State::new(storage.clone())));
     // Prefund dev faucet account if not already
     {
         let mut st = state.lock().unwrap();
         if st.balance_of("dyt1senderdev000000", "udgt") == 0 {
-            st.credit("dyt1senderdev000000", "udgt", 1_000_000);
+            mint_to(&mut st, "dyt1senderdev000000", "udgt", 1_000_000)
+                .map_err(anyhow::Error::msg)?;
         }
         // Prefund test account for E2E testing (always ensure funded)
         let test_addr = "dytallix163c72b98928b743df68324e4569e84d817a9a78b";
@@ -100,22 +148,24 @@ async fn main() -> anyhow::Result<()> {
         // Prefund DGT (governance token)
         let current_dgt = st.balance_of(test_addr, "udgt");
         if current_dgt < target_balance {
-            st.credit(test_addr, "udgt", target_balance - current_dgt);
+            mint_to(&mut st, test_addr, "udgt", target_balance - current_dgt)
+                .map_err(anyhow::Error::msg)?;
             eprintln!("Prefunded test account {} with {} udgt", test_addr, target_balance);
         }
         
         // Prefund DRT (reward token)
         let current_drt = st.balance_of(test_addr, "udrt");
         if current_drt < target_balance {
-            st.credit(test_addr, "udrt", target_balance - current_drt);
+            mint_to(&mut st, test_addr, "udrt", target_balance - current_drt)
+                .map_err(anyhow::Error::msg)?;
             eprintln!("Prefunded test account {} with {} udrt", test_addr, target_balance);
         }
         
         // Also prefund the testkey account for easier testing
         let testkey_addr = "dytallix125074e67f966c5c9a0538381c2398a8966cda568";
         let testkey_balance: u128 = 1_000_000_000; // 1000 tokens each
-        st.credit(testkey_addr, "udgt", testkey_balance);
-        st.credit(testkey_addr, "udrt", testkey_balance);
+        mint_to(&mut st, testkey_addr, "udgt", testkey_balance).map_err(anyhow::Error::msg)?;
+        mint_to(&mut st, testkey_addr, "udrt", testkey_balance).map_err(anyhow::Error::msg)?;
         eprintln!("Prefunded testkey account {} with {} udgt and udrt", testkey_addr, testkey_balance);
     }
 
@@ -129,7 +179,7 @@ async fn main() -> anyhow::Result<()> {
         ] {
             if st.balance_of(addr, "udgt") == 0 {
                 // 2000 DGT (micro units) for deposits + voting
-                st.credit(addr, "udgt", 2_000_000_000);
+                mint_to(&mut st, addr, "udgt", 2_000_000_000).map_err(anyhow::Error::msg)?;
             }
         }
     }
@@ -153,7 +203,30 @@ async fn main() -> anyhow::Result<()> {
                     for (denom, amount_val) in balances_obj.iter() {
                         if let Some(amount_str) = amount_val.as_str() {
                             if let Ok(amount) = amount_str.parse::<u128>() {
-                                st.credit(address, denom, amount);
+                                mint_to(&mut st, address, denom, amount)
+                                    .map_err(anyhow::Error::msg)?;
+                            }
+                        }
+                    }
+                    // Optional lockup over part of the balance; kept as is on restarts so
+                    // delegation tracking is not reset
+                    if let Some(vesting_val) = acc.get("vesting") {
+                        let mut vesting = VestingModule::new(storage.clone());
+                        if vesting.get(address).is_none() {
+                            match VestingAccount::from_genesis(address, vesting_val) {
+                                Ok(account)
+                                    if st.balance_of(address, &account.denom)
+                                        >= account.original_vesting =>
+                                {
+                                    if let Err(e) = vesting.set(&account) {
+                                        eprintln!("genesis vesting for {address}: {e}");
+                                    }
+                                }
+                                Ok(account) => eprintln!(
+                                    "genesis vesting for {address}: {} {} exceeds the balance",
+                                    account.original_vesting, account.denom
+                                ),
+                                Err(e) => eprintln!("genesis {e}"),
                             }
                         }
                     }
@@ -167,6 +240,13 @@ async fn main() -> anyhow::Result<()> {
     if let Some(genesis) = genesis_json.as_ref() {
         if let Some(staking) = genesis.get("staking") {
             let mut total_stake: u128 = 0;
+            // Validators stake can be delegated to
+            if let Some(validators) = staking.get("validators").and_then(|v| v.as_array()) {
+                let mut sm = staking_module.lock().unwrap();
+                for validator in validators.iter().filter_map(|v| v.as_str()) {
+                    sm.register_validator(validator);
+                }
+            }
             if let Some(delegations) = staking.get("delegations").and_then(|v| v.as_array()) {
                 let mut sm = staking_module.lock().unwrap();
                 for d in delegations {
@@ -177,6 +257,18 @@ async fn main() -> anyhow::Result<()> {
                         if let Ok(amount) = amount_str.parse::<u128>() {
                             sm.update_delegator_stake(delegator, amount);
                             total_stake = total_stake.saturating_add(amount);
+                            // Optional validator the stake is bonded to (genesis is applied
+                            // on every start, so only the missing part is attributed)
+                            if let Some(validator) = d.get("validator").and_then(|v| v.as_str()) {
+                                let attributed = sm.delegation(delegator, validator);
+                                if amount > attributed {
+                                    sm.add_validator_delegation(
+                                        delegator,
+                                        validator,
+                                        amount - attributed,
+                                    );
+                                }
+                            }
                         }
                     }
                 }
@@ -193,6 +285,14 @@ async fn main() -> anyhow::Result<()> {
                     }
                 }
                 sm.set_total_stake(total_stake);
+                // Genesis stake is bonded without a balance debit, so it is minted into
+                // the staking_bonded module account
+                let mut st = state.lock().unwrap();
+                let bonded = module_balance(&mut st, STAKING_BONDED, "udgt");
+                if total_stake > bonded {
+                    mint_to_module(&mut st, STAKING_BONDED, "udgt", total_stake - bonded)
+                        .map_err(anyhow::Error::msg)?;
+                }
             }
         }
         // Staking reward rate from genesis (if provided) unless env override exists
@@ -427,10 +527,17 @@ async fn main() -> anyhow::Result<()> {
     // Initialize bridge validators if provided
     bridge::ensure_bridge_validators(&storage.db).ok();
 
+    // Governance events are posted to these webhooks after each block
+    let gov_webhooks = WebhookDispatcher::from_env();
+    if gov_webhooks.is_enabled() {
+        println!("Governance event webhooks enabled");
+    }
+
     // Block producer task
     let producer_ctx = ctx.clone();
     tokio::spawn(async move {
         let mut ticker = interval(Duration::from_millis(block_interval_ms));
+        let mut upgrade_halt_logged = false;
         loop {
             ticker.tick().await;
             // Allow ops to pause block production to simulate stalls
@@ -443,8 +550,61 @@ async fn main() -> anyhow::Result<()> {
             let mempool_size = { producer_ctx.mempool.lock().unwrap().len() };
             producer_ctx.metrics.update_mempool_size(mempool_size);
 
-            // advance emission pools to new height (height+1)
             let next_height = producer_ctx.storage.height() + 1;
+
+            // A planned upgrade halts the chain before its height until the node is restarted
+            // with a binary that handles it
+            let halt = UpgradeModule::new(producer_ctx.storage.clone()).halt_for(next_height);
+            if let Some(plan) = halt {
+                if !upgrade_halt_logged {
+                    eprintln!(
+                        "UPGRADE \"{}\" NEEDED at height {}: halting block production ({})",
+                        plan.name, plan.height, plan.info
+                    );
+                    upgrade_halt_logged = true;
+                }
+                continue;
+            }
+
+            // Begin block: apply governed parameters changed up to the previous block
+            let governed = ParamsModule::new(producer_ctx.storage.clone()).all();
+            let gas_schedule =
+                GasScheduleModule::new(producer_ctx.storage.clone()).at_height(next_height);
+            // One statement per module, so no two locks are held together
+            let mut param_errors = Vec::new();
+            param_errors.extend(
+                apply_params(producer_ctx.mempool.lock().unwrap().config_mut(), &governed).err(),
+            );
+            param_errors.extend(
+                apply_params(&mut producer_ctx.fee_burn.lock().unwrap().config, &governed).err(),
+            );
+            param_errors.extend(
+                apply_params(
+                    &mut producer_ctx
+                        .emission
+                        .lock()
+                        .unwrap()
+                        .config
+                        .emission_breakdown,
+                    &governed,
+                )
+                .err(),
+            );
+            param_errors
+                .extend(apply_params(&mut *producer_ctx.staking.lock().unwrap(), &governed).err());
+            param_errors.extend(
+                producer_ctx
+                    .governance
+                    .lock()
+                    .unwrap()
+                    .apply_params(&governed)
+                    .err(),
+            );
+            for e in param_errors {
+                eprintln!("Governed parameter error at height {next_height}: {e}");
+            }
+
+            // advance emission pools to new height (height+1)
             producer_ctx
                 .emission
                 .lock()
@@ -466,34 +626,107 @@ async fn main() -> anyhow::Result<()> {
                         .apply_external_emission(staking_rewards);
                 }
             }
+
             let snapshot = { producer_ctx.mempool.lock().unwrap().take_snapshot(max_txs) };
-            if snapshot.is_empty() && !empty_blocks {
+            // Scheduled transfers maturing at this height are a reason to produce a block
+            let schedules_due = {
+                let mut st = producer_ctx.state.lock().unwrap();
+                !schedule::ScheduleModule::with_store(&mut *st)
+                    .due(next_height)
+                    .is_empty()
+            };
+            if snapshot.is_empty() && !empty_blocks && !schedules_due {
                 continue;
             }
 
+            // Begin block: release scheduled transfers maturing at this height
+            let released = {
+                let mut st = producer_ctx.state.lock().unwrap();
+                schedule::release_matured(&mut st, next_height)
+            };
+            match released {
+                Ok(events) if !events.is_empty() => {
+                    let _ = producer_ctx.storage.index_block_events(next_height, &events);
+                    if ws_enabled {
+                        for e in &events {
+                            producer_ctx.ws.broadcast_json(&json!({"type": e.event_type, "height": next_height, "attributes": e.attributes}));
+                        }
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => eprintln!("Schedule release error at height {next_height}: {e}"),
+            }
+
             let mut total_gas_used = 0u64;
-            let gas_schedule = GasSchedule::default();
 
             // Execute transactions using deterministic execution engine
             let mut receipts: Vec<TxReceipt> = vec![];
             let mut applied: Vec<dytallix_fast_node::storage::tx::Transaction> = vec![];
             {
+                // Lock order: governance -> staking -> state -> fee_burn
+                let mut governance = producer_ctx
+                    .features
+                    .governance
+                    .then(|| producer_ctx.governance.lock().unwrap());
+                let mut staking = producer_ctx
+                    .features
+                    .staking
+                    .then(|| producer_ctx.staking.lock().unwrap());
                 let mut st = producer_ctx.state.lock().unwrap();
                 let mut fee_burn = producer_ctx.fee_burn.lock().unwrap();
-                for (tx_index, tx) in snapshot.iter().enumerate() {
-                    let tx_start_time = SystemTime::now();
-
-                    // Use deterministic execution engine
-                    let result = execute_transaction(
-                        tx,
-                        &mut st,
-                        next_height,
-                        tx_index as u32,
-                        &gas_schedule,
-                        Some(&mut *fee_burn),
+                let exec_start_time = SystemTime::now();
+
+                let modules = ExecutionModules {
+                    staking: staking.as_deref_mut(),
+                    governance: governance.as_deref_mut(),
+                    #[cfg(feature = "contracts")]
+                    wasm: Some(&*producer_ctx.wasm_runtime),
+                };
+
+                // Deterministic execution engine, run optimistically in parallel; results
+                // match executing the snapshot sequentially
+                let block_exec = execute_block_parallel(
+                    &snapshot,
+                    &mut st,
+                    next_height,
+                    &gas_schedule.schedule,
+                    Some(&mut *fee_burn),
+                    modules,
+                    exec_workers,
+                );
+                if block_exec.conflicts > 0 {
+                    tracing::debug!(
+                        "block {next_height}: re-executed {} conflicting tx(s)",
+                        block_exec.conflicts
                     );
+                }
+
+                // End block: pay out the fee collector to the community pool and validators
+                if let Some(staking) = staking.as_deref() {
+                    match distribute_fees(&mut st, staking, community_tax_bps) {
+                        Ok(events) if !events.is_empty() => {
+                            let _ = producer_ctx.storage.index_block_events(next_height, &events);
+                            if ws_enabled {
+                                for e in &events {
+                                    producer_ctx.ws.broadcast_json(&json!({"type": e.event_type, "height": next_height, "attributes": e.attributes}));
+                                }
+                            }
+                        }
+                        Ok(_) => {}
+                        Err(e) => eprintln!("Fee distribution error at height {next_height}: {e}"),
+                    }
+                }
 
+                // Transactions run concurrently, so record the average processing time
+                let per_tx_time = exec_start_time
+                    .elapsed()
+                    .ok()
+                    .map(|elapsed| elapsed / snapshot.len().max(1) as u32);
+                for (tx, result) in snapshot.iter().zip(block_exec.results) {
                     total_gas_used += result.gas_used;
+                    producer_ctx
+                        .metrics
+                        .record_fee_settlement(result.fee_refunded, result.fee_burned);
                     receipts.push(result.receipt);
 
                     // Only include successful transactions in the block
@@ -501,8 +734,7 @@ async fn main() -> anyhow::Result<()> {
                         applied.push(tx.clone());
                     }
 
-                    // Record transaction processing time
-                    if let Ok(elapsed) = tx_start_time.elapsed() {
+                    if let Some(elapsed) = per_tx_time {
                         producer_ctx.metrics.record_transaction(elapsed);
                     }
                 }
@@ -581,11 +813,42 @@ async fn main() -> anyhow::Result<()> {
 
             // Process governance end block if feature enabled
             if producer_ctx.features.governance {
-                if let Err(e) = producer_ctx.governance.lock().unwrap().end_block(height) {
-                    eprintln!("Governance end_block error at height {height}: {e}");
+                // Events of this block's governance txs and of end_block
+                let gov_events = {
+                    let mut governance = producer_ctx.governance.lock().unwrap();
+                    if let Err(e) = governance.end_block(height) {
+                        eprintln!("Governance end_block error at height {height}: {e}");
+                    }
+                    governance.take_events()
+                };
+                if !gov_events.is_empty() {
+                    let events: Vec<_> = gov_events.iter().map(|e| e.to_event()).collect();
+                    let _ = producer_ctx.storage.index_block_events(height, &events);
+                    let messages: Vec<_> = gov_events
+                        .iter()
+                        .zip(&events)
+                        .map(|(g, e)| json!({"type": e.event_type, "height": height, "attributes": e.attributes, "event": g}))
+                        .collect();
+                    if ws_enabled {
+                        for m in &messages {
+                            producer_ctx.ws.broadcast_json(m);
+                        }
+                    }
+                    if gov_webhooks.is_enabled() {
+                        gov_webhooks.dispatch(json!({"height": height, "events": messages}));
+                    }
+                }
+            }
+
+            if check_invariants {
+                for check in supply::check_invariants(&producer_ctx.storage) {
+                    if !check.holds() {
+                        eprintln!(
+                            "Supply invariant violated at height {height}: {} supply={} balances={} module_accounts={}",
+                            check.denom, check.supply, check.balances, check.module_accounts
+                        );
+                    }
                 }
-                // Clear governance events after processing
-                producer_ctx.governance.lock().unwrap().clear_events();
             }
 
             producer_ctx
@@ -633,6 +896,7 @@ async fn main() -> anyhow::Result<()> {
         .route("/balance/:addr", get(rpc::get_balance))
         .route("/account/:addr", get(rpc::get_account))
         .route("/tx/:hash", get(rpc::get_tx))
+        .route("/tx/simulate", post(rpc::tx_simulate))
         .route("/transactions", get(rpc::list_transactions)) // List all transactions
         .route("/transactions/:hash", get(rpc::get_tx)) // Standard endpoint path
         .route("/transactions/pending", get(rpc::get_pending_transactions)) // Pending transactions list
@@ -655,6 +919,13 @@ async fn main() -> anyhow::Result<()> {
         .route("/emission/claim", post(rpc::emission_claim))
         .route("/api/rewards", get(rpc::get_rewards))
         .route("/api/rewards/:height", get(rpc::get_rewards_by_height))
+        .route("/api/feegrants/:granter", get(rpc::get_fee_grants))
+        .route("/api/schedules/:addr", get(rpc::get_schedules))
+        .route("/api/denoms", get(rpc::get_denoms))
+        .route("/api/supply", get(rpc::get_supply))
+        .route("/api/upgrade", get(rpc::get_upgrade))
+        .route("/gas/schedule", get(rpc::get_gas_schedule))
+        .route("/events", get(rpc::query_events))
         .route("/api/stats", get(rpc::stats_with_emission))
         .route("/api/contracts", get(rpc::list_contracts))
         .route(
@@ -674,13 +945,10 @@ async fn main() -> anyhow::Result<()> {
     // WASM contract routes
     #[cfg(feature = "contracts")]
     {
-        app = app
-            .route("/contracts/deploy", post(rpc::contracts_deploy))
-            .route("/contracts/call", post(rpc::contracts_call))
-            .route(
-                "/contracts/state/:contract_address/:key",
-                get(rpc::contracts_state),
-            );
+        app = app.route(
+            "/contracts/state/:contract_address/:key",
+            get(rpc::contracts_state),
+        );
     }
 
     // Oracle routes
@@ -699,12 +967,8 @@ async fn main() -> anyhow::Result<()> {
             .route("/oracle/stats", get(rpc::oracle::oracle_stats));
     }
 
-    // Governance routes (always exposed; tx endpoints return 501 if disabled)
+    // Governance query routes (always exposed)
     app = app
-        .route("/gov/submit", post(rpc::gov_submit_proposal))
-        .route("/gov/deposit", post(rpc::gov_deposit))
-        .route("/gov/vote", post(rpc::gov_vote))
-        .route("/gov/execute", post(rpc::gov_execute))
         .route("/gov/proposal/:id", get(rpc::gov_get_proposal))
         .route("/gov/tally/:id", get(rpc::gov_tally))
         .route("/gov/config", get(rpc::gov_get_config))
@@ -722,11 +986,8 @@ async fn main() -> anyhow::Result<()> {
             get(rpc::gov_get_total_voting_power),
         );
 
-    // Staking routes (always exposed; tx endpoints return 501 if disabled)
+    // Staking query routes (always exposed)
     app = app
-        .route("/api/staking/claim", post(rpc::staking_claim))
-        .route("/api/staking/delegate", post(rpc::staking_delegate))
-        .route("/api/staking/undelegate", post(rpc::staking_undelegate))
         .route(
             "/api/staking/accrued/:address",
             get(rpc::staking_get_accrued),
@@ -738,6 +999,29 @@ async fn main() -> anyhow::Result<()> {
         .route("/api/staking/stats", get(rpc::staking_get_stats))
         .route("/api/staking/validators", get(rpc::staking_get_validators));
 
+    // Legacy unauthenticated write endpoints act for any address without a signature;
+    // dev-only. Signed staking, governance and contract messages go through /submit.
+    let enable_legacy_tx_endpoints = std::env::var("DYT_ENABLE_LEGACY_TX_ENDPOINTS")
+        .map(|v| v == "1" || v.to_lowercase() == "true")
+        .unwrap_or(false);
+    if enable_legacy_tx_endpoints {
+        eprintln!("WARNING: legacy unauthenticated staking/gov/contract endpoints enabled");
+        app = app
+            .route("/gov/submit", post(rpc::gov_submit_proposal))
+            .route("/gov/deposit", post(rpc::gov_deposit))
+            .route("/gov/vote", post(rpc::gov_vote))
+            .route("/gov/execute", post(rpc::gov_execute))
+            .route("/api/staking/claim", post(rpc::staking_claim))
+            .route("/api/staking/delegate", post(rpc::staking_delegate))
+            .route("/api/staking/undelegate", post(rpc::staking_undelegate));
+        #[cfg(feature = "contracts")]
+        {
+            app = app
+                .route("/contracts/deploy", post(rpc::contracts_deploy))
+                .route("/contracts/call", post(rpc::contracts_call));
+        }
+    }
+
     // API routes for explorer search (aliases with /api prefix)
     app = app
         .route("/api/block/:id", get(rpc::get_block))
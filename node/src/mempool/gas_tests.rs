@@ -34,14 +34,14 @@ mod mempool_gas_tests {
     #[test]
     fn test_gas_validation_success() {
         let tx = create_test_transaction(25000, 1000);
-        let result = validate_gas(&tx);
+        let result = validate_gas(&tx, &GasSchedule::default());
         assert!(result.is_ok());
     }
 
     #[test]
     fn test_gas_validation_zero_price() {
         let tx = create_test_transaction(25000, 0);
-        let result = validate_gas(&tx);
+        let result = validate_gas(&tx, &GasSchedule::default());
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("gas price cannot be zero"));
     }
@@ -50,7 +50,7 @@ mod mempool_gas_tests {
     fn test_gas_validation_low_limit() {
         // Create transaction with very low gas limit
         let tx = create_test_transaction(100, 1000);
-        let result = validate_gas(&tx);
+        let result = validate_gas(&tx, &GasSchedule::default());
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("GasValidationError"));
     }
@@ -58,11 +58,56 @@ mod mempool_gas_tests {
     #[test]
     fn test_estimate_tx_size() {
         let tx = create_test_transaction(25000, 1000);
-        let size = estimate_tx_size(&tx);
+        let size = tx.encoded_size();
 
         // Should be reasonable size for a transaction
         assert!(size > 50);
         assert!(size < 1000);
+        // Locked vector: canonical JSON of the full transaction
+        assert_eq!(size, 230);
+    }
+
+    #[test]
+    fn test_encoded_size_counts_pqc_envelope_and_messages() {
+        // ML-DSA-87 sized key (2592 bytes) and signature (4627 bytes), base64 encoded
+        let mut tx = create_test_transaction(25000, 1000).with_pqc(
+            "B".repeat(3456),
+            "dyt-localnet",
+            "hello",
+        );
+        tx.signature = Some("A".repeat(6172));
+        assert_eq!(tx.encoded_size(), 9859);
+
+        let tx = tx.with_messages(vec![
+            TxMessage::Send {
+                from: "dytallix1test_from".to_string(),
+                to: "dytallix1test_to".to_string(),
+                denom: "udgt".to_string(),
+                amount: 1000,
+            },
+            TxMessage::Data {
+                from: "dytallix1test_from".to_string(),
+                data: "ping".to_string(),
+            },
+        ]);
+        assert_eq!(tx.encoded_size(), 10029);
+
+        // per_byte intrinsic gas is charged on the full encoded size
+        let schedule = GasSchedule::default();
+        assert_eq!(
+            crate::gas::intrinsic_gas(&TxKind::Transfer, tx.encoded_size(), 0, &schedule).unwrap(),
+            500 + 2 * 10029
+        );
+        assert!(validate_gas(&tx, &schedule).is_ok());
+        assert!(validate_gas(&tx.clone().with_gas(2000, 1000), &schedule).is_err());
+    }
+
+    #[test]
+    fn test_pending_tx_size_matches_encoded_size() {
+        let tx = create_test_transaction(25000, 1000);
+        let size = tx.encoded_size();
+        let pending = PendingTx::new(tx);
+        assert_eq!(pending.serialized_size, size);
     }
 
     #[test]
@@ -70,7 +115,7 @@ mod mempool_gas_tests {
         // This test would require a proper State implementation
         // For now, just test the gas validation part
         let tx = create_test_transaction(25000, 1000);
-        let result = validate_gas(&tx);
+        let result = validate_gas(&tx, &GasSchedule::default());
         assert!(result.is_ok());
     }
 
@@ -97,8 +142,8 @@ mod mempool_gas_tests {
         let tx1 = create_test_transaction(25000, 1000);
         let tx2 = create_test_transaction(25000, 1000);
 
-        let result1 = validate_gas(&tx1);
-        let result2 = validate_gas(&tx2);
+        let result1 = validate_gas(&tx1, &GasSchedule::default());
+        let result2 = validate_gas(&tx2, &GasSchedule::default());
 
         // Same inputs should produce same results
         assert_eq!(result1.is_ok(), result2.is_ok());
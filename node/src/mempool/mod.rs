@@ -6,6 +6,13 @@ use crate::crypto::{canonical_json, sha3_256, PQCAlgorithm, PQCVerifyError};
 use crate::gas::{validate_gas_limit, GasSchedule, TxKind};
 use crate::state::State;
 use crate::storage::tx::{Transaction, TxMessage};
+use crate::runtime::fee_grant::FeeGrantModule;
+use crate::runtime::gas_schedule::GasScheduleModule;
+use crate::runtime::params::{parse, GovernedParams};
+use crate::runtime::token_factory::{parse_factory_denom, TokenFactoryModule};
+use crate::runtime::vesting::VestingModule;
+use crate::types::multisig::MultisigAuth;
+use crate::types::tx::FeePayerSignature;
 use base64::{engine::general_purpose::STANDARD as B64, Engine};
 use dytallix_node::policy::signature_policy::{PolicyError, PolicyManager};
 
@@ -34,6 +41,8 @@ pub enum RejectionReason {
     OversizedTx { max: usize, got: usize },
     Duplicate(String),
     PolicyViolation(String),
+    FeeAllowanceDenied(String),
+    UnknownDenom(String),
     InternalError(String),
 }
 
@@ -48,6 +57,8 @@ impl RejectionReason {
             RejectionReason::OversizedTx { .. } => "oversized_tx",
             RejectionReason::Duplicate(_) => "duplicate",
             RejectionReason::PolicyViolation(_) => "policy_violation",
+            RejectionReason::FeeAllowanceDenied(_) => "fee_allowance_denied",
+            RejectionReason::UnknownDenom(_) => "unknown_denom",
             RejectionReason::InternalError(_) => "internal_error",
         }
     }
@@ -75,6 +86,8 @@ impl std::fmt::Display for RejectionReason {
                 write!(f, "oversized transaction: max {max}, got {got}")
             }
             RejectionReason::PolicyViolation(msg) => write!(f, "policy violation: {msg}"),
+            RejectionReason::FeeAllowanceDenied(msg) => write!(f, "fee allowance denied: {msg}"),
+            RejectionReason::UnknownDenom(denom) => write!(f, "unknown denom: {denom}"),
             RejectionReason::InternalError(msg) => write!(f, "internal error: {msg}"),
         }
     }
@@ -112,6 +125,31 @@ impl Default for MempoolConfig {
     }
 }
 
+impl GovernedParams for MempoolConfig {
+    const MODULE: &'static str = "mempool";
+
+    fn get_param(&self, key: &str) -> Option<String> {
+        Some(match key {
+            "mempool.max_tx_bytes" => self.max_tx_bytes.to_string(),
+            "mempool.min_gas_price" => self.min_gas_price.to_string(),
+            "mempool.max_txs" => self.max_txs.to_string(),
+            "mempool.max_bytes" => self.max_bytes.to_string(),
+            _ => return None,
+        })
+    }
+
+    fn set_param(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "mempool.max_tx_bytes" => self.max_tx_bytes = parse(key, value)?,
+            "mempool.min_gas_price" => self.min_gas_price = parse(key, value)?,
+            "mempool.max_txs" => self.max_txs = parse(key, value)?,
+            "mempool.max_bytes" => self.max_bytes = parse(key, value)?,
+            _ => return Err(format!("Unknown parameter: {key}")),
+        }
+        Ok(())
+    }
+}
+
 /// Transaction with priority ordering
 #[derive(Debug, Clone)]
 pub struct PendingTx {
@@ -147,7 +185,7 @@ impl PartialOrd for TxPriorityKey {
 
 impl PendingTx {
     pub fn new(tx: Transaction) -> Self {
-        let serialized_size = estimate_tx_size(&tx);
+        let serialized_size = tx.encoded_size();
         let received_at = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap()
@@ -235,14 +273,16 @@ impl Mempool {
             if amount == 0 {
                 return;
             }
-            let denom_key = denom.to_ascii_lowercase();
-            let entry = required.entry(denom_key).or_insert(0);
+            // Denoms are already canonical base units (see `DenomRegistry`)
+            let entry = required.entry(denom.to_string()).or_insert(0);
             *entry = (*entry).saturating_add(amount);
         };
 
-        // Fees and gas are always denominated in udgt for now.
-        let gas_cost = (tx.gas_limit as u128) * (tx.gas_price as u128);
-        add("udgt", tx.fee.saturating_add(gas_cost));
+        // Fees and gas are always denominated in udgt for now; sponsored fees
+        // are reserved against the fee payer instead (see `sponsored_fee`).
+        if tx.fee_payer.is_none() {
+            add("udgt", Self::fee_reservation(tx));
+        }
 
         if let Some(messages) = &tx.messages {
             for message in messages {
@@ -255,6 +295,30 @@ impl Mempool {
                     TxMessage::DmsRegister { .. } | TxMessage::DmsPing { .. } | TxMessage::DmsClaim { .. } => {
                         // DMS messages don't require token reserves from sender, only fee
                     }
+                    TxMessage::Delegate { amount, .. } | TxMessage::Deposit { amount, .. } => {
+                        // Bonded stake and proposal deposits leave the sender's udgt balance
+                        add("udgt", *amount)
+                    }
+                    TxMessage::ScheduleTransfer { denom, amount, .. } => {
+                        // Escrowed until the release height
+                        add(denom, *amount)
+                    }
+                    TxMessage::Burn { denom, amount, .. } => add(denom, *amount),
+                    TxMessage::Undelegate { .. }
+                    | TxMessage::ClaimRewards { .. }
+                    | TxMessage::SubmitProposal { .. }
+                    | TxMessage::Vote { .. }
+                    | TxMessage::WeightedVote { .. }
+                    | TxMessage::ContractDeploy { .. }
+                    | TxMessage::ContractCall { .. }
+                    | TxMessage::GrantFeeAllowance { .. }
+                    | TxMessage::RevokeFeeAllowance { .. }
+                    | TxMessage::CancelSchedule { .. }
+                    | TxMessage::CreateDenom { .. }
+                    | TxMessage::Mint { .. }
+                    | TxMessage::ChangeAdmin { .. } => {
+                        // Only the fee is reserved
+                    }
                 }
             }
         } else {
@@ -265,6 +329,32 @@ impl Mempool {
         required
     }
 
+    fn fee_reservation(tx: &Transaction) -> u128 {
+        let gas_cost = (tx.gas_limit as u128) * (tx.gas_price as u128);
+        tx.fee.saturating_add(gas_cost)
+    }
+
+    /// Fee held against the sponsoring account of a fee-payer transaction
+    fn sponsored_fee(tx: &Transaction) -> Option<(&str, HashMap<String, u128>)> {
+        let payer = tx.fee_payer.as_deref()?;
+        let fee = Self::fee_reservation(tx);
+        Some((payer, HashMap::from([("udgt".to_string(), fee)])))
+    }
+
+    fn reserve_tx(reserved: &mut HashMap<String, HashMap<String, u128>>, tx: &Transaction) {
+        Self::add_reserved_amounts(reserved, &tx.from, &Self::reserved_amounts_for_tx(tx));
+        if let Some((payer, fee)) = Self::sponsored_fee(tx) {
+            Self::add_reserved_amounts(reserved, payer, &fee);
+        }
+    }
+
+    fn release_tx(reserved: &mut HashMap<String, HashMap<String, u128>>, tx: &Transaction) {
+        Self::subtract_reserved_amounts(reserved, &tx.from, &Self::reserved_amounts_for_tx(tx));
+        if let Some((payer, fee)) = Self::sponsored_fee(tx) {
+            Self::subtract_reserved_amounts(reserved, payer, &fee);
+        }
+    }
+
     fn add_reserved_amounts(
         reserved: &mut HashMap<String, HashMap<String, u128>>,
         sender: &str,
@@ -273,7 +363,7 @@ impl Mempool {
         if delta.is_empty() {
             return;
         }
-        let entry = reserved.entry(sender.to_string()).or_insert_with(HashMap::new);
+        let entry = reserved.entry(sender.to_string()).or_default();
         for (denom, amount) in delta {
             if *amount == 0 {
                 continue;
@@ -353,7 +443,7 @@ impl Mempool {
         }
 
         // 3. Size check
-        let tx_size = estimate_tx_size(&tx);
+        let tx_size = tx.encoded_size();
         if tx_size > self.config.max_tx_bytes {
             return Err(RejectionReason::OversizedTx {
                 max: self.config.max_tx_bytes,
@@ -390,10 +480,9 @@ impl Mempool {
         // Ensure capacity before inserting (counts all)
         self.ensure_capacity_for(&pending_tx)?;
 
-        let reserved_delta = Self::reserved_amounts_for_tx(&tx);
         if tx.nonce == expected {
             // Eligible now: track reserved and eligible count
-            Self::add_reserved_amounts(&mut self.reserved_by_sender, &tx.from, &reserved_delta);
+            Self::reserve_tx(&mut self.reserved_by_sender, &tx);
             self.insert_eligible(pending_tx);
             // Try to promote any deferred txs for this sender
             self.try_promote_deferred(&tx.from, state);
@@ -414,14 +503,39 @@ impl Mempool {
         let account = state.snapshot_account(&tx.from);
         let existing_reserved = self.reserved_by_sender.get(&tx.from);
         let required = Self::reserved_amounts_for_tx(tx);
-
+        // Vesting lockups as of the next block cannot cover amounts or fees, but may be
+        // bonded by this transaction's delegations
+        let vesting = VestingModule::new(state.storage.clone());
+        let next_height = state.storage.height() + 1;
+        let delegated: u128 = tx
+            .messages
+            .iter()
+            .flatten()
+            .map(|m| match m {
+                TxMessage::Delegate { amount, .. } => *amount,
+                _ => 0,
+            })
+            .sum();
+
+        let factory = TokenFactoryModule::new(state.storage.clone());
         for (denom, needed_now) in required.iter() {
+            // User-created denoms must be registered with the token factory
+            if parse_factory_denom(denom).is_some() && !factory.is_registered(denom) {
+                return Err(RejectionReason::UnknownDenom(denom.clone()));
+            }
             let already_reserved = existing_reserved
                 .and_then(|m| m.get(denom))
                 .copied()
                 .unwrap_or(0);
             let total_needed = already_reserved.saturating_add(*needed_now);
-            let available = account.balance_of(denom);
+            let available = account
+                .balance_of(denom)
+                .saturating_sub(match denom.as_str() {
+                    "udgt" => vesting
+                        .locked(&tx.from, denom, next_height)
+                        .saturating_sub(delegated),
+                    _ => vesting.locked(&tx.from, denom, next_height),
+                });
 
             if available < total_needed {
                 eprintln!(
@@ -439,14 +553,72 @@ impl Mempool {
             }
         }
 
+        if let Some((payer, fee)) = Self::sponsored_fee(tx) {
+            self.validate_fee_payer(state, tx, payer, fee["udgt"])?;
+        }
+
         // Gas validation
         if tx.gas_limit > 0 || tx.gas_price > 0 {
-            validate_gas(tx).map_err(RejectionReason::InternalError)?;
+            let schedule = GasScheduleModule::new(state.storage.clone())
+                .at_height(state.storage.height() + 1)
+                .schedule;
+            validate_gas(tx, &schedule).map_err(RejectionReason::InternalError)?;
         }
 
         Ok(())
     }
 
+    /// The fee payer must cover the fee on top of its own reservations, and an
+    /// un-cosigned sponsorship needs a fee allowance from the payer to the sender
+    fn validate_fee_payer(
+        &self,
+        state: &State,
+        tx: &Transaction,
+        payer: &str,
+        fee: u128,
+    ) -> Result<(), RejectionReason> {
+        let already_reserved = self
+            .reserved_by_sender
+            .get(payer)
+            .and_then(|m| m.get("udgt"))
+            .copied()
+            .unwrap_or(0);
+        let total_needed = already_reserved.saturating_add(fee);
+        let locked = VestingModule::new(state.storage.clone()).locked(
+            payer,
+            "udgt",
+            state.storage.height() + 1,
+        );
+        let available = state
+            .snapshot_account(payer)
+            .balance_of("udgt")
+            .saturating_sub(locked);
+        if available < total_needed {
+            return Err(RejectionReason::InsufficientFunds {
+                denom: "udgt".to_string(),
+                required: total_needed,
+                available,
+            });
+        }
+
+        if tx.fee_payer_signature.is_none() {
+            let grants = FeeGrantModule::new(state.storage.clone());
+            let allowance = grants
+                .get_allowance(payer, &tx.from)
+                .ok_or_else(|| RejectionReason::FeeAllowanceDenied(format!(
+                    "no fee allowance from {payer} to {}",
+                    tx.from
+                )))?;
+            if allowance.spend_limit < fee {
+                return Err(RejectionReason::FeeAllowanceDenied(format!(
+                    "fee allowance exceeded: {} < {fee}",
+                    allowance.spend_limit
+                )));
+            }
+        }
+        Ok(())
+    }
+
     /// Insert an eligible transaction into main structures
     fn insert_eligible(&mut self, pending_tx: PendingTx) {
         let key = pending_tx.priority_key();
@@ -473,8 +645,7 @@ impl Mempool {
             .entry(pending_tx.tx.nonce)
             .or_default();
         // Track reserved value for deferred txs as well
-        let delta = Self::reserved_amounts_for_tx(&pending_tx.tx);
-        Self::add_reserved_amounts(&mut self.reserved_by_sender, &pending_tx.tx.from, &delta);
+        Self::reserve_tx(&mut self.reserved_by_sender, &pending_tx.tx);
         self.deferred_lookup
             .insert(pending_tx.tx.hash.clone(), pending_tx);
     }
@@ -616,12 +787,7 @@ impl Mempool {
                             self.eligible_by_sender.remove(&tx.tx.from);
                         }
                     }
-                    let delta = Self::reserved_amounts_for_tx(&tx.tx);
-                    Self::subtract_reserved_amounts(
-                        &mut self.reserved_by_sender,
-                        &tx.tx.from,
-                        &delta,
-                    );
+                    Self::release_tx(&mut self.reserved_by_sender, &tx.tx);
                     log::info!("Evicted transaction {} due to capacity", key.hash);
                 }
             } else {
@@ -636,12 +802,7 @@ impl Mempool {
                     }
                     self.tx_hashes.remove(&key.hash);
                     self.total_bytes = self.total_bytes.saturating_sub(tx.serialized_size);
-                    let delta = Self::reserved_amounts_for_tx(&tx.tx);
-                    Self::subtract_reserved_amounts(
-                        &mut self.reserved_by_sender,
-                        &tx.tx.from,
-                        &delta,
-                    );
+                    Self::release_tx(&mut self.reserved_by_sender, &tx.tx);
                     log::info!("Evicted deferred transaction {} due to capacity", key.hash);
                 }
             }
@@ -679,12 +840,7 @@ impl Mempool {
                         self.eligible_by_sender.remove(&pending_tx.tx.from);
                     }
                 }
-                let delta = Self::reserved_amounts_for_tx(&pending_tx.tx);
-                Self::subtract_reserved_amounts(
-                    &mut self.reserved_by_sender,
-                    &pending_tx.tx.from,
-                    &delta,
-                );
+                Self::release_tx(&mut self.reserved_by_sender, &pending_tx.tx);
 
                 // Record removed nonce per sender (eligible inclusion)
                 let sender = pending_tx.tx.from.clone();
@@ -706,12 +862,7 @@ impl Mempool {
                 self.tx_hashes.remove(hash);
                 self.total_bytes = self.total_bytes.saturating_sub(deferred_tx.serialized_size);
                 // Update reserved totals
-                let delta = Self::reserved_amounts_for_tx(&deferred_tx.tx);
-                Self::subtract_reserved_amounts(
-                    &mut self.reserved_by_sender,
-                    &deferred_tx.tx.from,
-                    &delta,
-                );
+                Self::release_tx(&mut self.reserved_by_sender, &deferred_tx.tx);
             }
         }
 
@@ -772,6 +923,10 @@ impl Mempool {
         &self.config
     }
 
+    pub fn config_mut(&mut self) -> &mut MempoolConfig {
+        &mut self.config
+    }
+
     /// Push a transaction into the mempool (RPC method)
     pub fn push(&mut self, tx: Transaction) -> Result<(), RejectionReason> {
         self.add_transaction(&State::default(), tx)
@@ -809,6 +964,14 @@ impl std::error::Error for MempoolError {}
 
 /// Verify transaction envelope (signature validation)
 pub(crate) fn verify_envelope(tx: &Transaction) -> bool {
+    if let Some(payer_sig) = &tx.fee_payer_signature {
+        if verify_fee_payer_envelope(tx, payer_sig).is_err() {
+            return false;
+        }
+    }
+    if let Some(multisig) = &tx.multisig {
+        return verify_multisig_envelope(tx, multisig).is_ok();
+    }
     match (&tx.signature, &tx.public_key) {
         (Some(signature), Some(public_key)) => {
             // Perform real PQC signature verification
@@ -821,6 +984,46 @@ pub(crate) fn verify_envelope(tx: &Transaction) -> bool {
     }
 }
 
+/// Verify k-of-n multisig signatures over the canonical transaction fields
+fn verify_multisig_envelope(tx: &Transaction, multisig: &MultisigAuth) -> Result<(), String> {
+    let address = multisig.public_key.address();
+    if tx.from != address {
+        return Err(format!(
+            "multisig sender mismatch: expected {address}, got {}",
+            tx.from
+        ));
+    }
+    let tx_bytes = canonical_json(&tx.canonical_fields())
+        .map_err(|e| format!("failed to serialize transaction: {e}"))?;
+    multisig
+        .verify(&sha3_256(&tx_bytes), PQCAlgorithm::default())
+        .map_err(|e| e.to_string())
+}
+
+/// Verify the fee payer's co-signature over the canonical transaction fields
+fn verify_fee_payer_envelope(
+    tx: &Transaction,
+    payer_sig: &FeePayerSignature,
+) -> Result<(), String> {
+    let fee_payer = tx
+        .fee_payer
+        .as_deref()
+        .ok_or("fee payer signature without fee_payer")?;
+    let pk = B64
+        .decode(&payer_sig.public_key)
+        .map_err(|e| format!("invalid fee payer public key encoding: {e}"))?;
+    if crate::addr::get_address(&pk) != fee_payer {
+        return Err(format!("fee payer public key does not match {fee_payer}"));
+    }
+    let sig = B64
+        .decode(&payer_sig.signature)
+        .map_err(|e| format!("invalid fee payer signature encoding: {e}"))?;
+    let tx_bytes = canonical_json(&tx.canonical_fields())
+        .map_err(|e| format!("failed to serialize transaction: {e}"))?;
+    crate::crypto::pqc_verify::verify(&pk, &sha3_256(&tx_bytes), &sig, PQCAlgorithm::default())
+        .map_err(|e| e.to_string())
+}
+
 /// Verify PQC signature for a transaction
 fn verify_pqc_signature(tx: &Transaction, signature: &str, public_key: &str) -> Result<(), String> {
     // 1. Decode base64 signature and public key
@@ -850,7 +1053,7 @@ fn verify_pqc_signature(tx: &Transaction, signature: &str, public_key: &str) ->
     // 4. Hash with SHA3-256
     let tx_hash = sha3_256(&tx_bytes);
 
-    tracing::info!("Transaction hash: {}", hex::encode(&tx_hash));
+    tracing::info!("Transaction hash: {}", hex::encode(tx_hash));
 
     // 5. Verify signature using new multi-algorithm verification
     // For mempool transactions, we use the default algorithm (Dilithium5)
@@ -898,16 +1101,14 @@ pub fn basic_validate(state: &State, tx: &Transaction) -> Result<(), String> {
 }
 
 /// Gas validation function with enhanced error reporting
-fn validate_gas(tx: &Transaction) -> Result<(), String> {
-    let schedule = GasSchedule::default();
-
+fn validate_gas(tx: &Transaction, schedule: &GasSchedule) -> Result<(), String> {
     // For now, assume all transactions are transfers
     // This will be extended when we have better transaction type detection
     let tx_kind = TxKind::Transfer;
 
-    // Estimate transaction size (approximation for now)
-    let tx_size_bytes = estimate_tx_size(tx);
-    let additional_signatures = 0; // Single signature for now
+    // Canonical encoded size, same figure used for mempool byte limits
+    let tx_size_bytes = tx.encoded_size();
+    let additional_signatures = tx.additional_signatures();
 
     // Validate gas limit against intrinsic requirements
     validate_gas_limit(
@@ -915,7 +1116,7 @@ fn validate_gas(tx: &Transaction) -> Result<(), String> {
         tx_size_bytes,
         additional_signatures,
         tx.gas_limit,
-        &schedule,
+        schedule,
     )
     .map_err(|e| format!("GasValidationError: {e}"))?;
 
@@ -927,30 +1128,6 @@ fn validate_gas(tx: &Transaction) -> Result<(), String> {
     Ok(())
 }
 
-/// Estimate transaction size for gas calculation and size limits
-fn estimate_tx_size(tx: &Transaction) -> usize {
-    // Cap the signature contribution to avoid pathological sizes from different
-    // signature schemes (e.g., PQC). This keeps mempool accounting predictable.
-    const MAX_SIG_ACCOUNTING: usize = 256;
-
-    let sig_len = tx
-        .signature
-        .as_ref()
-        .map_or(0, |s| s.len().min(MAX_SIG_ACCOUNTING));
-
-    // Rough estimate based on serialized fields
-    // This should be more precise in production
-    tx.hash.len() +
-    tx.from.len() +
-    tx.to.len() +
-    16 + // amount (u128)
-    16 + // fee (u128)
-    8 +  // nonce (u64)
-    sig_len +
-    8 +  // gas_limit (u64)
-    8 // gas_price (u64)
-}
-
 impl Mempool {
     /// Validate transaction signature algorithm against policy
     fn validate_signature_policy(&self, tx: &Transaction) -> Result<(), PolicyError> {
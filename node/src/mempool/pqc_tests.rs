@@ -119,4 +119,37 @@ mod tests {
             _ => panic!("Expected InvalidSignature rejection"),
         }
     }
+
+    #[test]
+    fn test_verify_envelope_multisig_threshold() {
+        use crate::types::multisig::{MultisigAuth, MultisigPubKey, MultisigSignature};
+
+        let members: Vec<(Vec<u8>, Vec<u8>)> = (0..3).map(|_| ActivePQC::keypair()).collect();
+        let key = MultisigPubKey::new(2, members.iter().map(|(_, pk)| B64.encode(pk)).collect())
+            .unwrap();
+        let tx = Transaction::base("test_hash", key.address(), "dyt1bob", 1_000, 1_000, 0)
+            .with_gas(21_000, 1_000);
+        let tx_hash = sha3_256(&canonical_json(&tx.canonical_fields()).unwrap());
+        let sign = |i: usize| {
+            let (sk, pk) = &members[i];
+            let index = key.public_keys.iter().position(|k| *k == B64.encode(pk)).unwrap();
+            MultisigSignature::new(index as u32, &ActivePQC::sign(sk, &tx_hash))
+        };
+
+        let with_sigs = |signatures| {
+            tx.clone().with_multisig(MultisigAuth {
+                public_key: key.clone(),
+                signatures,
+            })
+        };
+        assert!(!verify_envelope(&with_sigs(vec![sign(1)])), "below threshold");
+        let signed = with_sigs(vec![sign(1), sign(2)]);
+        assert!(verify_envelope(&signed), "2-of-3 should verify");
+        assert_eq!(signed.additional_signatures(), 1);
+
+        // The sender must be the multisig address
+        let mut wrong_sender = signed.clone();
+        wrong_sender.from = "dyt1alice".to_string();
+        assert!(!verify_envelope(&wrong_sender));
+    }
 }
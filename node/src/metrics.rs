@@ -47,6 +47,11 @@ mod tests {
         assert_eq!(metrics.current_block_height.get(), 1);
         assert_eq!(metrics.total_gas_used.get(), 100);
 
+        // Test fee settlement
+        metrics.record_fee_settlement(400, 25);
+        assert_eq!(metrics.dyt_fee_refunded_total.get(), 400);
+        assert_eq!(metrics.dyt_fee_burned_total.get(), 25);
+
         // Test mempool update
         metrics.update_mempool_size(25);
         assert_eq!(metrics.mempool_size.get(), 25);
@@ -177,6 +182,10 @@ pub struct Metrics {
     pub total_gas_used: IntCounter,
     pub current_block_gas: IntGauge,
 
+    // Fee settlement metrics
+    pub dyt_fee_refunded_total: IntCounter,
+    pub dyt_fee_burned_total: IntCounter,
+
     // Oracle metrics - new dyt_ prefixed
     pub dyt_oracle_update_latency_seconds: Histogram,
     pub dyt_oracle_request_latency_seconds: Histogram,
@@ -393,6 +402,18 @@ impl Metrics {
         ))?;
         registry.register(Box::new(current_block_gas.clone()))?;
 
+        let dyt_fee_refunded_total = IntCounter::with_opts(Opts::new(
+            "dyt_fee_refunded_total",
+            "Total udgt refunded to fee payers for unused gas",
+        ))?;
+        registry.register(Box::new(dyt_fee_refunded_total.clone()))?;
+
+        let dyt_fee_burned_total = IntCounter::with_opts(Opts::new(
+            "dyt_fee_burned_total",
+            "Total udgt burned from charged transaction fees",
+        ))?;
+        registry.register(Box::new(dyt_fee_burned_total.clone()))?;
+
         // Oracle metrics - using dyt_ prefix
         let dyt_oracle_update_latency_seconds = Histogram::with_opts(HistogramOpts::new(
             "dyt_oracle_update_latency_seconds",
@@ -581,6 +602,8 @@ impl Metrics {
             mempool_gossip_duplicates_total,
             total_gas_used,
             current_block_gas,
+            dyt_fee_refunded_total,
+            dyt_fee_burned_total,
             // Oracle metrics
             oracle_submit_total,
             oracle_latency_seconds,
@@ -664,6 +687,14 @@ impl Metrics {
         self.mempool_gossip_duplicates_total.inc();
     }
 
+    /// Record the refunded and burned parts of a settled transaction fee
+    pub fn record_fee_settlement(&self, refunded: u128, burned: u128) {
+        self.dyt_fee_refunded_total
+            .inc_by(u64::try_from(refunded).unwrap_or(u64::MAX));
+        self.dyt_fee_burned_total
+            .inc_by(u64::try_from(burned).unwrap_or(u64::MAX));
+    }
+
     /// Record transaction processing time
     pub fn record_transaction(&self, processing_time: Duration) {
         self.transaction_processing_time
@@ -734,6 +765,7 @@ impl Metrics {
     pub fn record_mempool_eviction(&self, _reason: &str) {}
     pub fn update_mempool_min_gas_price(&self, _gas_price: u64) {}
     pub fn record_gossip_duplicate(&self) {}
+    pub fn record_fee_settlement(&self, _refunded: u128, _burned: u128) {}
     pub fn record_transaction(&self, _processing_time: Duration) {}
     pub fn record_oracle_update(&self, _latency: Duration) {}
     pub fn record_oracle_submission(&self, _status: &str) {}
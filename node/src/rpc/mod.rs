@@ -1,11 +1,22 @@
 use crate::rpc::errors::ApiError;
 use crate::runtime::bridge;
+use crate::runtime::bridge::BridgeParams;
+use crate::runtime::dead_man_switch::DmsParams;
+use crate::runtime::denoms::DenomRegistry;
 use crate::runtime::emission::EmissionEngine;
 use crate::runtime::fee_burn::FeeBurnEngine;
+use crate::runtime::gas_schedule::GasScheduleModule;
 use crate::runtime::governance::{GovernanceModule, ProposalType};
 #[cfg(feature = "oracle")]
 use crate::runtime::oracle::current_timestamp;
 use crate::runtime::staking::StakingModule;
+use crate::runtime::module_accounts::{
+    emission_account, module_address, module_balance, module_balances, send_from_module,
+};
+use crate::runtime::params::{current_values, ParamsModule};
+use crate::runtime::pause::PauseModule;
+use crate::runtime::supply::{self, mint_to, SupplyModule};
+use crate::runtime::upgrade::UpgradeModule;
 #[cfg(not(feature = "oracle"))]
 fn current_timestamp() -> u64 {
     std::time::SystemTime::now()
@@ -17,7 +28,7 @@ fn current_timestamp() -> u64 {
 use crate::storage::oracle::OracleStore;
 use crate::types::{Msg, SignedTx, ValidationError};
 use crate::{
-    mempool::{basic_validate, Mempool},
+    mempool::Mempool,
     state::State,
     storage::blocks::TpsWindow,
     storage::{receipts::TxReceipt, state::Storage, tx::Transaction},
@@ -27,8 +38,6 @@ use axum::{
     extract::{Path, Query},
     Extension, Json,
 };
-#[cfg(feature = "contracts")]
-use base64::Engine;
 use serde::Deserialize;
 use serde_json::json;
 use std::collections::HashMap;
@@ -137,29 +146,22 @@ fn validate_signed_tx(
     let mut required_per_denom: std::collections::HashMap<String, u128> =
         std::collections::HashMap::new();
 
-    // Add transaction fee (always in udgt for now)
-    let fee_denom = "udgt".to_string();
-    required_per_denom.insert(fee_denom.clone(), signed_tx.tx.fee);
+    // Add transaction fee (always in udgt for now); sponsored fees are checked
+    // against the fee payer by the mempool
+    if signed_tx.tx.fee_payer.is_none() {
+        required_per_denom.insert("udgt".to_string(), signed_tx.tx.fee);
+    }
 
     // Add amounts from messages
     for msg in &signed_tx.tx.msgs {
         match msg {
             Msg::Send { denom, amount, .. } => {
-                // Normalize denomination to lowercase micro-denom
-                let normalized_denom = denom.to_ascii_lowercase();
-                
-                // If uppercase macro-denom (DGT/DRT) is used, convert to micro-denom
-                // Otherwise assume it's already a micro-denom (udgt/udrt)
-                let (micro_denom, micro_amount) = match normalized_denom.as_str() {
-                    "dgt" => ("udgt", amount.saturating_mul(1_000_000)),
-                    "drt" => ("udrt", amount.saturating_mul(1_000_000)),
-                    "udgt" | "udrt" => (normalized_denom.as_str(), *amount),
-                    _ => (normalized_denom.as_str(), *amount), // Pass through unknown denoms as-is
-                };
-
-                let current = required_per_denom.get(micro_denom).copied().unwrap_or(0);
-                let new_total = current.saturating_add(micro_amount);
-                required_per_denom.insert(micro_denom.to_string(), new_total);
+                // Signed amounts are in base units; display units (DGT/DRT) are rejected
+                DenomRegistry::native()
+                    .check_signed(denom)
+                    .map_err(ValidationError::Internal)?;
+                let current = required_per_denom.get(denom).copied().unwrap_or(0);
+                required_per_denom.insert(denom.clone(), current.saturating_add(*amount));
             }
             Msg::Data { .. } => {
                 // Data messages don't require balance checks, only fee payment
@@ -168,6 +170,34 @@ fn validate_signed_tx(
             Msg::DmsRegister { .. } | Msg::DmsPing { .. } | Msg::DmsClaim { .. } => {
                 // DMS messages don't require balance checks, only fee
             }
+            Msg::Delegate { amount, .. } | Msg::Deposit { amount, .. } => {
+                // Stake and proposal deposits are paid in udgt
+                let current = required_per_denom.get("udgt").copied().unwrap_or(0);
+                required_per_denom.insert("udgt".to_string(), current.saturating_add(*amount));
+            }
+            Msg::ScheduleTransfer { denom, amount, .. } | Msg::Burn { denom, amount, .. } => {
+                // Scheduled amounts are escrowed and burned amounts destroyed when the tx executes
+                DenomRegistry::native()
+                    .check_signed(denom)
+                    .map_err(ValidationError::Internal)?;
+                let current = required_per_denom.get(denom).copied().unwrap_or(0);
+                required_per_denom.insert(denom.clone(), current.saturating_add(*amount));
+            }
+            Msg::Undelegate { .. }
+            | Msg::ClaimRewards { .. }
+            | Msg::SubmitProposal { .. }
+            | Msg::Vote { .. }
+            | Msg::WeightedVote { .. }
+            | Msg::ContractDeploy { .. }
+            | Msg::ContractCall { .. }
+            | Msg::GrantFeeAllowance { .. }
+            | Msg::RevokeFeeAllowance { .. }
+            | Msg::CancelSchedule { .. }
+            | Msg::CreateDenom { .. }
+            | Msg::Mint { .. }
+            | Msg::ChangeAdmin { .. } => {
+                // Only the fee is required
+            }
         }
     }
 
@@ -198,6 +228,255 @@ fn append_submit_log(entry: &serde_json::Value) {
     }
 }
 
+/// Convert a SignedTx into the legacy storage/execution `Transaction` (gas fields left unset)
+fn to_legacy_transaction(
+    signed_tx: &SignedTx,
+    tx_hash: &str,
+    from: &str,
+) -> Result<Transaction, ValidationError> {
+    // Build legacy Transaction wrapper for storage compatibility
+    // TODO: Remove this legacy conversion once storage is updated
+    let mut legacy_tx = Transaction::new(
+        tx_hash.to_string(),
+        from.to_string(),
+        from.to_string(),
+        0, // populate below based on msgs
+        signed_tx.tx.fee,
+        signed_tx.tx.nonce,
+        Some(signed_tx.signature.clone()),
+    )
+    .with_pqc(
+        signed_tx.public_key.clone(),
+        signed_tx.tx.chain_id.clone(),
+        signed_tx.tx.memo.clone(),
+    );
+
+    // Convert messages to storage format
+    use crate::storage::tx::TxMessage;
+    let mut tx_messages = Vec::new();
+    
+    // Signed denoms are stored as is and must be base units; display units (e.g. DGT) and
+    // unknown or ambiguous denoms are rejected.
+    let registry = DenomRegistry::native();
+    let base_unit = |denom: &String| {
+        registry
+            .check_signed(denom)
+            .map(|()| denom.clone())
+            .map_err(ValidationError::Internal)
+    };
+
+    // Sum send amounts so legacy accounting reserves the correct value
+    let mut total_amount: u128 = 0;
+    let mut first_to = legacy_tx.to.clone();
+    let mut first_denom = "udgt".to_string(); // Default for backward compatibility
+    for msg in &signed_tx.tx.msgs {
+        match msg {
+            Msg::Send { to, amount, denom, from: msg_from, .. } => {
+                let base_denom = base_unit(denom)?;
+                total_amount = total_amount.saturating_add(*amount);
+
+                // Store message in new format
+                tx_messages.push(TxMessage::Send {
+                    from: msg_from.clone(),
+                    to: to.clone(),
+                    denom: base_denom.clone(),
+                    amount: *amount,
+                });
+                
+                if first_to == from {
+                    first_to = to.clone();
+                    first_denom = base_denom;
+                }
+            }
+            Msg::Data { from: msg_from, data } => {
+                // Store data message
+                tx_messages.push(TxMessage::Data {
+                    from: msg_from.clone(),
+                    data: data.clone(),
+                });
+            }
+            Msg::DmsRegister { from, beneficiary, period } => {
+                tx_messages.push(TxMessage::DmsRegister {
+                    from: from.clone(),
+                    beneficiary: beneficiary.clone(),
+                    period: *period,
+                });
+            }
+            Msg::DmsPing { from } => {
+                tx_messages.push(TxMessage::DmsPing {
+                    from: from.clone(),
+                });
+            }
+            Msg::DmsClaim { from, owner } => {
+                tx_messages.push(TxMessage::DmsClaim {
+                    from: from.clone(),
+                    owner: owner.clone(),
+                });
+            }
+            Msg::Delegate { from, validator, amount } => {
+                tx_messages.push(TxMessage::Delegate {
+                    from: from.clone(),
+                    validator: validator.clone(),
+                    amount: *amount,
+                });
+            }
+            Msg::Undelegate { from, validator, amount } => {
+                tx_messages.push(TxMessage::Undelegate {
+                    from: from.clone(),
+                    validator: validator.clone(),
+                    amount: *amount,
+                });
+            }
+            Msg::ClaimRewards { from } => {
+                tx_messages.push(TxMessage::ClaimRewards { from: from.clone() });
+            }
+            Msg::SubmitProposal { from, title, description, proposal, track } => {
+                tx_messages.push(TxMessage::SubmitProposal {
+                    from: from.clone(),
+                    title: title.clone(),
+                    description: description.clone(),
+                    proposal: proposal.clone(),
+                    track: *track,
+                });
+            }
+            Msg::Deposit { from, proposal_id, amount } => {
+                tx_messages.push(TxMessage::Deposit {
+                    from: from.clone(),
+                    proposal_id: *proposal_id,
+                    amount: *amount,
+                });
+            }
+            Msg::Vote { from, proposal_id, option } => {
+                tx_messages.push(TxMessage::Vote {
+                    from: from.clone(),
+                    proposal_id: *proposal_id,
+                    option: option.clone(),
+                });
+            }
+            Msg::WeightedVote { from, proposal_id, options } => {
+                tx_messages.push(TxMessage::WeightedVote {
+                    from: from.clone(),
+                    proposal_id: *proposal_id,
+                    options: options.clone(),
+                });
+            }
+            Msg::ContractDeploy { from, code } => {
+                tx_messages.push(TxMessage::ContractDeploy {
+                    from: from.clone(),
+                    code: code.clone(),
+                });
+            }
+            Msg::ContractCall { from, contract, method, args } => {
+                tx_messages.push(TxMessage::ContractCall {
+                    from: from.clone(),
+                    contract: contract.clone(),
+                    method: method.clone(),
+                    args: args.clone(),
+                });
+            }
+            Msg::GrantFeeAllowance { from, grantee, spend_limit, expiration_height } => {
+                tx_messages.push(TxMessage::GrantFeeAllowance {
+                    from: from.clone(),
+                    grantee: grantee.clone(),
+                    spend_limit: *spend_limit,
+                    expiration_height: *expiration_height,
+                });
+            }
+            Msg::RevokeFeeAllowance { from, grantee } => {
+                tx_messages.push(TxMessage::RevokeFeeAllowance {
+                    from: from.clone(),
+                    grantee: grantee.clone(),
+                });
+            }
+            Msg::ScheduleTransfer { from, to, denom, amount, release_height, cancellable } => {
+                tx_messages.push(TxMessage::ScheduleTransfer {
+                    from: from.clone(),
+                    to: to.clone(),
+                    denom: base_unit(denom)?,
+                    amount: *amount,
+                    release_height: *release_height,
+                    cancellable: *cancellable,
+                });
+            }
+            Msg::CancelSchedule { from, id } => {
+                tx_messages.push(TxMessage::CancelSchedule {
+                    from: from.clone(),
+                    id: *id,
+                });
+            }
+            Msg::CreateDenom { from, subdenom, symbol, decimals } => {
+                tx_messages.push(TxMessage::CreateDenom {
+                    from: from.clone(),
+                    subdenom: subdenom.clone(),
+                    symbol: symbol.clone(),
+                    decimals: *decimals,
+                });
+            }
+            Msg::Mint { from, denom, amount, to } => {
+                tx_messages.push(TxMessage::Mint {
+                    from: from.clone(),
+                    denom: denom.clone(),
+                    amount: *amount,
+                    to: to.clone(),
+                });
+            }
+            Msg::Burn { from, denom, amount } => {
+                tx_messages.push(TxMessage::Burn {
+                    from: from.clone(),
+                    denom: denom.clone(),
+                    amount: *amount,
+                });
+            }
+            Msg::ChangeAdmin { from, denom, new_admin } => {
+                tx_messages.push(TxMessage::ChangeAdmin {
+                    from: from.clone(),
+                    denom: denom.clone(),
+                    new_admin: new_admin.clone(),
+                });
+            }
+        }
+    }
+    legacy_tx.amount = total_amount;
+    legacy_tx.to = first_to;
+    legacy_tx.denom = first_denom;
+    legacy_tx = legacy_tx.with_messages(tx_messages);
+    if let Some(multisig) = &signed_tx.multisig {
+        // Multisig envelopes carry no single key/signature
+        legacy_tx.signature = None;
+        legacy_tx.public_key = None;
+        legacy_tx = legacy_tx.with_multisig(multisig.clone());
+    }
+    legacy_tx.fee_payer = signed_tx.tx.fee_payer.clone();
+    legacy_tx.fee_payer_signature = signed_tx.fee_payer_signature.clone();
+    Ok(legacy_tx)
+}
+
+/// Default gas limit for transactions that do not sign one:
+/// ENV override -> governance parameter, raised to cover intrinsic gas
+fn default_gas_limit(ctx: &RpcContext, tx: &Transaction) -> u64 {
+    let fallback_gas_limit = std::env::var("DYTALLIX_DEFAULT_GAS_LIMIT")
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok())
+        .unwrap_or_else(|| {
+            // Read from governance config as the canonical default
+            let gov = ctx.governance.lock().unwrap();
+            gov.get_config().gas_limit
+        });
+    // The fallback must still cover intrinsic gas for the encoded size
+    // (PQC key + signature alone are ~10KB)
+    let schedule = GasScheduleModule::new(ctx.storage.clone())
+        .at_height(ctx.storage.height() + 1)
+        .schedule;
+    crate::gas::estimate_gas_limit(
+        &crate::gas::TxKind::Transfer,
+        tx.encoded_size(),
+        tx.additional_signatures(),
+        &schedule,
+        1.2,
+    )
+    .map_or(fallback_gas_limit, |estimate| fallback_gas_limit.max(estimate))
+}
+
 #[axum::debug_handler]
 pub async fn submit(
     ctx: Extension<RpcContext>,
@@ -308,102 +587,17 @@ pub async fn submit(
         }
     }
 
-    // Build legacy Transaction wrapper for storage compatibility
-    // TODO: Remove this legacy conversion once storage is updated
-    let mut legacy_tx = Transaction::new(
-        tx_hash.clone(),
-        from.to_string(),
-        from.to_string(),
-        0, // populate below based on msgs
-        signed_tx.tx.fee,
-        signed_tx.tx.nonce,
-        Some(signed_tx.signature.clone()),
-    )
-    .with_pqc(
-        signed_tx.public_key.clone(),
-        signed_tx.tx.chain_id.clone(),
-        signed_tx.tx.memo.clone(),
-    );
-
-    // Convert messages to storage format
-    use crate::storage::tx::TxMessage;
-    let mut tx_messages = Vec::new();
-    
-    // Sum send amounts so legacy accounting reserves the correct value
-    let mut total_amount: u128 = 0;
-    let mut first_to = legacy_tx.to.clone();
-    let mut first_denom = "udgt".to_string(); // Default for backward compatibility
-    for msg in &signed_tx.tx.msgs {
-        match msg {
-            Msg::Send { to, amount, denom, from: msg_from, .. } => {
-                total_amount = total_amount.saturating_add(*amount);
-                
-                // Convert DGT/DRT to micro denominations for storage
-                let micro_denom = match denom.to_ascii_uppercase().as_str() {
-                    "DGT" => "udgt".to_string(),
-                    "DRT" => "udrt".to_string(),
-                    _ => denom.clone(), // Pass through other denoms
-                };
-                
-                // Store message in new format
-                tx_messages.push(TxMessage::Send {
-                    from: msg_from.clone(),
-                    to: to.clone(),
-                    denom: micro_denom.clone(),
-                    amount: *amount,
-                });
-                
-                if first_to == from {
-                    first_to = to.clone();
-                    first_denom = micro_denom;
-                }
-            }
-            Msg::Data { from: msg_from, data } => {
-                // Store data message
-                tx_messages.push(TxMessage::Data {
-                    from: msg_from.clone(),
-                    data: data.clone(),
-                });
-            }
-            Msg::DmsRegister { from, beneficiary, period } => {
-                tx_messages.push(TxMessage::DmsRegister {
-                    from: from.clone(),
-                    beneficiary: beneficiary.clone(),
-                    period: *period,
-                });
-            }
-            Msg::DmsPing { from } => {
-                tx_messages.push(TxMessage::DmsPing {
-                    from: from.clone(),
-                });
-            }
-            Msg::DmsClaim { from, owner } => {
-                tx_messages.push(TxMessage::DmsClaim {
-                    from: from.clone(),
-                    owner: owner.clone(),
-                });
-            }
-        }
-    }
-    legacy_tx.amount = total_amount;
-    legacy_tx.to = first_to;
-    legacy_tx.denom = first_denom;
-    legacy_tx = legacy_tx.with_messages(tx_messages);
+    let mut legacy_tx =
+        to_legacy_transaction(&signed_tx, &tx_hash, from).map_err(ApiError::Validation)?;
 
     // Set gas parameters before mempool validation
     let min_gas_price = ctx.mempool.lock().unwrap().config().min_gas_price;
     legacy_tx.gas_price = min_gas_price;
+    if let Some(gas_limit) = signed_tx.tx.gas_limit {
+        legacy_tx.gas_limit = gas_limit;
+    }
     if legacy_tx.gas_limit == 0 {
-        // Default legacy gas limit: ENV override -> governance parameter
-        let fallback_gas_limit = std::env::var("DYTALLIX_DEFAULT_GAS_LIMIT")
-            .ok()
-            .and_then(|s| s.parse::<u64>().ok())
-            .unwrap_or_else(|| {
-                // Read from governance config as the canonical default
-                let gov = ctx.governance.lock().unwrap();
-                gov.get_config().gas_limit
-            });
-        legacy_tx.gas_limit = fallback_gas_limit;
+        legacy_tx.gas_limit = default_gas_limit(&ctx, &legacy_tx);
     }
 
     // Add to mempool (mempool will perform full validation including gas cost)
@@ -463,6 +657,14 @@ pub async fn submit(
                     ApiError::BadRequest(format!("policy violation: {msg}")),
                     "POLICY_VIOLATION",
                 ),
+                crate::mempool::RejectionReason::FeeAllowanceDenied(msg) => (
+                    ApiError::BadRequest(format!("fee allowance denied: {msg}")),
+                    "FEE_ALLOWANCE_DENIED",
+                ),
+                crate::mempool::RejectionReason::UnknownDenom(denom) => (
+                    ApiError::BadRequest(format!("unknown denom: {denom}")),
+                    "UNKNOWN_DENOM",
+                ),
                 crate::mempool::RejectionReason::InternalError(_) => {
                     (ApiError::Internal, "INTERNAL_ERROR")
                 }
@@ -516,6 +718,177 @@ pub async fn submit(
     })))
 }
 
+#[derive(Deserialize)]
+pub struct SimulateTx {
+    pub tx: crate::types::tx::Tx,
+    // Signature is optional; when absent the envelope is sized with placeholders
+    #[serde(default)]
+    pub public_key: Option<String>,
+    #[serde(default)]
+    pub signature: Option<String>,
+    #[serde(default)]
+    pub algorithm: Option<String>,
+    /// Multisig envelope; replaces `public_key`/`signature` when present
+    #[serde(default)]
+    pub multisig: Option<crate::types::MultisigAuth>,
+    #[serde(default)]
+    pub fee_payer_signature: Option<crate::types::tx::FeePayerSignature>,
+}
+
+/// Base64 lengths of the active PQC public key and signature, used to size unsigned simulations
+static PQC_ENVELOPE_LEN: once_cell::sync::Lazy<(usize, usize)> = once_cell::sync::Lazy::new(|| {
+    use crate::crypto::{ActivePQC, PQC};
+    use base64::Engine;
+    let (sk, pk) = ActivePQC::keypair();
+    let sig = ActivePQC::sign(&sk, &[0u8; 32]);
+    (
+        base64::engine::general_purpose::STANDARD.encode(&pk).len(),
+        base64::engine::general_purpose::STANDARD.encode(&sig).len(),
+    )
+});
+
+/// POST /tx/simulate - dry-run a transaction against a fork of the current state.
+/// Nothing is persisted; returns gas used with per-operation breakdown, events and balance diff.
+pub async fn tx_simulate(
+    Extension(ctx): Extension<RpcContext>,
+    Json(body): Json<SimulateTx>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let chain_id = ctx.storage.get_chain_id().unwrap_or_default();
+    body.tx
+        .validate(&chain_id)
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    let from = body
+        .tx
+        .msgs
+        .first()
+        .map(|m| m.sender().to_string())
+        .ok_or_else(|| ApiError::BadRequest("no sender address found".to_string()))?;
+
+    let (pk_len, sig_len) = *PQC_ENVELOPE_LEN;
+    let mut signed_tx = match &body.multisig {
+        Some(multisig) => SignedTx::from_multisig(
+            body.tx.clone(),
+            multisig.public_key.clone(),
+            multisig.signatures.clone(),
+        ),
+        None => SignedTx {
+            tx: body.tx.clone(),
+            public_key: body.public_key.clone().unwrap_or_else(|| "A".repeat(pk_len)),
+            signature: body.signature.clone().unwrap_or_else(|| "A".repeat(sig_len)),
+            algorithm: body.algorithm.clone().unwrap_or_else(|| {
+                <crate::crypto::ActivePQC as crate::crypto::PQC>::ALG.to_string()
+            }),
+            version: 1,
+            multisig: None,
+            fee_payer_signature: None,
+        },
+    };
+    signed_tx.fee_payer_signature = body.fee_payer_signature.clone();
+    let signature_verified = body.signature.is_some() || body.multisig.is_some();
+    if signature_verified && signed_tx.verify().is_err() {
+        return Err(ApiError::InvalidSignature);
+    }
+
+    let tx_hash = body
+        .tx
+        .tx_hash()
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    let mut legacy_tx =
+        to_legacy_transaction(&signed_tx, &tx_hash, &from).map_err(ApiError::Validation)?;
+    legacy_tx.gas_price = ctx.mempool.lock().unwrap().config().min_gas_price;
+    // Forks of the modules, taken before the state lock (lock order: governance -> state),
+    // so the dry run keeps no governance events or contract state
+    let mut governance = ctx.governance.lock().unwrap().fork();
+    let max_gas_per_block = governance.get_config().max_gas_per_block;
+    let mut staking = StakingModule::new(ctx.storage.clone());
+    #[cfg(feature = "contracts")]
+    let wasm = ctx.wasm_runtime.fork();
+
+    let state = ctx.state.lock().unwrap();
+    legacy_tx.gas_limit = match body.tx.gas_limit {
+        Some(gas_limit) => gas_limit,
+        None => {
+            // Run with the most gas the fee payer could pay for, capped at the block gas limit
+            let spendable = match &legacy_tx.fee_payer {
+                Some(payer) => state.snapshot_account(payer).balance_of("udgt"),
+                None => {
+                    let sends: u128 = legacy_tx
+                        .messages
+                        .iter()
+                        .flatten()
+                        .filter_map(|m| match m {
+                            crate::storage::tx::TxMessage::Send { denom, amount, .. }
+                                if denom.eq_ignore_ascii_case("udgt") =>
+                            {
+                                Some(*amount)
+                            }
+                            _ => None,
+                        })
+                        .sum();
+                    state
+                        .snapshot_account(&from)
+                        .balance_of("udgt")
+                        .saturating_sub(sends)
+                }
+            };
+            let affordable = spendable / legacy_tx.gas_price.max(1) as u128;
+            affordable.min(max_gas_per_block as u128) as u64
+        }
+    };
+
+    let height = ctx.storage.height() + 1;
+    let gas = GasScheduleModule::new(ctx.storage.clone()).at_height(height);
+    let modules = crate::execution::ExecutionModules {
+        staking: ctx.features.staking.then_some(&mut staking),
+        governance: ctx.features.governance.then_some(&mut governance),
+        #[cfg(feature = "contracts")]
+        wasm: Some(&wasm),
+    };
+    let sim = crate::execution::simulate_transaction(
+        &legacy_tx,
+        &state,
+        height,
+        &gas.schedule,
+        modules,
+    );
+    drop(state);
+
+    let gas_breakdown: std::collections::BTreeMap<_, _> =
+        sim.result.gas_operations.iter().collect();
+    let balance_diff: Vec<serde_json::Value> = sim
+        .balance_diff
+        .iter()
+        .map(|d| {
+            let delta = if d.after >= d.before {
+                (d.after - d.before).to_string()
+            } else {
+                format!("-{}", d.before - d.after)
+            };
+            json!({
+                "address": d.address,
+                "denom": d.denom,
+                "before": d.before.to_string(),
+                "after": d.after.to_string(),
+                "delta": delta,
+            })
+        })
+        .collect();
+
+    Ok(Json(json!({
+        "success": sim.result.success,
+        "error": sim.result.receipt.error,
+        "tx_hash": tx_hash,
+        "signature_verified": signature_verified,
+        "gas_used": sim.result.gas_used,
+        "gas_limit": legacy_tx.gas_limit,
+        "gas_price": legacy_tx.gas_price,
+        "gas_breakdown": gas_breakdown,
+        "gas_schedule_version": gas.version,
+        "events": sim.result.events,
+        "balance_diff": balance_diff,
+    })))
+}
+
 // Remove legacy helper function
 // impl SignedTx { fn msgs_first_from(&self) { self.tx.msgs.get(0).and_then(|v| v.get("from")).and_then(|f| f.as_str()).map(|s| s.to_string()) } }
 
@@ -712,19 +1085,20 @@ pub async fn get_balance(
     let legacy_balance = state.legacy_balance_of(&addr);
 
     // Format balances for multi-denomination response
+    let registry = DenomRegistry::native();
     let formatted_balances: std::collections::HashMap<String, serde_json::Value> = balances
         .iter()
         .map(|(denom, amount)| {
             let denom_info = match denom.as_str() {
                 "udgt" => json!({
                     "balance": amount.to_string(),
-                    "formatted": format!("{} DGT", amount / 1_000_000), // Assuming 6 decimal places
+                    "formatted": registry.format("udgt", *amount),
                     "type": "governance",
                     "description": "Governance token for voting and staking"
                 }),
                 "udrt" => json!({
                     "balance": amount.to_string(),
-                    "formatted": format!("{} DRT", amount / 1_000_000), // Assuming 6 decimal places
+                    "formatted": registry.format("udrt", *amount),
                     "type": "reward",
                     "description": "Reward token for transaction fees and staking rewards"
                 }),
@@ -974,7 +1348,7 @@ pub async fn gov_submit_proposal(
             "governance feature disabled".into(),
         ));
     }
-    use crate::runtime::governance::ProposalType;
+    use crate::runtime::governance::{ProposalTrack, ProposalType};
 
     let title = body
         .get("title")
@@ -984,27 +1358,41 @@ pub async fn gov_submit_proposal(
         .get("description")
         .and_then(|v| v.as_str())
         .ok_or(ApiError::Internal)?;
-    let key = body
-        .get("key")
-        .and_then(|v| v.as_str())
-        .ok_or(ApiError::Internal)?;
-    let value = body
-        .get("value")
-        .and_then(|v| v.as_str())
-        .ok_or(ApiError::Internal)?;
-    let height = ctx.storage.height();
-    let proposal_type = ProposalType::ParameterChange {
-        key: key.to_string(),
-        value: value.to_string(),
+    // `proposal` takes any ProposalType as in the submit_proposal tx; bare key/value is
+    // the legacy form of a ParameterChange
+    let proposal_type = match body.get("proposal") {
+        Some(proposal) => serde_json::from_value::<ProposalType>(proposal.clone())
+            .map_err(|e| ApiError::BadRequest(format!("invalid proposal: {e}")))?,
+        None => {
+            let key = body
+                .get("key")
+                .and_then(|v| v.as_str())
+                .ok_or(ApiError::Internal)?;
+            let value = body
+                .get("value")
+                .and_then(|v| v.as_str())
+                .ok_or(ApiError::Internal)?;
+            ProposalType::ParameterChange {
+                key: key.to_string(),
+                value: value.to_string(),
+            }
+        }
+    };
+    let track = match body.get("track") {
+        Some(track) => serde_json::from_value::<ProposalTrack>(track.clone())
+            .map_err(|e| ApiError::BadRequest(format!("invalid track: {e}")))?,
+        None => ProposalTrack::Standard,
     };
-    match ctx.governance.lock().unwrap().submit_proposal(
+    let height = ctx.storage.height();
+    match ctx.governance.lock().unwrap().submit_proposal_on_track(
         height,
         title.to_string(),
         description.to_string(),
         proposal_type,
+        track,
     ) {
         Ok(proposal_id) => Ok(Json(json!({"proposal_id": proposal_id}))),
-        Err(_) => Err(ApiError::Internal),
+        Err(e) => Err(ApiError::BadRequest(e)),
     }
 }
 
@@ -1064,11 +1452,27 @@ pub async fn gov_vote(
         .get("proposal_id")
         .and_then(|v| v.as_u64())
         .ok_or(ApiError::Internal)?;
+    let height = ctx.storage.height();
+
+    // Split vote: "options": [{ "option": "Yes", "weight": "0.7" }, ...]
+    if let Some(options) = body.get("options") {
+        let options = serde_json::from_value(options.clone())
+            .map_err(|e| ApiError::BadRequest(format!("invalid options: {e}")))?;
+        return match ctx
+            .governance
+            .lock()
+            .unwrap()
+            .weighted_vote(height, voter, proposal_id, options)
+        {
+            Ok(()) => Ok(Json(json!({"success": true}))),
+            Err(e) => Err(ApiError::BadRequest(e)),
+        };
+    }
+
     let option_str = body
         .get("option")
         .and_then(|v| v.as_str())
         .ok_or(ApiError::Internal)?;
-    let height = ctx.storage.height();
 
     let option = match option_str {
         "yes" => VoteOption::Yes,
@@ -1110,8 +1514,12 @@ pub async fn gov_tally(
     Extension(ctx): Extension<RpcContext>,
     Path(proposal_id): Path<u64>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
-    match ctx.governance.lock().unwrap().tally(proposal_id) {
-        Ok(tally) => Ok(Json(serde_json::to_value(tally).unwrap())),
+    match ctx.governance.lock().unwrap().tally_with_breakdown(proposal_id) {
+        Ok((tally, validators)) => {
+            let mut body = serde_json::to_value(tally).unwrap();
+            body["validators"] = serde_json::to_value(validators).unwrap();
+            Ok(Json(body))
+        }
         Err(e) => {
             eprintln!("Governance tally error: {e}");
             Err(ApiError::Internal)
@@ -1147,11 +1555,37 @@ pub async fn gov_execute(
 pub async fn gov_get_config(
     Extension(ctx): Extension<RpcContext>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
-    let config = {
-        let governance = ctx.governance.lock().unwrap();
-        governance.get_config().clone()
-    };
-    Ok(Json(serde_json::to_value(config).unwrap()))
+    let governed = ParamsModule::new(ctx.storage.clone()).all();
+    // Locks are taken one at a time
+    let config = ctx.governance.lock().unwrap().get_config().clone();
+    let mut params = current_values(&config, &governed);
+    params.extend(current_values(&*ctx.staking.lock().unwrap(), &governed));
+    params.extend(current_values(
+        ctx.mempool.lock().unwrap().config(),
+        &governed,
+    ));
+    let gas = GasScheduleModule::new(ctx.storage.clone()).at_height(ctx.storage.height() + 1);
+    params.extend(current_values(&gas.schedule, &governed));
+    params.extend(current_values(
+        &ctx.fee_burn.lock().unwrap().config,
+        &governed,
+    ));
+    params.extend(current_values(
+        &ctx.emission.lock().unwrap().config.emission_breakdown,
+        &governed,
+    ));
+    params.extend(current_values(&BridgeParams::default(), &governed));
+    #[cfg(feature = "oracle")]
+    params.extend(current_values(
+        &crate::runtime::oracle::OracleParams::default(),
+        &governed,
+    ));
+    params.extend(current_values(&DmsParams::default(), &governed));
+
+    let mut body = serde_json::to_value(config).unwrap();
+    body["params"] = serde_json::to_value(params).unwrap();
+    body["paused_modules"] = json!(PauseModule::new(ctx.storage.clone()).paused());
+    Ok(Json(body))
 }
 
 /// GET /api/governance/proposals - List all governance proposals
@@ -1167,7 +1601,10 @@ pub async fn gov_list_proposals(
             for proposal in proposals {
                 // Get current tally for each proposal
                 let current_tally = governance.tally(proposal.id).ok();
-                let total_voting_power = governance.total_voting_power().unwrap_or(1);
+                // Quorum counts against the snapshot once voting has started
+                let total_voting_power = governance
+                    .snapshot_total_power(proposal.id)
+                    .unwrap_or_else(|| governance.total_voting_power().unwrap_or(1));
 
                 let participating_voting_power = current_tally
                     .as_ref()
@@ -1185,8 +1622,14 @@ pub async fn gov_list_proposals(
                 let proposal_summary = json!({
                     "id": proposal.id,
                     "type": match &proposal.proposal_type {
-                        ProposalType::ParameterChange { key, .. } => format!("ParameterChange({key})")
+                        ProposalType::ParameterChange { key, .. } => format!("ParameterChange({key})"),
+                        ProposalType::Text => "Text".to_string(),
+                        ProposalType::CommunitySpend { amount, denom, .. } => format!("CommunitySpend({amount}{denom})"),
+                        ProposalType::SoftwareUpgrade { name, height, .. } => format!("SoftwareUpgrade({name}@{height})"),
+                        ProposalType::HaltBridge { halted } => format!("HaltBridge({halted})"),
+                        ProposalType::PauseModule { module, paused } => format!("PauseModule({module}={paused})"),
                     },
+                    "track": proposal.track,
                     "title": proposal.title,
                     "status": proposal.status,
                     "submit_time": proposal.submit_height, // In a real impl, convert to timestamp
@@ -1232,6 +1675,7 @@ pub async fn gov_get_proposal_votes(
                     json!({
                         "voter": vote.voter,
                         "option": vote.option,
+                        "options": vote.options,
                         "voting_power": vote.weight.to_string(),
                         "timestamp": null // TODO: add timestamp to Vote struct
                     })
@@ -1397,6 +1841,193 @@ pub async fn get_rewards_by_height(
     }
 }
 
+/// GET /api/feegrants/:granter - Fee allowances issued by an account
+pub async fn get_fee_grants(
+    Extension(ctx): Extension<RpcContext>,
+    Path(granter): Path<String>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let grants = crate::runtime::fee_grant::FeeGrantModule::new(ctx.storage.clone());
+    let allowances: Vec<_> = grants
+        .allowances_by_granter(&granter)
+        .into_iter()
+        .map(|a| {
+            json!({
+                "granter": a.granter,
+                "grantee": a.grantee,
+                "spend_limit": a.spend_limit.to_string(),
+                "expiration_height": a.expiration_height,
+            })
+        })
+        .collect();
+    Ok(Json(json!({ "granter": granter, "allowances": allowances })))
+}
+
+/// GET /api/schedules/:addr - Pending scheduled transfers sent or received by an account
+pub async fn get_schedules(
+    Extension(ctx): Extension<RpcContext>,
+    Path(addr): Path<String>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let schedules = crate::runtime::schedule::ScheduleModule::new(ctx.storage.clone());
+    let items: Vec<_> = schedules
+        .schedules_for(&addr)
+        .into_iter()
+        .map(|s| {
+            json!({
+                "id": s.id,
+                "from": s.from,
+                "to": s.to,
+                "denom": s.denom,
+                "amount": s.amount.to_string(),
+                "release_height": s.release_height,
+                "cancellable": s.cancellable,
+                "created_height": s.created_height,
+            })
+        })
+        .collect();
+    Ok(Json(json!({ "address": addr, "schedules": items })))
+}
+
+/// GET /api/denoms - Native and token factory denominations with their base/display units
+pub async fn get_denoms(
+    Extension(ctx): Extension<RpcContext>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let factory = crate::runtime::token_factory::TokenFactoryModule::new(ctx.storage.clone());
+    let factory_denoms = factory.all();
+    let registry = DenomRegistry::with_factory(factory_denoms.clone());
+    let items: Vec<_> = registry
+        .denoms()
+        .iter()
+        .map(|meta| {
+            let mut item = json!(meta);
+            if let Some(d) = factory_denoms.iter().find(|d| d.denom == meta.base) {
+                item["creator"] = json!(d.creator);
+                item["admin"] = json!(d.admin);
+                item["supply"] = json!(d.supply.to_string());
+            }
+            item
+        })
+        .collect();
+    Ok(Json(json!({ "denoms": items })))
+}
+
+/// GET /api/supply - total supply per denom, module account balances and the supply invariant
+pub async fn get_supply(
+    Extension(ctx): Extension<RpcContext>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let totals: Vec<_> = SupplyModule::new(ctx.storage.clone())
+        .all_supply()
+        .into_iter()
+        .map(|(denom, amount)| json!({ "denom": denom, "amount": amount.to_string() }))
+        .collect();
+    let module_accounts: Vec<_> = module_balances(&ctx.storage)
+        .into_iter()
+        .map(|(name, balances)| {
+            let balances: HashMap<_, _> =
+                balances.into_iter().map(|(d, a)| (d, a.to_string())).collect();
+            json!({ "name": name, "address": module_address(&name), "balances": balances })
+        })
+        .collect();
+    let violations: Vec<_> = supply::check_invariants(&ctx.storage)
+        .into_iter()
+        .filter(|c| !c.holds())
+        .map(|c| {
+            json!({
+                "denom": c.denom,
+                "supply": c.supply.to_string(),
+                "balances": c.balances.to_string(),
+            })
+        })
+        .collect();
+    Ok(Json(json!({
+        "height": ctx.storage.height(),
+        "supply": totals,
+        "module_accounts": module_accounts,
+        "invariant": { "ok": violations.is_empty(), "violations": violations },
+    })))
+}
+
+/// GET /gas/schedule?version= - the gas schedule pricing the next block, or one version,
+/// plus every version with its activation height
+pub async fn get_gas_schedule(
+    Extension(ctx): Extension<RpcContext>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let gas = GasScheduleModule::new(ctx.storage.clone());
+    let height = ctx.storage.height();
+    let schedule = match params.get("version") {
+        Some(raw) => {
+            let version = raw
+                .parse::<u32>()
+                .map_err(|_| ApiError::BadRequest("invalid version".into()))?;
+            gas.version(version).ok_or(ApiError::NotFound)?
+        }
+        None => gas.at_height(height + 1),
+    };
+    let versions: Vec<_> = gas
+        .versions()
+        .into_iter()
+        .map(|v| json!({ "version": v.version, "activation_height": v.activation_height }))
+        .collect();
+    Ok(Json(json!({
+        "height": height,
+        "schedule": schedule,
+        "versions": versions,
+    })))
+}
+
+/// GET /api/upgrade - pending upgrade plan, whether production is halted for it, and the
+/// upgrades applied so far
+pub async fn get_upgrade(
+    Extension(ctx): Extension<RpcContext>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let upgrades = UpgradeModule::new(ctx.storage.clone());
+    let height = ctx.storage.height();
+    let applied: Vec<_> = upgrades
+        .applied()
+        .into_iter()
+        .map(|(name, height)| json!({ "name": name, "height": height }))
+        .collect();
+    Ok(Json(json!({
+        "height": height,
+        "plan": upgrades.plan(),
+        "halted": upgrades.halt_for(height + 1).is_some(),
+        "applied": applied,
+    })))
+}
+
+/// GET /events?type=&attr.<key>=<value>&from_height=&to_height=&limit=
+/// Query indexed receipt events; every `attr.*` condition must match.
+pub async fn query_events(
+    Extension(ctx): Extension<RpcContext>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    use crate::storage::events::EventFilter;
+
+    let parse_u64 = |name: &str| -> Result<Option<u64>, ApiError> {
+        params
+            .get(name)
+            .map(|v| {
+                v.parse::<u64>()
+                    .map_err(|_| ApiError::BadRequest(format!("invalid {name}: {v}")))
+            })
+            .transpose()
+    };
+    let mut attributes: Vec<(String, String)> = params
+        .iter()
+        .filter_map(|(k, v)| k.strip_prefix("attr.").map(|k| (k.to_string(), v.clone())))
+        .collect();
+    attributes.sort();
+    let filter = EventFilter {
+        event_type: params.get("type").filter(|t| !t.is_empty()).cloned(),
+        attributes,
+        from_height: parse_u64("from_height")?,
+        to_height: parse_u64("to_height")?,
+        limit: parse_u64("limit")?.unwrap_or(0) as usize,
+    };
+    let events = ctx.storage.query_events(&filter);
+    Ok(Json(json!({ "count": events.len(), "events": events })))
+}
+
 /// POST /dev/faucet - Development-only faucet to credit balances directly
 /// Body: { "address": string, "udgt": u64 (optional), "udrt": u64 (optional) }
 pub async fn dev_faucet(
@@ -1417,8 +2048,8 @@ pub async fn dev_faucet(
         .unwrap_or(10_000_000_000); // Default 10,000 DRT
     {
         let mut st = ctx.state.lock().unwrap();
-        st.credit(addr, "udgt", udgt as u128);
-        st.credit(addr, "udrt", udrt as u128);
+        mint_to(&mut st, addr, "udgt", udgt as u128).map_err(|_| ApiError::Internal)?;
+        mint_to(&mut st, addr, "udrt", udrt as u128).map_err(|_| ApiError::Internal)?;
     }
     Ok(Json(serde_json::json!({
         "success": true,
@@ -1479,10 +2110,19 @@ pub async fn staking_claim(
         .and_then(|v| v.as_str())
         .ok_or(ApiError::BadRequest("Missing address field".to_string()))?;
     let mut staking = ctx.staking.lock().unwrap();
+    let pool = emission_account("staking_rewards");
+    {
+        // Rewards are paid out of the staking emission pool
+        let mut state = ctx.state.lock().unwrap();
+        if module_balance(&mut state, &pool, "udrt") < staking.get_accrued_rewards(address) {
+            return Err(ApiError::Internal);
+        }
+    }
     let claimed = staking.claim_rewards(address);
     if claimed > 0 {
         if let Ok(mut state) = ctx.state.lock() {
-            state.credit(address, "udrt", claimed);
+            send_from_module(&mut state, &pool, address, "udrt", claimed)
+                .map_err(|_| ApiError::Internal)?;
         }
     }
     let reward_index = staking.get_stats().1;
@@ -1754,7 +2394,7 @@ pub async fn params_staking_reward_rate(
 
 /// JSON-RPC 2.0 endpoint stub (minimal WASM contract support)
 pub async fn json_rpc(
-    Extension(ctx): Extension<RpcContext>,
+    Extension(_ctx): Extension<RpcContext>,
     Json(body): Json<serde_json::Value>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
     let method = body
@@ -1865,7 +2505,7 @@ pub async fn asset_get(
 /// POST /faucet - Testnet faucet to credit tokens to an address
 /// This is an administrative endpoint that directly credits tokens without requiring a transaction.
 /// Request body: { "address": "dytallix1...", "dgt_amount": 10000000, "drt_amount": 100000000 }
-/// Amounts are in micro-units (1 DGT = 1_000_000 udgt)
+/// Amounts are whole DGT/DRT, converted to base units through the `DenomRegistry`
 pub async fn faucet(
     Extension(ctx): Extension<RpcContext>,
     Json(body): Json<serde_json::Value>,
@@ -1874,34 +2514,33 @@ pub async fn faucet(
     let address = body
         .get("address")
         .and_then(|v| v.as_str())
-        .ok_or(ApiError::InvalidParam("address is required".to_string()))?;
+        .ok_or(ApiError::BadRequest("address is required".to_string()))?;
     
     // Validate address format
     if !address.starts_with("dyt") && !address.starts_with("dytallix") {
-        return Err(ApiError::InvalidParam("Invalid address format".to_string()));
+        return Err(ApiError::BadRequest("Invalid address format".to_string()));
     }
     
-    // Get amounts (default: 10 DGT, 100 DRT)
-    let dgt_amount = body
-        .get("dgt_amount")
-        .and_then(|v| v.as_u64())
-        .map(|v| v as u128 * 1_000_000) // Convert whole units to micro-units
-        .unwrap_or(10_000_000); // Default 10 DGT
-    
-    let drt_amount = body
-        .get("drt_amount")
-        .and_then(|v| v.as_u64())
-        .map(|v| v as u128 * 1_000_000) // Convert whole units to micro-units
-        .unwrap_or(100_000_000); // Default 100 DRT
+    // Get amounts in whole display units (default: 10 DGT, 100 DRT)
+    let registry = DenomRegistry::native();
+    let to_base = |field: &str, display: &str, default: u64| {
+        let whole = body.get(field).and_then(|v| v.as_u64()).unwrap_or(default);
+        registry
+            .canonicalize(display, whole as u128)
+            .map(|(_, amount)| amount)
+            .map_err(ApiError::BadRequest)
+    };
+    let dgt_amount = to_base("dgt_amount", "DGT", 10)?;
+    let drt_amount = to_base("drt_amount", "DRT", 100)?;
     
     // Credit the tokens
     {
         let mut state = ctx.state.lock().unwrap();
         if dgt_amount > 0 {
-            state.credit(address, "udgt", dgt_amount);
+            mint_to(&mut state, address, "udgt", dgt_amount).map_err(|_| ApiError::Internal)?;
         }
         if drt_amount > 0 {
-            state.credit(address, "udrt", drt_amount);
+            mint_to(&mut state, address, "udrt", drt_amount).map_err(|_| ApiError::Internal)?;
         }
     }
     
@@ -1919,12 +2558,12 @@ pub async fn faucet(
             "dgt": {
                 "amount": dgt_amount,
                 "denom": "udgt",
-                "formatted": format!("{} DGT", dgt_amount / 1_000_000)
+                "formatted": registry.format("udgt", dgt_amount)
             },
             "drt": {
                 "amount": drt_amount,
-                "denom": "udrt", 
-                "formatted": format!("{} DRT", drt_amount / 1_000_000)
+                "denom": "udrt",
+                "formatted": registry.format("udrt", drt_amount)
             }
         },
         "timestamp": current_timestamp()
@@ -109,6 +109,8 @@ pub async fn sign_and_submit_transaction(
         }],
         fee: 1000, // Standard fee
         memo: request.transaction.memo.unwrap_or_default(),
+        gas_limit: None,
+        fee_payer: None,
     };
 
     // 6. Sign the transaction
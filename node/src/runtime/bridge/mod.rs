@@ -1,9 +1,39 @@
 use crate::rpc::{errors::ApiError, RpcContext};
 use crate::storage::bridge::{verify_bridge_message, BridgeMessage, BridgeStore, BridgeValidator};
+use crate::runtime::params::{load_params, parse_ratio, GovernedParams};
 use axum::{Extension, Json};
 use serde::Deserialize;
 use serde_json::json;
 
+/// Governed bridge parameters
+#[derive(Debug, Clone)]
+pub struct BridgeParams {
+    /// Share of bridge validators that must sign a message, as (numerator, denominator)
+    pub quorum: (u64, u64),
+}
+
+impl Default for BridgeParams {
+    fn default() -> Self {
+        Self { quorum: (2, 3) }
+    }
+}
+
+impl GovernedParams for BridgeParams {
+    const MODULE: &'static str = "bridge";
+
+    fn get_param(&self, key: &str) -> Option<String> {
+        (key == "bridge.quorum").then(|| format!("{}/{}", self.quorum.0, self.quorum.1))
+    }
+
+    fn set_param(&mut self, key: &str, value: &str) -> Result<(), String> {
+        if key != "bridge.quorum" {
+            return Err(format!("Unknown parameter: {key}"));
+        }
+        self.quorum = parse_ratio(value)?;
+        Ok(())
+    }
+}
+
 #[derive(Deserialize)]
 pub struct IngestBridgeMessage {
     pub id: String,
@@ -42,7 +72,8 @@ pub async fn ingest(
         signers: body.signers,
     };
     let validators = store.get_validators();
-    match verify_bridge_message(&msg, &validators) {
+    let params: BridgeParams = load_params(ctx.storage.clone());
+    match verify_bridge_message(&msg, &validators, params.quorum) {
         Ok(_) => {
             store.put_pending(&msg).map_err(|_| ApiError::Internal)?;
             store
@@ -1,3 +1,5 @@
+use crate::runtime::params::{load_params, parse, GovernedParams};
+use crate::state::KvStore;
 use crate::storage::state::Storage;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -13,46 +15,82 @@ pub struct DeadManSwitchConfig {
     pub last_active_block: u64,
 }
 
-/// Dead Man Switch module
-pub struct DeadManSwitchModule {
-    storage: Arc<Storage>,
+/// Governed dead man switch parameters
+#[derive(Debug, Clone)]
+pub struct DmsParams {
+    pub min_period_blocks: u64,
+}
+
+impl Default for DmsParams {
+    fn default() -> Self {
+        Self {
+            min_period_blocks: 1,
+        }
+    }
+}
+
+impl GovernedParams for DmsParams {
+    const MODULE: &'static str = "dms";
+
+    fn get_param(&self, key: &str) -> Option<String> {
+        (key == "dms.min_period_blocks").then(|| self.min_period_blocks.to_string())
+    }
+
+    fn set_param(&mut self, key: &str, value: &str) -> Result<(), String> {
+        if key != "dms.min_period_blocks" {
+            return Err(format!("Unknown parameter: {key}"));
+        }
+        self.min_period_blocks = parse(key, value)?;
+        Ok(())
+    }
+}
+
+/// Dead Man Switch module over a KV store (storage directly, or a `State`/overlay
+/// during block execution so writes revert with the transaction)
+pub struct DeadManSwitchModule<K = Arc<Storage>> {
+    kv: K,
 }
 
 impl DeadManSwitchModule {
     pub fn new(storage: Arc<Storage>) -> Self {
-        Self { storage }
+        Self { kv: storage }
+    }
+}
+
+impl<K: KvStore> DeadManSwitchModule<K> {
+    pub fn with_store(kv: K) -> Self {
+        Self { kv }
     }
 
     /// Load the switch configuration for a user
     pub fn load_config(&self, owner: &str) -> Option<DeadManSwitchConfig> {
         let key = format!("dms:config:{}", owner);
-        self.storage
-            .db
-            .get(&key)
-            .ok()
-            .flatten()
+        self.kv
+            .kv_get(&key)
             .and_then(|v| bincode::deserialize::<DeadManSwitchConfig>(&v).ok())
     }
 
     /// Save the switch configuration for a user
-    fn save_config(&self, owner: &str, config: &DeadManSwitchConfig) -> Result<(), String> {
+    fn save_config(&mut self, owner: &str, config: &DeadManSwitchConfig) -> Result<(), String> {
         let key = format!("dms:config:{}", owner);
-        self.storage
-            .db
-            .put(&key, bincode::serialize(config).map_err(|e| e.to_string())?)
-            .map_err(|e| e.to_string())
+        self.kv
+            .kv_put(&key, bincode::serialize(config).map_err(|e| e.to_string())?)
     }
 
     /// Register or update a dead man switch
     pub fn register(
-        &self,
+        &mut self,
         owner: &str,
         beneficiary: &str,
         period_blocks: u64,
         current_block: u64,
     ) -> Result<(), String> {
-        if period_blocks == 0 {
-            return Err("Period must be greater than 0".to_string());
+        let params: DmsParams = load_params(&mut self.kv);
+        if period_blocks < params.min_period_blocks {
+            return Err(format!(
+                "Period must be at least {} blocks",
+                params.min_period_blocks
+            ));
         }
         if owner == beneficiary {
             return Err("Beneficiary cannot be the owner".to_string());
@@ -67,7 +105,7 @@ impl DeadManSwitchModule {
     }
 
     /// Reset the inactivity timer
-    pub fn ping(&self, owner: &str, current_block: u64) -> Result<(), String> {
+    pub fn ping(&mut self, owner: &str, current_block: u64) -> Result<(), String> {
         let mut config = self.load_config(owner).ok_or("No dead man switch registered")?;
         config.last_active_block = current_block;
         self.save_config(owner, &config)
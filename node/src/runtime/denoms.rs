@@ -0,0 +1,158 @@
+use crate::runtime::token_factory::{parse_factory_denom, FactoryDenom};
+use serde::Serialize;
+
+/// Base and display units of a denomination; amounts on chain are always in the base unit
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct DenomMetadata {
+    /// On-chain unit (`udgt`, `factory/{creator}/{sub}`)
+    pub base: String,
+    /// Human unit (`DGT`); 1 display unit = 10^exponent base units
+    pub display: String,
+    pub exponent: u32,
+    pub symbol: String,
+    pub description: String,
+    /// `native` or `factory`
+    pub source: String,
+}
+
+/// Registry of known denominations. Signed payloads carry base units only; display units
+/// are converted for unsigned inputs such as the faucet.
+#[derive(Debug, Clone)]
+pub struct DenomRegistry {
+    denoms: Vec<DenomMetadata>,
+}
+
+impl DenomRegistry {
+    /// The native governance (DGT) and reward (DRT) tokens
+    pub fn native() -> Self {
+        let native = |base: &str, display: &str, description: &str| DenomMetadata {
+            base: base.to_string(),
+            display: display.to_string(),
+            exponent: 6,
+            symbol: display.to_string(),
+            description: description.to_string(),
+            source: "native".to_string(),
+        };
+        Self {
+            denoms: vec![
+                native("udgt", "DGT", "Governance token for voting and staking"),
+                native("udrt", "DRT", "Reward token for transaction fees and staking rewards"),
+            ],
+        }
+    }
+
+    /// Native denoms plus the token factory's
+    pub fn with_factory(factory_denoms: impl IntoIterator<Item = FactoryDenom>) -> Self {
+        let mut registry = Self::native();
+        registry
+            .denoms
+            .extend(factory_denoms.into_iter().map(|d| DenomMetadata {
+                display: d.symbol.clone(),
+                exponent: d.decimals as u32,
+                symbol: d.symbol,
+                description: format!("Token factory denom created by {}", d.creator),
+                source: "factory".to_string(),
+                base: d.denom,
+            }));
+        registry
+    }
+
+    pub fn denoms(&self) -> &[DenomMetadata] {
+        &self.denoms
+    }
+
+    /// Metadata of a base denom
+    pub fn get(&self, base: &str) -> Option<&DenomMetadata> {
+        self.denoms.iter().find(|d| d.base == base)
+    }
+
+    /// Check that a denom in a signed payload is a base unit. Display units are rejected
+    /// rather than scaled, so a signed amount always means the same number of base units.
+    pub fn check_signed(&self, denom: &str) -> Result<(), String> {
+        if parse_factory_denom(denom).is_some() || self.get(denom).is_some() {
+            return Ok(());
+        }
+        if let Some(meta) = self.denoms.iter().find(|d| d.display == denom) {
+            return Err(format!(
+                "display unit {denom} is not accepted in signed payloads: use {} (1 {denom} = 10^{} {})",
+                meta.base, meta.exponent, meta.base
+            ));
+        }
+        self.canonicalize(denom, 0).map(|_| ())
+    }
+
+    /// Convert `amount` of `denom` to its base unit. Only exact base or native display
+    /// names are accepted; other spellings of a known unit (`dgt`, `UDGT`) are rejected as
+    /// ambiguous. Well-formed factory denoms pass through; whether they exist is checked
+    /// against state by the mempool.
+    pub fn canonicalize(&self, denom: &str, amount: u128) -> Result<(String, u128), String> {
+        if parse_factory_denom(denom).is_some() || self.get(denom).is_some() {
+            return Ok((denom.to_string(), amount));
+        }
+        let native = self.denoms.iter().filter(|d| d.source == "native");
+        for meta in native {
+            if meta.display == denom {
+                let base_amount = 10u128
+                    .checked_pow(meta.exponent)
+                    .and_then(|scale| amount.checked_mul(scale))
+                    .ok_or_else(|| format!("amount {amount} {denom} overflows {}", meta.base))?;
+                return Ok((meta.base.clone(), base_amount));
+            }
+            if meta.base.eq_ignore_ascii_case(denom) || meta.display.eq_ignore_ascii_case(denom) {
+                return Err(format!(
+                    "ambiguous denom {denom}: use {} (base) or {} (display)",
+                    meta.base, meta.display
+                ));
+            }
+        }
+        Err(format!("unsupported denom: {denom}"))
+    }
+
+    /// `amount` base units in whole display units, e.g. `1500000 udgt` -> `1.5 DGT`
+    pub fn format(&self, base: &str, amount: u128) -> String {
+        let Some(meta) = self.get(base) else {
+            return format!("{amount} {base}");
+        };
+        let scale = 10u128.pow(meta.exponent);
+        let (whole, frac) = (amount / scale, amount % scale);
+        if frac == 0 {
+            return format!("{whole} {}", meta.display);
+        }
+        let frac = format!("{frac:0width$}", width = meta.exponent as usize);
+        format!("{whole}.{} {}", frac.trim_end_matches('0'), meta.display)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonicalize_and_format() {
+        let registry = DenomRegistry::native();
+        assert_eq!(registry.canonicalize("udgt", 5), Ok(("udgt".to_string(), 5)));
+        assert_eq!(
+            registry.canonicalize("DRT", 2),
+            Ok(("udrt".to_string(), 2_000_000))
+        );
+        assert_eq!(
+            registry.canonicalize("factory/alice/gold", 7),
+            Ok(("factory/alice/gold".to_string(), 7))
+        );
+        for ambiguous in ["dgt", "UDGT", "Drt"] {
+            assert!(registry.canonicalize(ambiguous, 1).unwrap_err().contains("ambiguous"));
+        }
+        assert!(registry.canonicalize("uatom", 1).is_err());
+        assert!(registry.canonicalize("DGT", u128::MAX).is_err());
+
+        // Signed payloads take base units only
+        assert!(registry.check_signed("udgt").is_ok());
+        assert!(registry.check_signed("factory/alice/gold").is_ok());
+        assert!(registry.check_signed("DGT").unwrap_err().contains("use udgt"));
+        assert!(registry.check_signed("UDGT").unwrap_err().contains("ambiguous"));
+        assert!(registry.check_signed("uatom").is_err());
+
+        assert_eq!(registry.format("udgt", 1_500_000), "1.5 DGT");
+        assert_eq!(registry.format("udrt", 3_000_000), "3 DRT");
+    }
+}
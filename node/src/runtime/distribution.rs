@@ -0,0 +1,86 @@
+use crate::runtime::module_accounts::{
+    module_address, module_balance, send_from_module, COMMUNITY_POOL, FEE_COLLECTOR,
+};
+use crate::runtime::staking::StakingModule;
+use crate::state::State;
+use crate::storage::events::Event;
+
+/// Share of distributed fees sent to the community pool, in basis points
+pub const DEFAULT_COMMUNITY_TAX_BPS: u128 = 200;
+
+/// End-block fee distribution. The fee collector's udgt (what is left after the burn) goes
+/// to the community pool (`community_tax_bps`) and to validators pro rata to their bonded
+/// stake; rounding dust stays in the collector. Nothing is paid out while no validator has
+/// stake. Returns one event per payout.
+pub fn distribute_fees(
+    state: &mut State,
+    staking: &StakingModule,
+    community_tax_bps: u128,
+) -> Result<Vec<Event>, String> {
+    let collected = module_balance(state, FEE_COLLECTOR, "udgt");
+    let validators = staking.validators();
+    let total_stake = validators.iter().map(|(_, stake)| *stake).sum::<u128>();
+    if collected == 0 || total_stake == 0 {
+        return Ok(Vec::new());
+    }
+    let mut events = Vec::new();
+    let tax = collected * community_tax_bps.min(10_000) / 10_000;
+    if tax > 0 {
+        send_from_module(state, FEE_COLLECTOR, &module_address(COMMUNITY_POOL), "udgt", tax)?;
+        events.push(
+            Event::new("community_tax")
+                .attr("amount", tax)
+                .attr("denom", "udgt"),
+        );
+    }
+    let rewards = collected - tax;
+    for (validator, stake) in validators {
+        let share = rewards
+            .checked_mul(stake)
+            .map(|v| v / total_stake)
+            .unwrap_or(rewards / total_stake * stake);
+        if share == 0 {
+            continue;
+        }
+        send_from_module(state, FEE_COLLECTOR, &validator, "udgt", share)?;
+        events.push(
+            Event::new("validator_fee_reward")
+                .attr("validator", &validator)
+                .attr("amount", share)
+                .attr("denom", "udgt"),
+        );
+    }
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::module_accounts::collect_block_fees;
+    use crate::runtime::supply::{check_invariants, mint_to};
+
+    #[test]
+    fn test_fees_split_between_community_pool_and_validators() {
+        let mut state = State::new_for_test();
+        let mut staking = StakingModule::new(state.storage.clone());
+        mint_to(&mut state, "alice", "udgt", 10_000).unwrap();
+
+        // Fees are kept until some validator has stake
+        state.set_balance("alice", "udgt", 9_000);
+        collect_block_fees(&mut state, 1_000, 0).unwrap();
+        assert!(distribute_fees(&mut state, &staking, 200).unwrap().is_empty());
+
+        staking.register_validator("val1");
+        staking.register_validator("val2");
+        staking.delegate("alice", "val1", 300).unwrap();
+        staking.delegate("alice", "val2", 600).unwrap();
+        let events = distribute_fees(&mut state, &staking, 200).unwrap();
+        assert_eq!(events.len(), 3);
+        assert_eq!(module_balance(&mut state, COMMUNITY_POOL, "udgt"), 20);
+        assert_eq!(state.balance_of("val1", "udgt"), 326);
+        assert_eq!(state.balance_of("val2", "udgt"), 653);
+        // 980 * 1/3 and 980 * 2/3 leave 1 udgt of dust
+        assert_eq!(module_balance(&mut state, FEE_COLLECTOR, "udgt"), 1);
+        assert!(check_invariants(&state.storage).iter().all(|c| c.holds()));
+    }
+}
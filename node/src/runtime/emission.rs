@@ -1,3 +1,7 @@
+use crate::runtime::module_accounts::{
+    emission_account, mint_to_module, module_address, send_from_module, EMISSION_POOLS,
+};
+use crate::runtime::params::{parse_shares, GovernedParams};
 use crate::{state::State, storage::state::Storage};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -60,6 +64,55 @@ pub struct EmissionBreakdown {
     pub bridge_operations: u8,    // percentage (5)
 }
 
+impl Default for EmissionBreakdown {
+    fn default() -> Self {
+        Self {
+            block_rewards: 60,
+            staking_rewards: 25,
+            ai_module_incentives: 10,
+            bridge_operations: 5,
+        }
+    }
+}
+
+impl GovernedParams for EmissionBreakdown {
+    const MODULE: &'static str = "emission";
+
+    fn get_param(&self, key: &str) -> Option<String> {
+        (key == "emission.breakdown").then(|| {
+            format!(
+                "{},{},{},{}",
+                self.block_rewards,
+                self.staking_rewards,
+                self.ai_module_incentives,
+                self.bridge_operations
+            )
+        })
+    }
+
+    fn set_param(&mut self, key: &str, value: &str) -> Result<(), String> {
+        if key != "emission.breakdown" {
+            return Err(format!("Unknown parameter: {key}"));
+        }
+        let shares = parse_shares(value)?
+            .into_iter()
+            .map(u8::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| format!("Invalid {key}: {value}"))?;
+        let [block_rewards, staking_rewards, ai_module_incentives, bridge_operations] = shares[..]
+        else {
+            return Err(format!("Invalid {key}: {value}"));
+        };
+        *self = Self {
+            block_rewards,
+            staking_rewards,
+            ai_module_incentives,
+            bridge_operations,
+        };
+        Ok(())
+    }
+}
+
 impl EmissionBreakdown {
     pub fn is_valid(&self) -> bool {
         self.block_rewards
@@ -86,12 +139,7 @@ impl EmissionEngine {
                 annual_inflation_rate: 500,
             }, // 5% in basis points
             initial_supply: 0, // DRT starts with 0 supply
-            emission_breakdown: EmissionBreakdown {
-                block_rewards: 60,
-                staking_rewards: 25,
-                ai_module_incentives: 10,
-                bridge_operations: 5,
-            },
+            emission_breakdown: EmissionBreakdown::default(),
         };
 
         // Load existing circulating supply (cumulative emitted) from storage
@@ -133,10 +181,6 @@ impl EmissionEngine {
         }
     }
 
-    fn pool_key(pool: &str) -> String {
-        format!("emission:pool:{pool}")
-    }
-
     fn height_key() -> &'static str {
         "emission:last_height"
     }
@@ -220,23 +264,15 @@ impl EmissionEngine {
         pools
     }
 
+    /// udrt balance of the module account backing `pool`
     pub fn pool_amount(&self, pool: &str) -> u128 {
         self.storage
-            .db
-            .get(Self::pool_key(pool))
-            .ok()
-            .flatten()
-            .and_then(|v| bincode::deserialize::<u128>(&v).ok())
+            .get_balances_db(&module_address(&emission_account(pool)))
+            .get("udrt")
+            .copied()
             .unwrap_or(0)
     }
 
-    fn set_pool_amount(&self, pool: &str, amt: u128) {
-        let _ = self
-            .storage
-            .db
-            .put(Self::pool_key(pool), bincode::serialize(&amt).unwrap());
-    }
-
     fn set_circulating_supply(&self, supply: u128) {
         let _ = self.storage.db.put(
             Self::circulating_supply_key(),
@@ -294,10 +330,13 @@ impl EmissionEngine {
             let total_emission = self.calculate_per_block_emission(h);
             let pool_distributions = self.calculate_pool_distributions(total_emission);
 
-            // Update pool amounts
-            for (pool, amount) in &pool_distributions {
-                let current = self.pool_amount(pool);
-                self.set_pool_amount(pool, current.saturating_add(*amount));
+            // Mint the emission into the pools' module accounts
+            if let Ok(mut st) = self.state.lock() {
+                for (pool, amount) in &pool_distributions {
+                    if let Err(e) = mint_to_module(&mut st, &emission_account(pool), "udrt", *amount) {
+                        eprintln!("emission mint into {pool} failed at height {h}: {e}");
+                    }
+                }
             }
 
             // Update circulating supply
@@ -337,29 +376,22 @@ impl EmissionEngine {
         }
     }
     pub fn claim(&self, pool: &str, amount: u128, to: &str) -> Result<u128, String> {
+        if !EMISSION_POOLS.contains(&pool) {
+            return Err(format!("unknown emission pool {pool}"));
+        }
         let cur = self.pool_amount(pool);
         if amount > cur {
             return Err("InsufficientPool".into());
         }
-        let new_amt = cur - amount;
-        self.set_pool_amount(pool, new_amt);
-        // credit account with DRT tokens (reward token), not DGT (governance token)
-        if let Ok(mut st) = self.state.lock() {
-            st.credit(to, "udrt", amount);
-        }
-        Ok(new_amt)
+        // pay out DRT tokens (reward token), not DGT (governance token)
+        let mut st = self.state.lock().map_err(|e| e.to_string())?;
+        send_from_module(&mut st, &emission_account(pool), to, "udrt", amount)?;
+        Ok(cur - amount)
     }
     pub fn snapshot(&self) -> EmissionSnapshot {
         let mut pools = std::collections::HashMap::new();
 
-        // Use current pool names from emission breakdown
-        let pool_names = [
-            "block_rewards",
-            "staking_rewards",
-            "ai_module_incentives",
-            "bridge_operations",
-        ];
-        for pool in pool_names.iter() {
+        for pool in EMISSION_POOLS.iter() {
             pools.insert(pool.to_string(), self.pool_amount(pool));
         }
 
@@ -370,15 +402,14 @@ impl EmissionEngine {
     }
 
     /// Process emission for a single block (for testing)
-    pub fn process_block_emission(&mut self, height: u64, _state: &mut crate::state::State) -> Result<EmissionEvent, String> {
+    pub fn process_block_emission(&mut self, height: u64, state: &mut crate::state::State) -> Result<EmissionEvent, String> {
         // Calculate emission for this block
         let total_emission = self.calculate_per_block_emission(height);
         let pool_distributions = self.calculate_pool_distributions(total_emission);
 
-        // Update pool amounts
+        // Mint the emission into the pools' module accounts
         for (pool, amount) in &pool_distributions {
-            let current = self.pool_amount(pool);
-            self.set_pool_amount(pool, current.saturating_add(*amount));
+            mint_to_module(state, &emission_account(pool), "udrt", *amount)?;
         }
 
         // Update circulating supply
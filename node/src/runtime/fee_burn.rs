@@ -5,6 +5,7 @@ Implements configurable fee burning to reduce circulating supply of DGT/DRT toke
 Supports governance-configurable burn rates with transparent accounting.
 */
 
+use crate::runtime::params::{parse, GovernedParams};
 use crate::state::State;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -34,6 +35,29 @@ impl Default for FeeBurnConfig {
     }
 }
 
+impl GovernedParams for FeeBurnConfig {
+    const MODULE: &'static str = "fee_burn";
+
+    fn get_param(&self, key: &str) -> Option<String> {
+        Some(match key {
+            "fee_burn.burn_rate_bps" => self.burn_rate_bps.to_string(),
+            "fee_burn.min_burn_threshold" => self.min_burn_threshold.to_string(),
+            "fee_burn.enabled" => self.enabled.to_string(),
+            _ => return None,
+        })
+    }
+
+    fn set_param(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "fee_burn.burn_rate_bps" => self.burn_rate_bps = parse(key, value)?,
+            "fee_burn.min_burn_threshold" => self.min_burn_threshold = parse(key, value)?,
+            "fee_burn.enabled" => self.enabled = parse(key, value)?,
+            _ => return Err(format!("Unknown parameter: {key}")),
+        }
+        Ok(())
+    }
+}
+
 /// Fee burn event for accounting and audit trails
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FeeBurnEvent {
@@ -86,6 +110,11 @@ impl FeeBurnEngine {
             return Ok(None);
         }
 
+        // Fees are only charged in udgt, so there is nothing to burn in any other token
+        if self.config.burn_token != "udgt" {
+            return Ok(None);
+        }
+
         // Check minimum threshold
         if fee_paid < self.config.min_burn_threshold {
             return Ok(None);
@@ -179,11 +208,9 @@ impl FeeBurnEngine {
             .map(|e| e.burn_amount)
             .sum::<u128>();
 
-        let effective_burn_rate = if total_fees_processed > 0 {
-            (total_burned_all * 10000) / total_fees_processed
-        } else {
-            0
-        };
+        let effective_burn_rate = (total_burned_all * 10000)
+            .checked_div(total_fees_processed)
+            .unwrap_or(0);
 
         BurnStats {
             total_events,
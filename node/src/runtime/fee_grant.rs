@@ -0,0 +1,189 @@
+use crate::state::KvStore;
+use crate::storage::state::Storage;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Allowance letting `grantee` spend up to `spend_limit` udgt of `granter`'s balance on fees
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FeeAllowance {
+    pub granter: String,
+    pub grantee: String,
+    /// Total udgt the grantee may still spend on fees
+    pub spend_limit: u128,
+    /// Last block height at which the allowance can be used (None = no expiry)
+    pub expiration_height: Option<u64>,
+}
+
+impl FeeAllowance {
+    pub fn is_expired(&self, height: u64) -> bool {
+        self.expiration_height.is_some_and(|h| height > h)
+    }
+}
+
+/// Fee grant module over a KV store (see `DeadManSwitchModule`)
+pub struct FeeGrantModule<K = Arc<Storage>> {
+    kv: K,
+}
+
+impl FeeGrantModule {
+    pub fn new(storage: Arc<Storage>) -> Self {
+        Self { kv: storage }
+    }
+}
+
+impl<K: KvStore> FeeGrantModule<K> {
+    pub fn with_store(kv: K) -> Self {
+        Self { kv }
+    }
+
+    fn key(granter: &str, grantee: &str) -> String {
+        format!("feegrant:{granter}:{grantee}")
+    }
+
+    /// Load the allowance `granter` gave `grantee`
+    pub fn get_allowance(&self, granter: &str, grantee: &str) -> Option<FeeAllowance> {
+        self.kv
+            .kv_get(&Self::key(granter, grantee))
+            .and_then(|v| bincode::deserialize::<FeeAllowance>(&v).ok())
+    }
+
+    /// All allowances issued by `granter`
+    pub fn allowances_by_granter(&self, granter: &str) -> Vec<FeeAllowance> {
+        self.kv
+            .kv_scan_prefix(&format!("feegrant:{granter}:"))
+            .into_iter()
+            .filter_map(|(_, v)| bincode::deserialize::<FeeAllowance>(&v).ok())
+            .collect()
+    }
+
+    fn save_allowance(&mut self, allowance: &FeeAllowance) -> Result<(), String> {
+        let key = Self::key(&allowance.granter, &allowance.grantee);
+        self.kv
+            .kv_put(&key, bincode::serialize(allowance).map_err(|e| e.to_string())?)
+    }
+
+    fn delete_allowance(&mut self, granter: &str, grantee: &str) -> Result<(), String> {
+        self.kv.kv_delete(&Self::key(granter, grantee))
+    }
+
+    /// Create or replace an allowance
+    pub fn grant(
+        &mut self,
+        granter: &str,
+        grantee: &str,
+        spend_limit: u128,
+        expiration_height: Option<u64>,
+        current_block: u64,
+    ) -> Result<(), String> {
+        if granter == grantee {
+            return Err("Cannot grant a fee allowance to yourself".to_string());
+        }
+        if spend_limit == 0 {
+            return Err("Spend limit must be greater than 0".to_string());
+        }
+        if expiration_height.is_some_and(|h| h < current_block) {
+            return Err("Expiration height is in the past".to_string());
+        }
+        self.save_allowance(&FeeAllowance {
+            granter: granter.to_string(),
+            grantee: grantee.to_string(),
+            spend_limit,
+            expiration_height,
+        })
+    }
+
+    /// Remove an allowance
+    pub fn revoke(&mut self, granter: &str, grantee: &str) -> Result<(), String> {
+        if self.get_allowance(granter, grantee).is_none() {
+            return Err("No fee allowance found".to_string());
+        }
+        self.delete_allowance(granter, grantee)
+    }
+
+    /// Check an allowance can cover `amount` at `current_block` without consuming it
+    pub fn check(
+        &self,
+        granter: &str,
+        grantee: &str,
+        amount: u128,
+        current_block: u64,
+    ) -> Result<FeeAllowance, String> {
+        let allowance = self
+            .get_allowance(granter, grantee)
+            .ok_or_else(|| format!("No fee allowance from {granter} to {grantee}"))?;
+        if allowance.is_expired(current_block) {
+            return Err("Fee allowance expired".to_string());
+        }
+        if allowance.spend_limit < amount {
+            return Err(format!(
+                "Fee allowance exceeded: {} < {amount}",
+                allowance.spend_limit
+            ));
+        }
+        Ok(allowance)
+    }
+
+    /// Deduct `amount` from an allowance; an exhausted allowance is removed
+    pub fn use_allowance(
+        &mut self,
+        granter: &str,
+        grantee: &str,
+        amount: u128,
+        current_block: u64,
+    ) -> Result<(), String> {
+        let allowance = self.check(granter, grantee, amount, current_block)?;
+        self.settle(allowance, amount)
+    }
+
+    /// Store `allowance` (as previously returned by `check`) reduced by `spent`. Used to
+    /// rebase an allowance once the final fee of a transaction is known.
+    pub fn settle(&mut self, mut allowance: FeeAllowance, spent: u128) -> Result<(), String> {
+        allowance.spend_limit = allowance.spend_limit.saturating_sub(spent);
+        if allowance.spend_limit == 0 {
+            self.delete_allowance(&allowance.granter, &allowance.grantee)
+        } else {
+            self.save_allowance(&allowance)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_allowance_spend_and_expiry() {
+        let dir = tempdir().unwrap();
+        let storage = Arc::new(Storage::open(dir.path().join("test.db")).unwrap());
+        let mut grants = FeeGrantModule::new(storage);
+
+        grants.grant("sponsor", "user", 1_000, Some(50), 10).unwrap();
+        grants.use_allowance("sponsor", "user", 400, 20).unwrap();
+        assert_eq!(grants.get_allowance("sponsor", "user").unwrap().spend_limit, 600);
+
+        assert!(grants.use_allowance("sponsor", "user", 700, 20).is_err());
+        assert!(grants.use_allowance("sponsor", "user", 100, 51).is_err());
+
+        grants.use_allowance("sponsor", "user", 600, 50).unwrap();
+        assert!(grants.get_allowance("sponsor", "user").is_none());
+    }
+
+    #[test]
+    fn test_grant_validation_and_revoke() {
+        let dir = tempdir().unwrap();
+        let storage = Arc::new(Storage::open(dir.path().join("test.db")).unwrap());
+        let mut grants = FeeGrantModule::new(storage);
+
+        assert!(grants.grant("a", "a", 10, None, 1).is_err());
+        assert!(grants.grant("a", "b", 0, None, 1).is_err());
+        assert!(grants.grant("a", "b", 10, Some(5), 6).is_err());
+
+        grants.grant("a", "b", 10, None, 1).unwrap();
+        grants.grant("a", "c", 10, None, 1).unwrap();
+        assert_eq!(grants.allowances_by_granter("a").len(), 2);
+        grants.revoke("a", "b").unwrap();
+        assert!(grants.revoke("a", "b").is_err());
+        assert_eq!(grants.allowances_by_granter("a").len(), 1);
+    }
+}
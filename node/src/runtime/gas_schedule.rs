@@ -0,0 +1,170 @@
+use crate::gas::{GasSchedule, GAS_TABLE_VERSION};
+use crate::runtime::params::{self, GovernedParams};
+use crate::state::KvStore;
+use crate::storage::state::Storage;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// A gas schedule and the first height it prices
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GasScheduleVersion {
+    pub version: u32,
+    pub activation_height: u64,
+    pub schedule: GasSchedule,
+}
+
+/// Gas schedules kept in state. Every governed change to a `gas.*` parameter stores a new
+/// version activating at the next block; old versions are kept so the gas of any historical
+/// transaction can be recomputed from the version recorded in its receipt.
+pub struct GasScheduleModule<K = Arc<Storage>> {
+    kv: K,
+}
+
+// Key layout:
+//   gas:schedule:{version:010}  -> JSON(GasScheduleVersion)
+// JSON so versions stored before a cost field existed still decode (new fields default).
+// Until a version is stored, the built-in schedule applies as GAS_TABLE_VERSION.
+impl GasScheduleModule {
+    pub fn new(storage: Arc<Storage>) -> Self {
+        Self { kv: storage }
+    }
+}
+
+fn built_in() -> GasScheduleVersion {
+    GasScheduleVersion {
+        version: GAS_TABLE_VERSION,
+        activation_height: 0,
+        schedule: GasSchedule::default(),
+    }
+}
+
+impl<K: KvStore> GasScheduleModule<K> {
+    pub fn with_store(kv: K) -> Self {
+        Self { kv }
+    }
+
+    /// Store the built-in schedule as the genesis version, so later binaries with different
+    /// defaults keep pricing the chain the same. Returns false if a version exists.
+    pub fn init(&mut self) -> Result<bool, String> {
+        if !self.kv.kv_scan_prefix("gas:schedule:").is_empty() {
+            return Ok(false);
+        }
+        self.put(&built_in())?;
+        Ok(true)
+    }
+
+    fn put(&mut self, version: &GasScheduleVersion) -> Result<(), String> {
+        let data = serde_json::to_vec(version).map_err(|e| e.to_string())?;
+        self.kv
+            .kv_put(&format!("gas:schedule:{:010}", version.version), data)
+    }
+
+    /// Every version, oldest first
+    pub fn versions(&self) -> Vec<GasScheduleVersion> {
+        let stored: Vec<GasScheduleVersion> = self
+            .kv
+            .kv_scan_prefix("gas:schedule:")
+            .into_iter()
+            .filter_map(|(_, v)| serde_json::from_slice::<GasScheduleVersion>(&v).ok())
+            .map(|mut v| {
+                // Versions stored before schedules carried their version
+                v.schedule.version = v.version;
+                v
+            })
+            .collect();
+        if stored.is_empty() {
+            vec![built_in()]
+        } else {
+            stored
+        }
+    }
+
+    pub fn version(&self, version: u32) -> Option<GasScheduleVersion> {
+        self.versions().into_iter().find(|v| v.version == version)
+    }
+
+    /// Newest version, which may activate after the current height
+    pub fn latest(&self) -> GasScheduleVersion {
+        self.versions().pop().unwrap_or_else(built_in)
+    }
+
+    /// Version that prices block `height`
+    pub fn at_height(&self, height: u64) -> GasScheduleVersion {
+        self.versions()
+            .into_iter()
+            .rev()
+            .find(|v| v.activation_height <= height)
+            .unwrap_or_else(built_in)
+    }
+
+    /// Store a new version with one field of the newest schedule changed. Returns the new
+    /// version number.
+    pub fn set_param(
+        &mut self,
+        key: &str,
+        value: &str,
+        activation_height: u64,
+    ) -> Result<u32, String> {
+        params::spec(key)?.check(value)?;
+        self.init()?;
+        let latest = self.latest();
+        if activation_height < latest.activation_height {
+            return Err(format!(
+                "gas schedule version {} already activates at height {}",
+                latest.version, latest.activation_height
+            ));
+        }
+        let mut schedule = latest.schedule;
+        schedule.set_param(key, value)?;
+        schedule.version = latest.version + 1;
+        let next = GasScheduleVersion {
+            version: schedule.version,
+            activation_height,
+            schedule,
+        };
+        self.put(&next)?;
+        Ok(next.version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_versions_activate_at_their_height() {
+        let dir = tempdir().unwrap();
+        let storage = Arc::new(Storage::open(dir.path().join("test.db")).unwrap());
+        let mut gas = GasScheduleModule::new(storage);
+        assert!(gas.init().unwrap());
+        assert!(!gas.init().unwrap());
+        assert_eq!(gas.at_height(5).version, GAS_TABLE_VERSION);
+
+        assert!(gas.set_param("gas.transfer_base", "-1", 10).is_err());
+        assert_eq!(gas.set_param("gas.transfer_base", "900", 10).unwrap(), 2);
+        // A second change in the same block builds on the pending version
+        assert_eq!(gas.set_param("gas.per_byte", "3", 10).unwrap(), 3);
+        assert!(gas.set_param("gas.per_byte", "4", 9).is_err());
+
+        let before = gas.at_height(9);
+        assert_eq!(before.version, 1);
+        assert_eq!(
+            before.schedule.transfer_base,
+            GasSchedule::default().transfer_base
+        );
+        let after = gas.at_height(10);
+        assert_eq!(after.version, 3);
+        assert_eq!(after.schedule.version, 3);
+        assert_eq!(
+            (after.schedule.transfer_base, after.schedule.per_byte),
+            (900, 3)
+        );
+        // Historical versions stay available
+        assert_eq!(
+            gas.version(2).unwrap().schedule.per_byte,
+            GasSchedule::default().per_byte
+        );
+        assert_eq!(gas.versions().len(), 3);
+    }
+}
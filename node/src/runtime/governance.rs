@@ -1,5 +1,18 @@
-use crate::{runtime::staking::StakingModule, state::State, storage::state::Storage};
+use crate::runtime::gas_schedule::GasScheduleModule;
+use crate::runtime::module_accounts::{
+    burn_from_module, module_accounts, module_address, send_from_module, send_to_module,
+    COMMUNITY_POOL, GOV_DEPOSITS,
+};
+use crate::runtime::params::{self, parse, GovernedParams, ParamsModule};
+use crate::runtime::pause::{self, PauseModule};
+use crate::runtime::staking::StakingModule;
+use crate::runtime::upgrade::{UpgradeModule, UpgradePlan};
+use crate::state::{KvStore, State, StateOverlay};
+use crate::storage::bridge::BridgeStore;
+use crate::storage::events::Event;
+use crate::storage::state::Storage;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
 use std::sync::{Arc, Mutex};
 use std::{fs, path::PathBuf};
 
@@ -14,6 +27,95 @@ pub struct GovernanceConfig {
     pub quorum: u128,           // Minimum participation required (in micro units)
     pub threshold: u128, // Minimum yes votes for proposal to pass (in basis points, e.g., 5000 = 50%)
     pub veto_threshold: u128, // Minimum no_with_veto votes to veto proposal (in basis points)
+    /// Accounts without stake vote with their liquid udgt balance (at voting start)
+    #[serde(default)]
+    pub liquid_balance_voting: bool,
+    /// Deposit an expedited or emergency proposal needs before voting starts
+    #[serde(default = "default_expedited_min_deposit")]
+    pub expedited_min_deposit: u128,
+    /// Voting period of expedited proposals, in blocks
+    #[serde(default = "default_expedited_voting_period")]
+    pub expedited_voting_period: u64,
+    /// Yes share an expedited proposal needs (in basis points); below it the proposal
+    /// continues as a standard one
+    #[serde(default = "default_expedited_threshold")]
+    pub expedited_threshold: u128,
+    /// Blocks validators have to pass an emergency proposal
+    #[serde(default = "default_emergency_voting_period")]
+    pub emergency_voting_period: u64,
+    /// Share of the stake delegated to validators whose validators must vote yes on an
+    /// emergency proposal (in basis points)
+    #[serde(default = "default_emergency_threshold")]
+    pub emergency_threshold: u128,
+}
+
+fn default_expedited_min_deposit() -> u128 {
+    GovernanceConfig::default().expedited_min_deposit
+}
+
+fn default_expedited_voting_period() -> u64 {
+    GovernanceConfig::default().expedited_voting_period
+}
+
+fn default_expedited_threshold() -> u128 {
+    GovernanceConfig::default().expedited_threshold
+}
+
+fn default_emergency_voting_period() -> u64 {
+    GovernanceConfig::default().emergency_voting_period
+}
+
+fn default_emergency_threshold() -> u128 {
+    GovernanceConfig::default().emergency_threshold
+}
+
+impl GovernedParams for GovernanceConfig {
+    const MODULE: &'static str = "governance";
+
+    fn get_param(&self, key: &str) -> Option<String> {
+        Some(match key {
+            "gas_limit" => self.gas_limit.to_string(),
+            "consensus.max_gas_per_block" => self.max_gas_per_block.to_string(),
+            "governance.min_deposit" => self.min_deposit.to_string(),
+            "governance.deposit_period" => self.deposit_period.to_string(),
+            "governance.voting_period" => self.voting_period.to_string(),
+            "governance.quorum" => self.quorum.to_string(),
+            "governance.threshold" => self.threshold.to_string(),
+            "governance.veto_threshold" => self.veto_threshold.to_string(),
+            "governance.liquid_balance_voting" => self.liquid_balance_voting.to_string(),
+            "governance.expedited_min_deposit" => self.expedited_min_deposit.to_string(),
+            "governance.expedited_voting_period" => self.expedited_voting_period.to_string(),
+            "governance.expedited_threshold" => self.expedited_threshold.to_string(),
+            "governance.emergency_voting_period" => self.emergency_voting_period.to_string(),
+            "governance.emergency_threshold" => self.emergency_threshold.to_string(),
+            _ => return None,
+        })
+    }
+
+    fn set_param(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "gas_limit" => self.gas_limit = parse(key, value)?,
+            "consensus.max_gas_per_block" => self.max_gas_per_block = parse(key, value)?,
+            "governance.min_deposit" => self.min_deposit = parse(key, value)?,
+            "governance.deposit_period" => self.deposit_period = parse(key, value)?,
+            "governance.voting_period" => self.voting_period = parse(key, value)?,
+            "governance.quorum" => self.quorum = parse(key, value)?,
+            "governance.threshold" => self.threshold = parse(key, value)?,
+            "governance.veto_threshold" => self.veto_threshold = parse(key, value)?,
+            "governance.liquid_balance_voting" => self.liquid_balance_voting = parse(key, value)?,
+            "governance.expedited_min_deposit" => self.expedited_min_deposit = parse(key, value)?,
+            "governance.expedited_voting_period" => {
+                self.expedited_voting_period = parse(key, value)?
+            }
+            "governance.expedited_threshold" => self.expedited_threshold = parse(key, value)?,
+            "governance.emergency_voting_period" => {
+                self.emergency_voting_period = parse(key, value)?
+            }
+            "governance.emergency_threshold" => self.emergency_threshold = parse(key, value)?,
+            _ => return Err(format!("Unknown parameter: {key}")),
+        }
+        Ok(())
+    }
 }
 
 impl Default for GovernanceConfig {
@@ -27,6 +129,12 @@ impl Default for GovernanceConfig {
             quorum: 3333,                  // 33.33% quorum required (in basis points)
             threshold: 5000,               // 50% threshold for passing (in basis points)
             veto_threshold: 3333,          // 33.33% veto threshold (in basis points)
+            liquid_balance_voting: false,
+            expedited_min_deposit: 5_000_000_000, // 5000 DGT
+            expedited_voting_period: 50,
+            expedited_threshold: 6667,
+            emergency_voting_period: 50,
+            emergency_threshold: 6667, // Two thirds of validator-delegated stake
         }
     }
 }
@@ -45,12 +153,108 @@ pub struct Proposal {
     pub voting_start_height: u64,
     pub voting_end_height: u64,
     pub tally: Option<TallyResult>,
+    #[serde(default)]
+    pub track: ProposalTrack,
+}
+
+/// Proposals stored before proposal tracks existed
+#[derive(Deserialize)]
+struct LegacyProposal {
+    id: u64,
+    title: String,
+    description: String,
+    proposal_type: ProposalType,
+    status: ProposalStatus,
+    total_deposit: u128,
+    submit_height: u64,
+    deposit_end_height: u64,
+    voting_start_height: u64,
+    voting_end_height: u64,
+    tally: Option<TallyResult>,
+}
+
+impl Proposal {
+    /// Decode a stored proposal; proposals from before tracks existed are standard
+    pub fn decode(bytes: &[u8]) -> Option<Proposal> {
+        bincode::deserialize::<Proposal>(bytes).ok().or_else(|| {
+            let legacy = bincode::deserialize::<LegacyProposal>(bytes).ok()?;
+            Some(Proposal {
+                id: legacy.id,
+                title: legacy.title,
+                description: legacy.description,
+                proposal_type: legacy.proposal_type,
+                status: legacy.status,
+                total_deposit: legacy.total_deposit,
+                submit_height: legacy.submit_height,
+                deposit_end_height: legacy.deposit_end_height,
+                voting_start_height: legacy.voting_start_height,
+                voting_end_height: legacy.voting_end_height,
+                tally: legacy.tally,
+                track: ProposalTrack::Standard,
+            })
+        })
+    }
+}
+
+/// How a proposal is voted on
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposalTrack {
+    /// `voting_period` and `threshold`
+    #[default]
+    Standard,
+    /// Higher deposit, `expedited_voting_period` and `expedited_threshold`; a proposal that
+    /// does not pass continues as a standard one until `voting_period` has elapsed
+    Expedited,
+    /// Bridge halts and module pauses only. Passes, and executes, as soon as validators
+    /// holding `emergency_threshold` of the delegated stake vote yes
+    Emergency,
+}
+
+impl ProposalTrack {
+    pub fn is_standard(&self) -> bool {
+        *self == ProposalTrack::Standard
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProposalTrack::Standard => "standard",
+            ProposalTrack::Expedited => "expedited",
+            ProposalTrack::Emergency => "emergency",
+        }
+    }
 }
 
 /// Types of proposals supported
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum ProposalType {
-    ParameterChange { key: String, value: String },
+    ParameterChange {
+        key: String,
+        value: String,
+    },
+    /// Signalling only; passing it executes nothing
+    Text,
+    /// Pay `amount` from the community pool module account to `recipient`
+    CommunitySpend {
+        recipient: String,
+        amount: u128,
+        denom: String,
+    },
+    /// Schedule a chain halt at `height` for the upgrade `name`
+    SoftwareUpgrade {
+        name: String,
+        height: u64,
+        info: String,
+    },
+    /// Halt (or resume) bridge message ingestion
+    HaltBridge {
+        halted: bool,
+    },
+    /// Pause (or unpause) one of `pause::PAUSABLE_MODULES`
+    PauseModule {
+        module: String,
+        paused: bool,
+    },
 }
 
 /// Proposal status transitions
@@ -70,8 +274,91 @@ pub enum ProposalStatus {
 pub struct Vote {
     pub proposal_id: u64,
     pub voter: String,
+    /// Option with the largest share (the only one unless the vote is split)
+    pub option: VoteOption,
+    pub weight: u128, // Voting power in the proposal's snapshot
+    /// Split of the voting power across options; shares sum to `WEIGHT_ONE`
+    pub options: Vec<WeightedVoteOption>,
+}
+
+/// Votes stored before split votes existed
+#[derive(Deserialize)]
+struct LegacyVote {
+    proposal_id: u64,
+    voter: String,
+    option: VoteOption,
+    weight: u128,
+}
+
+impl Vote {
+    fn decode(bytes: &[u8]) -> Option<Vote> {
+        bincode::deserialize::<Vote>(bytes).ok().or_else(|| {
+            let legacy = bincode::deserialize::<LegacyVote>(bytes).ok()?;
+            Some(Vote {
+                proposal_id: legacy.proposal_id,
+                voter: legacy.voter,
+                options: vec![WeightedVoteOption {
+                    option: legacy.option.clone(),
+                    weight: WEIGHT_ONE,
+                }],
+                option: legacy.option,
+                weight: legacy.weight,
+            })
+        })
+    }
+}
+
+/// Fixed-point scale of vote shares (18 decimals): `WEIGHT_ONE` is 1.0
+pub const WEIGHT_ONE: u128 = 1_000_000_000_000_000_000;
+
+/// One option of a split vote and the share of the voter's power it gets
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WeightedVoteOption {
     pub option: VoteOption,
-    pub weight: u128, // DGT balance at time of vote
+    /// Decimal share such as "0.25" in JSON
+    #[serde(with = "decimal_weight")]
+    pub weight: u128,
+}
+
+/// Parse a decimal share ("0.25", "1") into `WEIGHT_ONE` units
+pub fn parse_weight(raw: &str) -> Result<u128, String> {
+    let invalid = || format!("invalid vote weight {raw:?}");
+    let (int, frac) = raw.split_once('.').unwrap_or((raw, ""));
+    let digits = int.chars().chain(frac.chars()).all(|c| c.is_ascii_digit());
+    if int.is_empty() || frac.len() > 18 || !digits {
+        return Err(invalid());
+    }
+    let int: u128 = int.parse().map_err(|_| invalid())?;
+    let frac: u128 = format!("{frac:0<18}").parse().map_err(|_| invalid())?;
+    int.checked_mul(WEIGHT_ONE)
+        .and_then(|v| v.checked_add(frac))
+        .ok_or_else(invalid)
+}
+
+/// Format `WEIGHT_ONE` units as a decimal share without trailing zeros
+pub fn format_weight(weight: u128) -> String {
+    let frac = weight % WEIGHT_ONE;
+    if frac == 0 {
+        return (weight / WEIGHT_ONE).to_string();
+    }
+    let frac = format!("{frac:018}");
+    format!("{}.{}", weight / WEIGHT_ONE, frac.trim_end_matches('0'))
+}
+
+mod decimal_weight {
+    use serde::{Deserialize, Deserializer, Serializer};
+    pub fn serialize<S: Serializer>(v: &u128, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(&super::format_weight(*v))
+    }
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<u128, D::Error> {
+        let s: String = Deserialize::deserialize(d)?;
+        super::parse_weight(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Share `weight` (in `WEIGHT_ONE` units) of `power`, rounded down without overflowing
+fn weighted_share(power: u128, weight: u128) -> u128 {
+    power / WEIGHT_ONE * weight + power % WEIGHT_ONE * weight / WEIGHT_ONE
 }
 
 /// Deposit on a proposal (for tracking individual deposits)
@@ -83,7 +370,7 @@ pub struct Deposit {
     pub denom: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum VoteOption {
     Yes,
     No,
@@ -101,6 +388,29 @@ pub struct TallyResult {
     pub total_voting_power: u128,
 }
 
+/// An account's entry in a proposal's voting power snapshot
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SnapshotEntry {
+    /// Own voting power: bonded stake, or liquid udgt with `liquid_balance_voting`
+    pub power: u128,
+    /// Bonded stake by validator; a validator's vote covers it unless this account votes
+    pub delegations: BTreeMap<String, u128>,
+}
+
+/// How a validator's delegated power counted in a tally
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ValidatorTally {
+    pub validator: String,
+    /// Stake delegated to the validator in the snapshot
+    pub delegated_power: u128,
+    /// Part of it whose delegators voted themselves
+    pub overridden_power: u128,
+    /// Part of it counted with the validator's vote (0 if the validator did not vote)
+    pub inherited_power: u128,
+    /// The validator's vote, empty if it did not vote
+    pub vote: Vec<WeightedVoteOption>,
+}
+
 /// Governance events
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum GovernanceEvent {
@@ -145,6 +455,324 @@ pub enum GovernanceEvent {
         depositor: String,
         amount: u128,
     },
+    CommunitySpend {
+        proposal_id: u64,
+        recipient: String,
+        amount: u128,
+        denom: String,
+    },
+    UpgradeScheduled {
+        proposal_id: u64,
+        name: String,
+        height: u64,
+    },
+    /// An expedited proposal missed its threshold and continues as a standard one
+    ProposalConverted {
+        id: u64,
+        voting_end_height: u64,
+    },
+    BridgeHaltChanged {
+        proposal_id: u64,
+        halted: bool,
+    },
+    ModulePauseChanged {
+        proposal_id: u64,
+        module: String,
+        paused: bool,
+    },
+}
+
+impl GovernanceEvent {
+    /// The event as indexed and published: type `gov_` plus the variant name in snake case
+    /// (`gov_proposal_passed`), with the variant's fields as attributes in name order. `id`
+    /// is renamed `proposal_id` so every event about a proposal has the same attribute.
+    pub fn to_event(&self) -> Event {
+        let value = serde_json::to_value(self).unwrap_or_default();
+        let Some((variant, fields)) = value.as_object().and_then(|o| o.iter().next()) else {
+            return Event::new("gov_event");
+        };
+        let mut topic = String::from("gov");
+        for c in variant.chars() {
+            if c.is_ascii_uppercase() {
+                topic.push('_');
+            }
+            topic.push(c.to_ascii_lowercase());
+        }
+        let mut event = Event::new(&topic);
+        let mut fields: Vec<_> = fields.as_object().into_iter().flatten().collect();
+        fields.sort_by_key(|(key, _)| *key);
+        for (key, value) in fields {
+            let key = match key.as_str() {
+                "id" => "proposal_id",
+                other => other,
+            };
+            match value {
+                serde_json::Value::Null => {}
+                serde_json::Value::String(s) => event = event.attr(key, s),
+                other => event = event.attr(key, other),
+            }
+        }
+        event
+    }
+}
+
+/// Proposals, votes, deposits and voting power snapshots. Block execution builds it over
+/// the transaction's state overlay, so a failed transaction leaves none of its writes.
+pub struct GovernanceStore<K = Arc<Storage>> {
+    kv: K,
+}
+
+// Key layout:
+//   gov:last_proposal_id                        -> id of the last submitted proposal
+//   gov:proposal:{id}                           -> Proposal
+//   gov:vote:{id}:{voter}                       -> Vote
+//   gov:deposit:{id}:{depositor}                -> Deposit (accumulated)
+//   gov:snapshot:{id}:{address}                 -> SnapshotEntry at voting start
+//   gov:snapshot_validator:{id}:{validator}     -> stake delegated to the validator
+//   gov:snapshot_total:{id}                     -> total voting power in the snapshot
+impl GovernanceStore {
+    pub fn new(storage: Arc<Storage>) -> Self {
+        Self { kv: storage }
+    }
+}
+
+impl<K: KvStore> GovernanceStore<K> {
+    pub fn with_store(kv: K) -> Self {
+        Self { kv }
+    }
+
+    pub fn last_proposal_id(&self) -> u64 {
+        self.kv
+            .kv_get("gov:last_proposal_id")
+            .and_then(|b| bincode::deserialize::<u64>(&b).ok())
+            .unwrap_or(0)
+    }
+
+    /// Reserve the next proposal id
+    pub fn next_proposal_id(&mut self) -> Result<u64, String> {
+        let next_id = self.last_proposal_id() + 1;
+        self.kv
+            .kv_put(
+                "gov:last_proposal_id",
+                bincode::serialize(&next_id).unwrap(),
+            )
+            .map_err(|e| format!("Failed to store proposal id: {e}"))?;
+        Ok(next_id)
+    }
+
+    pub fn proposal(&self, proposal_id: u64) -> Result<Option<Proposal>, String> {
+        match self.kv.kv_get(&format!("gov:proposal:{proposal_id}")) {
+            Some(data) => Proposal::decode(&data)
+                .map(Some)
+                .ok_or_else(|| "Failed to deserialize proposal".to_string()),
+            None => Ok(None),
+        }
+    }
+
+    pub fn put_proposal(&mut self, proposal: &Proposal) -> Result<(), String> {
+        let data = bincode::serialize(proposal)
+            .map_err(|e| format!("Failed to serialize proposal: {e}"))?;
+        self.kv
+            .kv_put(&format!("gov:proposal:{}", proposal.id), data)
+            .map_err(|e| format!("Failed to store proposal: {e}"))
+    }
+
+    pub fn has_voted(&self, proposal_id: u64, voter: &str) -> bool {
+        self.kv
+            .kv_get(&format!("gov:vote:{proposal_id}:{voter}"))
+            .is_some()
+    }
+
+    pub fn put_vote(&mut self, vote: &Vote) -> Result<(), String> {
+        let data =
+            bincode::serialize(vote).map_err(|e| format!("Failed to serialize vote: {e}"))?;
+        self.kv
+            .kv_put(
+                &format!("gov:vote:{}:{}", vote.proposal_id, vote.voter),
+                data,
+            )
+            .map_err(|e| format!("Failed to store vote: {e}"))
+    }
+
+    pub fn votes(&self, proposal_id: u64) -> Vec<Vote> {
+        self.kv
+            .kv_scan_prefix(&format!("gov:vote:{proposal_id}:"))
+            .into_iter()
+            .filter_map(|(_, data)| Vote::decode(&data))
+            .collect()
+    }
+
+    pub fn deposit(&self, proposal_id: u64, depositor: &str) -> Result<Option<Deposit>, String> {
+        match self
+            .kv
+            .kv_get(&format!("gov:deposit:{proposal_id}:{depositor}"))
+        {
+            Some(data) => bincode::deserialize::<Deposit>(&data)
+                .map(Some)
+                .map_err(|e| format!("Failed to deserialize deposit: {e}")),
+            None => Ok(None),
+        }
+    }
+
+    pub fn put_deposit(&mut self, deposit: &Deposit) -> Result<(), String> {
+        let key = format!("gov:deposit:{}:{}", deposit.proposal_id, deposit.depositor);
+        let data =
+            bincode::serialize(deposit).map_err(|e| format!("Failed to serialize deposit: {e}"))?;
+        self.kv
+            .kv_put(&key, data)
+            .map_err(|e| format!("Failed to store deposit: {e}"))
+    }
+
+    pub fn deposits(&self, proposal_id: u64) -> Vec<Deposit> {
+        self.kv
+            .kv_scan_prefix(&format!("gov:deposit:{proposal_id}:"))
+            .into_iter()
+            .filter_map(|(_, data)| bincode::deserialize::<Deposit>(&data).ok())
+            .collect()
+    }
+
+    pub fn snapshot_entry(&self, proposal_id: u64, address: &str) -> Option<SnapshotEntry> {
+        self.kv
+            .kv_get(&format!("gov:snapshot:{proposal_id}:{address}"))
+            .and_then(|b| bincode::deserialize::<SnapshotEntry>(&b).ok())
+    }
+
+    /// Stake delegated to each validator in the snapshot of `proposal_id`
+    pub fn snapshot_validators(&self, proposal_id: u64) -> BTreeMap<String, u128> {
+        let prefix = format!("gov:snapshot_validator:{proposal_id}:");
+        self.kv
+            .kv_scan_prefix(&prefix)
+            .into_iter()
+            .filter_map(|(k, v)| {
+                let amount = bincode::deserialize::<u128>(&v).ok()?;
+                Some((k.trim_start_matches(&prefix).to_string(), amount))
+            })
+            .collect()
+    }
+
+    /// Total voting power in the snapshot of `proposal_id`, if it has been taken
+    pub fn snapshot_total_power(&self, proposal_id: u64) -> Option<u128> {
+        self.kv
+            .kv_get(&format!("gov:snapshot_total:{proposal_id}"))
+            .and_then(|b| bincode::deserialize::<u128>(&b).ok())
+    }
+
+    /// Store the snapshot of `proposal_id`: each account's entry, the stake delegated to
+    /// each validator and the total voting power
+    pub fn put_snapshot(
+        &mut self,
+        proposal_id: u64,
+        entries: &BTreeMap<String, SnapshotEntry>,
+        validators: &BTreeMap<String, u128>,
+        total: u128,
+    ) -> Result<(), String> {
+        for (address, entry) in entries {
+            let key = format!("gov:snapshot:{proposal_id}:{address}");
+            self.put_snapshot_value(&key, bincode::serialize(entry).unwrap())?;
+        }
+        for (validator, amount) in validators {
+            let key = format!("gov:snapshot_validator:{proposal_id}:{validator}");
+            self.put_snapshot_value(&key, bincode::serialize(amount).unwrap())?;
+        }
+        let key = format!("gov:snapshot_total:{proposal_id}");
+        self.put_snapshot_value(&key, bincode::serialize(&total).unwrap())
+    }
+
+    /// Tally votes with validator vote inheritance: stake delegated to a validator that
+    /// votes counts with that vote, except for delegators who voted themselves. Returns the
+    /// tally and how each validator's delegated power counted.
+    pub fn tally_with_breakdown(
+        &self,
+        proposal_id: u64,
+    ) -> Result<(TallyResult, Vec<ValidatorTally>), String> {
+        let votes = self.votes(proposal_id);
+        let mut validators: BTreeMap<String, ValidatorTally> = self
+            .snapshot_validators(proposal_id)
+            .into_iter()
+            .map(|(validator, delegated_power)| {
+                let tally = ValidatorTally {
+                    validator: validator.clone(),
+                    delegated_power,
+                    overridden_power: 0,
+                    inherited_power: 0,
+                    vote: Vec::new(),
+                };
+                (validator, tally)
+            })
+            .collect();
+
+        // Delegators who voted override their validators' votes for their stake
+        for vote in &votes {
+            let delegations = self
+                .snapshot_entry(proposal_id, &vote.voter)
+                .map(|e| e.delegations)
+                .unwrap_or_default();
+            for (validator, amount) in delegations {
+                if let Some(v) = validators.get_mut(&validator) {
+                    v.overridden_power = v.overridden_power.saturating_add(amount);
+                }
+            }
+        }
+
+        let mut tally = TallyResult {
+            yes: 0,
+            no: 0,
+            no_with_veto: 0,
+            abstain: 0,
+            total_voting_power: 0,
+        };
+        for vote in &votes {
+            let mut weight = vote.weight;
+            if let Some(v) = validators.get_mut(&vote.voter) {
+                v.inherited_power = v.delegated_power.saturating_sub(v.overridden_power);
+                v.vote = vote.options.clone();
+                weight = weight.saturating_add(v.inherited_power);
+            }
+            // Split votes count each option's share, rounded down
+            for o in &vote.options {
+                let share = weighted_share(weight, o.weight);
+                match o.option {
+                    VoteOption::Yes => tally.yes += share,
+                    VoteOption::No => tally.no += share,
+                    VoteOption::NoWithVeto => tally.no_with_veto += share,
+                    VoteOption::Abstain => tally.abstain += share,
+                }
+            }
+        }
+        tally.total_voting_power = tally.yes + tally.no + tally.no_with_veto + tally.abstain;
+
+        Ok((tally, validators.into_values().collect()))
+    }
+
+    /// Stake delegated to validators that voted yes on an emergency proposal, and all
+    /// stake delegated to validators, in its snapshot
+    pub fn emergency_tally(&self, proposal_id: u64) -> Result<(u128, u128), String> {
+        let validators = self.snapshot_validators(proposal_id);
+        let total = validators
+            .values()
+            .fold(0u128, |acc, power| acc.saturating_add(*power));
+        let mut yes = 0u128;
+        for vote in self.votes(proposal_id) {
+            let Some(power) = validators.get(&vote.voter) else {
+                continue;
+            };
+            for o in vote.options.iter().filter(|o| o.option == VoteOption::Yes) {
+                yes = yes.saturating_add(weighted_share(*power, o.weight));
+            }
+        }
+        Ok((yes, total))
+    }
+
+    /// Voting power quorum is measured against (non-zero to avoid division by zero)
+    pub fn quorum_base(&self, proposal_id: u64) -> u128 {
+        self.snapshot_total_power(proposal_id).unwrap_or(0).max(1)
+    }
+
+    fn put_snapshot_value(&mut self, key: &str, value: Vec<u8>) -> Result<(), String> {
+        self.kv
+            .kv_put(key, value)
+            .map_err(|e| format!("Failed to store voting power snapshot: {e}"))
+    }
 }
 
 pub struct GovernanceModule {
@@ -153,6 +781,8 @@ pub struct GovernanceModule {
     staking: Arc<Mutex<StakingModule>>,
     config: GovernanceConfig,
     events: Vec<GovernanceEvent>,
+    /// False for dry-run forks, whose events are not written to the evidence log
+    write_evidence: bool,
 }
 
 impl GovernanceModule {
@@ -168,6 +798,7 @@ impl GovernanceModule {
             staking,
             config,
             events: Vec::new(),
+            write_evidence: true,
         }
     }
 
@@ -183,6 +814,20 @@ impl GovernanceModule {
             staking,
             config,
             events: Vec::new(),
+            write_evidence: true,
+        }
+    }
+
+    /// Copy for dry runs: proposals, votes and deposits go through the state it is given,
+    /// and the events it records stay in the copy
+    pub fn fork(&self) -> Self {
+        Self {
+            storage: self.storage.clone(),
+            state: self.state.clone(),
+            staking: self.staking.clone(),
+            config: self.config.clone(),
+            events: Vec::new(),
+            write_evidence: false,
         }
     }
 
@@ -194,7 +839,71 @@ impl GovernanceModule {
         description: String,
         proposal_type: ProposalType,
     ) -> Result<u64, String> {
-        let proposal_id = self.next_proposal_id();
+        self.submit_proposal_on_track(
+            height,
+            title,
+            description,
+            proposal_type,
+            ProposalTrack::Standard,
+        )
+    }
+
+    /// Submit a new proposal voted on along `track`
+    pub fn submit_proposal_on_track(
+        &mut self,
+        height: u64,
+        title: String,
+        description: String,
+        proposal_type: ProposalType,
+        track: ProposalTrack,
+    ) -> Result<u64, String> {
+        let state = self.state.clone();
+        let mut state = state.lock().unwrap();
+        let mut events = Vec::new();
+        let proposal_id = self.submit_proposal_in_block(
+            &mut state,
+            height,
+            title,
+            description,
+            proposal_type,
+            track,
+            &mut events,
+        )?;
+        self.record_events(events);
+        Ok(proposal_id)
+    }
+
+    /// Submit against a state the caller already holds (block execution path). Events go
+    /// to `events`; the caller records them once the transaction commits.
+    #[allow(clippy::too_many_arguments)]
+    pub fn submit_proposal_in_block(
+        &self,
+        state: &mut State,
+        height: u64,
+        title: String,
+        description: String,
+        proposal_type: ProposalType,
+        track: ProposalTrack,
+        events: &mut Vec<GovernanceEvent>,
+    ) -> Result<u64, String> {
+        let emergency_action = matches!(
+            proposal_type,
+            ProposalType::HaltBridge { .. } | ProposalType::PauseModule { .. }
+        );
+        if track == ProposalTrack::Emergency && !emergency_action {
+            return Err(
+                "emergency proposals may only halt the bridge or pause modules".to_string(),
+            );
+        }
+        // An expedited proposal that does not pass runs for the full standard period
+        let longest_voting_period = match track {
+            ProposalTrack::Emergency => self.config.emergency_voting_period,
+            _ => self.config.voting_period,
+        };
+        let latest_voting_end = height + self.config.deposit_period + longest_voting_period;
+        self.validate_proposal_type(state, &proposal_type, latest_voting_end)?;
+        let mut store = GovernanceStore::with_store(&mut *state);
+        let proposal_id = store.next_proposal_id()?;
 
         let proposal = Proposal {
             id: proposal_id,
@@ -206,16 +915,89 @@ impl GovernanceModule {
             submit_height: height,
             deposit_end_height: height + self.config.deposit_period,
             voting_start_height: height + self.config.deposit_period,
-            voting_end_height: height + self.config.deposit_period + self.config.voting_period,
+            voting_end_height: height + self.config.deposit_period + self.voting_period(track),
             tally: None,
+            track,
         };
 
-        self.store_proposal(&proposal)?;
-        self.emit_event(GovernanceEvent::ProposalSubmitted { id: proposal_id });
-        let _ = self.write_governance_evidence();
+        store.put_proposal(&proposal)?;
+        events.push(GovernanceEvent::ProposalSubmitted { id: proposal_id });
         Ok(proposal_id)
     }
 
+    /// Reject proposals that could never execute
+    fn validate_proposal_type(
+        &self,
+        state: &mut State,
+        proposal_type: &ProposalType,
+        voting_end_height: u64,
+    ) -> Result<(), String> {
+        match proposal_type {
+            ProposalType::ParameterChange { key, value } => params::spec(key)?.check(value)?,
+            ProposalType::Text => {}
+            ProposalType::CommunitySpend {
+                recipient,
+                amount,
+                denom,
+            } => {
+                if recipient.is_empty() || denom.is_empty() {
+                    return Err("community spend needs a recipient and a denom".to_string());
+                }
+                if *amount == 0 {
+                    return Err("community spend amount must be positive".to_string());
+                }
+            }
+            ProposalType::SoftwareUpgrade { name, height, .. } => {
+                if name.is_empty() {
+                    return Err("software upgrade needs a name".to_string());
+                }
+                if UpgradeModule::with_store(&mut *state)
+                    .applied_height(name)
+                    .is_some()
+                {
+                    return Err(format!("upgrade {name} has already been applied"));
+                }
+                // The voting period may end later than this if deposits are slow; execution
+                // checks the height again
+                if *height <= voting_end_height {
+                    return Err(format!(
+                        "upgrade height {height} must be after the voting period ends ({voting_end_height})"
+                    ));
+                }
+            }
+            ProposalType::HaltBridge { .. } => {}
+            ProposalType::PauseModule { module, .. } => pause::check_pausable(module)?,
+        }
+        Ok(())
+    }
+
+    /// Deposit `track` needs before voting starts
+    fn min_deposit(&self, track: ProposalTrack) -> u128 {
+        match track {
+            ProposalTrack::Standard => self.config.min_deposit,
+            ProposalTrack::Expedited | ProposalTrack::Emergency => {
+                self.config.expedited_min_deposit
+            }
+        }
+    }
+
+    /// Blocks a proposal on `track` is voted on
+    fn voting_period(&self, track: ProposalTrack) -> u64 {
+        match track {
+            ProposalTrack::Standard => self.config.voting_period,
+            ProposalTrack::Expedited => self.config.expedited_voting_period,
+            ProposalTrack::Emergency => self.config.emergency_voting_period,
+        }
+    }
+
+    /// Yes share (basis points of non-abstaining votes) a proposal on `track` needs
+    fn threshold(&self, track: ProposalTrack) -> u128 {
+        match track {
+            ProposalTrack::Expedited => self.config.expedited_threshold,
+            _ => self.config.threshold,
+        }
+    }
+
     /// Deposit DGT tokens on a proposal
     pub fn deposit(
         &mut self,
@@ -224,13 +1006,44 @@ impl GovernanceModule {
         proposal_id: u64,
         amount: u128,
         denom: &str,
+    ) -> Result<(), String> {
+        let state = self.state.clone();
+        let mut state = state.lock().unwrap();
+        let mut events = Vec::new();
+        self.deposit_in_block(
+            &mut state,
+            height,
+            depositor,
+            proposal_id,
+            amount,
+            denom,
+            &mut events,
+        )?;
+        self.record_events(events);
+        Ok(())
+    }
+
+    /// Deposit against a state the caller already holds (block execution path)
+    #[allow(clippy::too_many_arguments)]
+    pub fn deposit_in_block(
+        &self,
+        state: &mut State,
+        height: u64,
+        depositor: &str,
+        proposal_id: u64,
+        amount: u128,
+        denom: &str,
+        events: &mut Vec<GovernanceEvent>,
     ) -> Result<(), String> {
         if denom != "udgt" {
             return Err("Only DGT (udgt) deposits are allowed".to_string());
         }
+        if amount == 0 {
+            return Err("Deposit amount cannot be zero".to_string());
+        }
 
-        let mut proposal = self
-            ._get_proposal(proposal_id)?
+        let mut proposal = GovernanceStore::with_store(&mut *state)
+            .proposal(proposal_id)?
             .ok_or("Proposal not found")?;
 
         // Check if we're in deposit period
@@ -240,43 +1053,48 @@ impl GovernanceModule {
         }
 
         // Deduct from depositor's balance
-        {
-            let mut state = self.state.lock().unwrap();
-            let mut account = state.get_account(depositor);
-            account.sub_balance(denom, amount)?;
-            state
-                .accounts
-                .insert(depositor.to_string(), account.clone());
-            let _ = state.storage.set_balances_db(depositor, &account.balances);
+        let available = state.balance_of(depositor, denom);
+        if available < amount {
+            return Err(format!(
+                "Insufficient balance in {denom}: {available} < {amount}"
+            ));
         }
+        send_to_module(state, depositor, GOV_DEPOSITS, denom, amount)?;
 
-        // Store individual deposit for refund/burn tracking
+        // Store individual deposit for refund/burn tracking; repeated deposits accumulate
+        let mut store = GovernanceStore::with_store(&mut *state);
+        let previous = store
+            .deposit(proposal_id, depositor)?
+            .map(|d| d.amount)
+            .unwrap_or(0);
         let deposit = Deposit {
             proposal_id,
             depositor: depositor.to_string(),
-            amount,
+            amount: previous.saturating_add(amount),
             denom: denom.to_string(),
         };
-        self.store_deposit(&deposit)?;
+        store.put_deposit(&deposit)?;
 
         // Update proposal deposit
         proposal.total_deposit += amount;
 
         // Check if min deposit reached - transition to voting period
-        if proposal.total_deposit >= self.config.min_deposit {
+        let voting_started = proposal.total_deposit >= self.min_deposit(proposal.track);
+        if voting_started {
             proposal.status = ProposalStatus::VotingPeriod;
             // When transitioning early, start voting immediately and set end relative to now
             proposal.voting_start_height = height;
-            proposal.voting_end_height = height + self.config.voting_period;
-            self.emit_event(GovernanceEvent::VotingStarted { id: proposal_id });
+            proposal.voting_end_height = height + self.voting_period(proposal.track);
         }
-
-        self.store_proposal(&proposal)?;
-        self.emit_event(GovernanceEvent::Deposit {
+        store.put_proposal(&proposal)?;
+        if voting_started {
+            self.snapshot_voting_power(state, proposal_id)?;
+            events.push(GovernanceEvent::VotingStarted { id: proposal_id });
+        }
+        events.push(GovernanceEvent::Deposit {
             id: proposal_id,
             amount,
         });
-        let _ = self.write_governance_evidence();
         Ok(())
     }
 
@@ -288,162 +1106,357 @@ impl GovernanceModule {
         proposal_id: u64,
         option: VoteOption,
     ) -> Result<(), String> {
-        let proposal = self
-            ._get_proposal(proposal_id)?
-            .ok_or("Proposal not found")?;
+        let options = vec![WeightedVoteOption {
+            option,
+            weight: WEIGHT_ONE,
+        }];
+        self.weighted_vote(height, voter, proposal_id, options)
+    }
 
-        // Check if we're in voting period
-        if proposal.status != ProposalStatus::VotingPeriod {
-            return Err("Proposal is not in voting period".to_string());
-        }
+    /// Split vote: each option gets its share of the voter's power
+    pub fn weighted_vote(
+        &mut self,
+        height: u64,
+        voter: &str,
+        proposal_id: u64,
+        options: Vec<WeightedVoteOption>,
+    ) -> Result<(), String> {
+        let state = self.state.clone();
+        let mut state = state.lock().unwrap();
+        let mut events = Vec::new();
+        self.weighted_vote_in_block(&mut state, height, voter, proposal_id, options, &mut events)?;
+        self.record_events(events);
+        Ok(())
+    }
+
+    /// Vote against a state the caller already holds (block execution path)
+    pub fn vote_in_block(
+        &self,
+        state: &mut State,
+        height: u64,
+        voter: &str,
+        proposal_id: u64,
+        option: VoteOption,
+        events: &mut Vec<GovernanceEvent>,
+    ) -> Result<(), String> {
+        let options = vec![WeightedVoteOption {
+            option,
+            weight: WEIGHT_ONE,
+        }];
+        self.weighted_vote_in_block(state, height, voter, proposal_id, options, events)
+    }
+
+    /// Split vote against a state the caller already holds (block execution path)
+    pub fn weighted_vote_in_block(
+        &self,
+        state: &mut State,
+        height: u64,
+        voter: &str,
+        proposal_id: u64,
+        options: Vec<WeightedVoteOption>,
+        events: &mut Vec<GovernanceEvent>,
+    ) -> Result<(), String> {
+        if options.is_empty() {
+            return Err("Vote needs at least one option".to_string());
+        }
+        if options.iter().any(|o| o.weight == 0) {
+            return Err("Vote option weights must be positive".to_string());
+        }
+        for (i, o) in options.iter().enumerate() {
+            if options[..i].iter().any(|prev| prev.option == o.option) {
+                return Err(format!("Vote option {:?} appears more than once", o.option));
+            }
+        }
+        let total = options
+            .iter()
+            .try_fold(0u128, |acc, o| acc.checked_add(o.weight))
+            .unwrap_or(u128::MAX);
+        if total != WEIGHT_ONE {
+            return Err(format!(
+                "Vote option weights must sum to 1, got {}",
+                format_weight(total)
+            ));
+        }
+
+        let proposal = GovernanceStore::with_store(&mut *state)
+            .proposal(proposal_id)?
+            .ok_or("Proposal not found")?;
+
+        // Check if we're in voting period
+        if proposal.status != ProposalStatus::VotingPeriod {
+            return Err("Proposal is not in voting period".to_string());
+        }
 
         if height < proposal.voting_start_height || height > proposal.voting_end_height {
             return Err("Not in voting period".to_string());
         }
 
         // Check if voter already voted
-        if self.has_voted(proposal_id, voter)? {
+        if GovernanceStore::with_store(&mut *state).has_voted(proposal_id, voter) {
             return Err("Voter has already voted on this proposal".to_string());
         }
 
-        // Get voter's voting power from staking (delegations + validator self-stake)
-        let weight = self.voting_power(voter)?;
+        // Votes weigh what the voter held when voting started. Proposals that entered
+        // voting before snapshots existed get theirs on the first vote.
+        if GovernanceStore::with_store(&mut *state)
+            .snapshot_total_power(proposal_id)
+            .is_none()
+        {
+            self.snapshot_voting_power(state, proposal_id)?;
+        }
+        let mut store = GovernanceStore::with_store(&mut *state);
+        let weight = store
+            .snapshot_entry(proposal_id, voter)
+            .map(|e| e.power)
+            .unwrap_or(0);
+        let is_validator = store.snapshot_validators(proposal_id).contains_key(voter);
+        if proposal.track == ProposalTrack::Emergency && !is_validator {
+            return Err(format!(
+                "only validators vote on emergency proposal {proposal_id}"
+            ));
+        }
+        if weight == 0 && !is_validator {
+            return Err(format!(
+                "{voter} had no voting power when proposal {proposal_id} entered voting"
+            ));
+        }
 
+        // Highest share wins ties by order of submission
+        let option = options
+            .iter()
+            .fold(
+                &options[0],
+                |best, o| if o.weight > best.weight { o } else { best },
+            )
+            .option
+            .clone();
         let vote = Vote {
             proposal_id,
             voter: voter.to_string(),
             option,
             weight,
+            options,
         };
 
-        self.store_vote(&vote)?;
-        self.emit_event(GovernanceEvent::VoteCast {
+        store.put_vote(&vote)?;
+        events.push(GovernanceEvent::VoteCast {
             id: proposal_id,
             voter: voter.to_string(),
         });
-        let _ = self.write_governance_evidence();
         Ok(())
     }
 
-    /// Process end of block - handle period transitions and execution
+    /// Process end of block - handle period transitions and execution. Each proposal is
+    /// processed in its own state overlay, committed only if processing succeeds.
     pub fn end_block(&mut self, height: u64) -> Result<(), String> {
         let proposal_ids = self.get_all_proposal_ids()?;
+        let state = self.state.clone();
+        let mut state = state.lock().unwrap();
 
         for proposal_id in proposal_ids {
-            if let Some(mut proposal) = self._get_proposal(proposal_id)? {
-                match proposal.status {
-                    ProposalStatus::DepositPeriod => {
-                        // Updated: if min deposit has been reached (at any time), transition to voting now.
-                        // Deposits after deposit_end_height are rejected in `deposit`, so this is safe and fixes timing races.
-                        if proposal.total_deposit >= self.config.min_deposit {
-                            proposal.status = ProposalStatus::VotingPeriod;
-                            proposal.voting_start_height = height;
-                            proposal.voting_end_height = height + self.config.voting_period;
-                            self.store_proposal(&proposal)?;
-                            self.emit_event(GovernanceEvent::VotingStarted { id: proposal_id });
-                            let _ = self.write_governance_evidence();
-                            continue;
-                        }
-
-                        if height > proposal.deposit_end_height {
-                            // Deposit period ended without reaching min deposit
-                            proposal.status = ProposalStatus::Failed;
-                            self.store_proposal(&proposal)?;
-                            // Burn deposits for failed proposals (insufficient deposits)
-                            self.burn_deposits(proposal_id)?;
-                            self.emit_event(GovernanceEvent::ProposalRejected {
-                                id: proposal_id,
-                                reason: Some("Insufficient deposits - proposal failed".to_string()),
-                            });
-                            let _ = self.write_governance_evidence();
-                        }
-                    }
-                    ProposalStatus::VotingPeriod => {
-                        if height > proposal.voting_end_height {
-                            // Voting period ended - tally votes
-                            let tally = self.tally(proposal_id)?;
-                            proposal.tally = Some(tally.clone());
-
-                            // Use enhanced tally logic to determine if proposal passes
-                            if self.proposal_passes(&tally)? {
-                                proposal.status = ProposalStatus::Passed;
-                                self.emit_event(GovernanceEvent::ProposalPassed {
-                                    id: proposal_id,
-                                    yes: tally.yes,
-                                    no: tally.no,
-                                    abstain: tally.abstain,
-                                });
-                                let _ = self.write_governance_evidence();
-                            } else {
-                                proposal.status = ProposalStatus::Rejected;
-                                // Burn deposits for rejected proposals
-                                self.burn_deposits(proposal_id)?;
-                                let reason = if tally.total_voting_power
-                                    < (self.get_total_staking_power()? * self.config.quorum) / 10000
-                                {
-                                    "Quorum not met"
-                                } else if tally.no_with_veto
-                                    >= (tally.total_voting_power * self.config.veto_threshold)
-                                        / 10000
-                                {
-                                    "Proposal vetoed"
-                                } else {
-                                    "Threshold not met"
-                                };
-                                self.emit_event(GovernanceEvent::ProposalRejected {
-                                    id: proposal_id,
-                                    reason: Some(reason.to_string()),
-                                });
-                                let _ = self.write_governance_evidence();
-                            }
-                            self.store_proposal(&proposal)?;
-                        }
-                    }
-                    ProposalStatus::Passed => {
-                        // Execute once; execute() handles status updates, refunds, and events.
-                        let _ = self.execute(proposal_id);
-                        // Do not mutate proposal here to avoid double writes or loops.
-                    }
-                    _ => {}
-                }
+            let config = self.config.clone();
+            let mut overlay = StateOverlay::new(&state);
+            let mut events = Vec::new();
+            if let Err(e) = self.end_block_proposal(&mut overlay, height, proposal_id, &mut events)
+            {
+                // Parameter changes applied to the in-memory config are discarded too
+                self.config = config;
+                return Err(e);
             }
+            overlay.commit(&mut state);
+            self.record_events(events);
         }
         Ok(())
     }
 
-    /// Tally votes for a proposal
-    pub fn tally(&self, proposal_id: u64) -> Result<TallyResult, String> {
-        let votes = self._get_proposal_votes(proposal_id)?;
+    fn end_block_proposal(
+        &mut self,
+        state: &mut State,
+        height: u64,
+        proposal_id: u64,
+        events: &mut Vec<GovernanceEvent>,
+    ) -> Result<(), String> {
+        let Some(mut proposal) = GovernanceStore::with_store(&mut *state).proposal(proposal_id)?
+        else {
+            return Ok(());
+        };
+        match proposal.status {
+            ProposalStatus::DepositPeriod => {
+                // Updated: if min deposit has been reached (at any time), transition to voting now.
+                // Deposits after deposit_end_height are rejected in `deposit`, so this is safe and fixes timing races.
+                if proposal.total_deposit >= self.min_deposit(proposal.track) {
+                    proposal.status = ProposalStatus::VotingPeriod;
+                    proposal.voting_start_height = height;
+                    proposal.voting_end_height = height + self.voting_period(proposal.track);
+                    GovernanceStore::with_store(&mut *state).put_proposal(&proposal)?;
+                    self.snapshot_voting_power(state, proposal_id)?;
+                    events.push(GovernanceEvent::VotingStarted { id: proposal_id });
+                    return Ok(());
+                }
 
-        let mut yes = 0u128;
-        let mut no = 0u128;
-        let mut no_with_veto = 0u128;
-        let mut abstain = 0u128;
-
-        for vote in votes {
-            match vote.option {
-                VoteOption::Yes => yes += vote.weight,
-                VoteOption::No => no += vote.weight,
-                VoteOption::NoWithVeto => no_with_veto += vote.weight,
-                VoteOption::Abstain => abstain += vote.weight,
+                if height > proposal.deposit_end_height {
+                    // Deposit period ended without reaching min deposit
+                    proposal.status = ProposalStatus::Failed;
+                    GovernanceStore::with_store(&mut *state).put_proposal(&proposal)?;
+                    // Burn deposits for failed proposals (insufficient deposits)
+                    self.burn_deposits(state, proposal_id, events)?;
+                    events.push(GovernanceEvent::ProposalRejected {
+                        id: proposal_id,
+                        reason: Some("Insufficient deposits - proposal failed".to_string()),
+                    });
+                }
+            }
+            ProposalStatus::VotingPeriod if proposal.track == ProposalTrack::Emergency => {
+                self.end_block_emergency(state, proposal, height, events)?;
+            }
+            ProposalStatus::VotingPeriod if height > proposal.voting_end_height => {
+                // Voting period ended - tally votes
+                let store = GovernanceStore::with_store(&mut *state);
+                let (tally, _) = store.tally_with_breakdown(proposal_id)?;
+                proposal.tally = Some(tally.clone());
+
+                // Use enhanced tally logic to determine if proposal passes
+                if self.proposal_passes_in(&store, proposal_id, &tally)? {
+                    proposal.status = ProposalStatus::Passed;
+                    events.push(GovernanceEvent::ProposalPassed {
+                        id: proposal_id,
+                        yes: tally.yes,
+                        no: tally.no,
+                        abstain: tally.abstain,
+                    });
+                } else if proposal.track == ProposalTrack::Expedited {
+                    // Votes carry over; voting continues for the standard period
+                    proposal.track = ProposalTrack::Standard;
+                    proposal.voting_end_height =
+                        proposal.voting_start_height + self.config.voting_period;
+                    events.push(GovernanceEvent::ProposalConverted {
+                        id: proposal_id,
+                        voting_end_height: proposal.voting_end_height,
+                    });
+                } else {
+                    proposal.status = ProposalStatus::Rejected;
+                    let reason = if tally.total_voting_power
+                        < (store.quorum_base(proposal_id) * self.config.quorum) / 10000
+                    {
+                        "Quorum not met"
+                    } else if tally.no_with_veto
+                        >= (tally.total_voting_power * self.config.veto_threshold) / 10000
+                    {
+                        "Proposal vetoed"
+                    } else {
+                        "Threshold not met"
+                    };
+                    // Burn deposits for rejected proposals
+                    self.burn_deposits(state, proposal_id, events)?;
+                    events.push(GovernanceEvent::ProposalRejected {
+                        id: proposal_id,
+                        reason: Some(reason.to_string()),
+                    });
+                }
+                GovernanceStore::with_store(&mut *state).put_proposal(&proposal)?;
             }
+            ProposalStatus::Passed => {
+                // Execute once; execute_in() handles status updates, refunds, and events.
+                let _ = self.execute_in(state, proposal_id, events);
+                // Do not mutate proposal here to avoid double writes or loops.
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// An emergency proposal passes and executes as soon as enough validators vote yes, and
+    /// is rejected if its voting period ends first
+    fn end_block_emergency(
+        &mut self,
+        state: &mut State,
+        mut proposal: Proposal,
+        height: u64,
+        events: &mut Vec<GovernanceEvent>,
+    ) -> Result<(), String> {
+        let store = GovernanceStore::with_store(&mut *state);
+        let passes = self.emergency_passes_in(&store, proposal.id)?;
+        if !passes && height <= proposal.voting_end_height {
+            return Ok(());
         }
+        let (tally, _) = store.tally_with_breakdown(proposal.id)?;
+        proposal.tally = Some(tally.clone());
+        if passes {
+            proposal.status = ProposalStatus::Passed;
+            GovernanceStore::with_store(&mut *state).put_proposal(&proposal)?;
+            events.push(GovernanceEvent::ProposalPassed {
+                id: proposal.id,
+                yes: tally.yes,
+                no: tally.no,
+                abstain: tally.abstain,
+            });
+            let _ = self.execute_in(state, proposal.id, events);
+        } else {
+            proposal.status = ProposalStatus::Rejected;
+            GovernanceStore::with_store(&mut *state).put_proposal(&proposal)?;
+            self.burn_deposits(state, proposal.id, events)?;
+            events.push(GovernanceEvent::ProposalRejected {
+                id: proposal.id,
+                reason: Some("Validator supermajority not reached".to_string()),
+            });
+        }
+        Ok(())
+    }
 
-        let total_voting_power = yes + no + no_with_veto + abstain;
+    /// Stake delegated to validators that voted yes on an emergency proposal, and all
+    /// stake delegated to validators, in its snapshot. Only validators' votes count, each
+    /// with its whole delegated stake.
+    pub fn emergency_tally(&self, proposal_id: u64) -> Result<(u128, u128), String> {
+        self.store().emergency_tally(proposal_id)
+    }
 
-        Ok(TallyResult {
-            yes,
-            no,
-            no_with_veto,
-            abstain,
-            total_voting_power,
-        })
+    fn emergency_passes_in<K: KvStore>(
+        &self,
+        store: &GovernanceStore<K>,
+        proposal_id: u64,
+    ) -> Result<bool, String> {
+        let (yes, total) = store.emergency_tally(proposal_id)?;
+        Ok(total > 0
+            && yes.saturating_mul(10000) >= total.saturating_mul(self.config.emergency_threshold))
+    }
+
+    /// Tally votes for a proposal
+    pub fn tally(&self, proposal_id: u64) -> Result<TallyResult, String> {
+        self.tally_with_breakdown(proposal_id)
+            .map(|(tally, _)| tally)
+    }
+
+    /// Tally votes with validator vote inheritance: stake delegated to a validator that
+    /// votes counts with that vote, except for delegators who voted themselves. Returns the
+    /// tally and how each validator's delegated power counted.
+    pub fn tally_with_breakdown(
+        &self,
+        proposal_id: u64,
+    ) -> Result<(TallyResult, Vec<ValidatorTally>), String> {
+        self.store().tally_with_breakdown(proposal_id)
     }
 
     /// Check if a proposal passes based on governance parameters
-    pub fn proposal_passes(&self, tally: &TallyResult) -> Result<bool, String> {
-        // Get total staking power for quorum calculation
-        let total_staking_power = self.get_total_staking_power()?;
+    pub fn proposal_passes(&self, proposal_id: u64, tally: &TallyResult) -> Result<bool, String> {
+        self.proposal_passes_in(&self.store(), proposal_id, tally)
+    }
+
+    fn proposal_passes_in<K: KvStore>(
+        &self,
+        store: &GovernanceStore<K>,
+        proposal_id: u64,
+        tally: &TallyResult,
+    ) -> Result<bool, String> {
+        let track = store
+            .proposal(proposal_id)?
+            .map(|p| p.track)
+            .unwrap_or_default();
+        if track == ProposalTrack::Emergency {
+            return self.emergency_passes_in(store, proposal_id);
+        }
+
+        // Quorum is measured against the voting power snapshot taken at voting start
+        let total_staking_power = store.quorum_base(proposal_id);
 
         // Check quorum: minimum participation required
         let quorum_required = (total_staking_power * self.config.quorum) / 10000; // basis points
@@ -463,33 +1476,109 @@ impl GovernanceModule {
             return Ok(false); // No participating votes
         }
 
-        let threshold_required = (participating_votes * self.config.threshold) / 10000;
+        let threshold_required = (participating_votes * self.threshold(track)) / 10000;
         Ok(tally.yes >= threshold_required)
     }
 
     /// Get voting power for a specific address (derived from delegations and validator self-bond)
     pub fn voting_power(&self, address: &str) -> Result<u128, String> {
         let staking = self.staking.lock().unwrap();
+        let state = self.state.lock().unwrap();
+        Ok(self.voting_power_of(&staking, &state, address))
+    }
 
+    fn voting_power_of(&self, staking: &StakingModule, state: &State, address: &str) -> u128 {
         // Get delegator stake amount
-        let delegator_record = staking.load_delegator_record(address);
-        let total_power = delegator_record.stake_amount;
-        drop(staking);
+        let total_power = staking.load_delegator_record(address).stake_amount;
+        if total_power > 0 || !self.config.liquid_balance_voting {
+            return total_power;
+        }
+
+        // Fallback: use liquid udgt balance when no stake is present
+        state.snapshot_account(address).balance_of("udgt")
+    }
 
-        if total_power > 0 {
-            return Ok(total_power);
+    /// Record every account's voting power as `proposal_id` enters its voting period: bonded
+    /// stake, plus (with `liquid_balance_voting`) the liquid udgt of accounts without stake.
+    /// Votes and quorum use this snapshot, so tokens moved after voting starts add no votes.
+    fn snapshot_voting_power(&self, state: &mut State, proposal_id: u64) -> Result<(), String> {
+        let staking = StakingModule::with_store(&mut *state);
+        let (stakes, delegations) = (staking.delegator_stakes(), staking.delegations());
+        // The electorate is the registered validator set, each with its bonded stake;
+        // delegations to other addresses give them nothing to vote with
+        let validators: BTreeMap<String, u128> = staking.validators().into_iter().collect();
+        let mut entries: BTreeMap<String, SnapshotEntry> = BTreeMap::new();
+        for (address, stake) in stakes {
+            entries.entry(address).or_default().power = stake;
+        }
+        for (delegator, validator, amount) in delegations {
+            entries
+                .entry(delegator)
+                .or_default()
+                .delegations
+                .insert(validator, amount);
+        }
+        if self.config.liquid_balance_voting {
+            // Stored accounts plus those the state has cached; balances are read through
+            // the state so they include this block's earlier transactions
+            let mut addresses: BTreeSet<String> = state
+                .storage
+                .scan_prefix("acct:balances:")
+                .into_iter()
+                .map(|(k, _)| k.trim_start_matches("acct:balances:").to_string())
+                .collect();
+            addresses.extend(state.accounts.keys().cloned());
+            let modules: BTreeSet<String> = module_accounts()
+                .iter()
+                .map(|name| module_address(name))
+                .collect();
+            for address in addresses {
+                if modules.contains(&address) {
+                    continue;
+                }
+                let amount = state.snapshot_account(&address).balance_of("udgt");
+                if amount > 0 {
+                    let entry = entries.entry(address).or_default();
+                    if entry.power == 0 {
+                        entry.power = amount;
+                    }
+                }
+            }
         }
 
-        // Fallback: use liquid udgt balance when no stake is present (MVP behavior)
-        let mut state = self.state.lock().unwrap();
-        let power = state.get_account(address).balance_of("udgt");
-        Ok(power)
+        let total = entries
+            .values()
+            .fold(0u128, |acc, e| acc.saturating_add(e.power));
+        GovernanceStore::with_store(&mut *state).put_snapshot(
+            proposal_id,
+            &entries,
+            &validators,
+            total,
+        )
+    }
+
+    /// Voting power of `address` in the snapshot of `proposal_id`
+    pub fn snapshot_power(&self, proposal_id: u64, address: &str) -> u128 {
+        self.store()
+            .snapshot_entry(proposal_id, address)
+            .map(|e| e.power)
+            .unwrap_or(0)
+    }
+
+    /// Stake delegated to each validator in the snapshot of `proposal_id`
+    pub fn snapshot_validators(&self, proposal_id: u64) -> BTreeMap<String, u128> {
+        self.store().snapshot_validators(proposal_id)
+    }
+
+    /// Total voting power in the snapshot of `proposal_id`, if it has been taken
+    pub fn snapshot_total_power(&self, proposal_id: u64) -> Option<u128> {
+        self.store().snapshot_total_power(proposal_id)
     }
 
     /// Get total voting power across all eligible stakers
     pub fn total_voting_power(&self) -> Result<u128, String> {
         let staking = self.staking.lock().unwrap();
-        Ok(staking.total_stake)
+        Ok(staking.total_stake())
     }
 
     /// Get active set voting power (currently same as total for MVP)
@@ -499,160 +1588,205 @@ impl GovernanceModule {
         self.total_voting_power()
     }
 
-    /// Get total staking power for quorum calculation (updated to use staking module)
-    fn get_total_staking_power(&self) -> Result<u128, String> {
-        // Use the new total_voting_power function
-        let total_power = self.total_voting_power()?;
-
-        // Minimum total power to avoid division by zero
-        Ok(total_power.max(1))
-    }
-
     /// Execute a passed proposal
     pub fn execute(&mut self, proposal_id: u64) -> Result<(), String> {
-        let mut proposal = self
-            ._get_proposal(proposal_id)?
+        let state = self.state.clone();
+        let mut state = state.lock().unwrap();
+        let mut events = Vec::new();
+        let result = self.execute_in(&mut state, proposal_id, &mut events);
+        self.record_events(events);
+        result
+    }
+
+    /// Execute against a state the caller already holds. The proposal's effects are
+    /// applied in an overlay committed only if they all succeed; a failed execution is
+    /// recorded with none of them.
+    fn execute_in(
+        &mut self,
+        state: &mut State,
+        proposal_id: u64,
+        events: &mut Vec<GovernanceEvent>,
+    ) -> Result<(), String> {
+        let mut proposal = GovernanceStore::with_store(&mut *state)
+            .proposal(proposal_id)?
             .ok_or("Proposal not found")?;
 
         if proposal.status != ProposalStatus::Passed {
             return Err("Proposal has not passed".to_string());
         }
 
-        match &proposal.proposal_type {
-            ProposalType::ParameterChange { key, value } => {
-                // Apply change and transition status within this method to avoid double execution
-                match self.apply_parameter_change(key, value) {
-                    Ok(_) => {
-                        // Success: mark executed, persist, refund deposits, emit event
-                        proposal.status = ProposalStatus::Executed;
-                        self.store_proposal(&proposal)?;
-                        // Refund deposits for successfully executed proposals (mirror end_block behavior)
-                        let _ = self.refund_deposits(proposal_id);
-                        self.emit_event(GovernanceEvent::ProposalExecuted { id: proposal_id });
-                        let _ = self.write_governance_evidence();
-                        Ok(())
-                    }
-                    Err(e) => {
-                        // Failure: mark failed execution, persist, refund deposits, emit event
-                        proposal.status = ProposalStatus::FailedExecution;
-                        self.store_proposal(&proposal)?;
-                        // Refund deposits for failed execution (not proposer's fault)
-                        let _ = self.refund_deposits(proposal_id);
-                        self.emit_event(GovernanceEvent::ExecutionFailed {
-                            id: proposal_id,
-                            error: e.clone(),
-                        });
-                        let _ = self.write_governance_evidence();
-                        Err(e)
-                    }
-                }
+        // Apply and transition status within this method to avoid double execution
+        let config = self.config.clone();
+        let mut overlay = StateOverlay::new(state);
+        let mut applied = Vec::new();
+        match self.apply_proposal(
+            &mut overlay,
+            proposal_id,
+            &proposal.proposal_type,
+            &mut applied,
+        ) {
+            Ok(_) => {
+                overlay.commit(state);
+                events.extend(applied);
+                // Success: mark executed, persist, refund deposits, emit event
+                proposal.status = ProposalStatus::Executed;
+                GovernanceStore::with_store(&mut *state).put_proposal(&proposal)?;
+                // Refund deposits for successfully executed proposals (mirror end_block behavior)
+                let _ = self.refund_deposits(state, proposal_id);
+                events.push(GovernanceEvent::ProposalExecuted { id: proposal_id });
+                Ok(())
+            }
+            Err(e) => {
+                // Failure: discard the partial effects, mark failed execution, persist,
+                // refund deposits, emit event
+                drop(overlay);
+                self.config = config;
+                proposal.status = ProposalStatus::FailedExecution;
+                GovernanceStore::with_store(&mut *state).put_proposal(&proposal)?;
+                // Refund deposits for failed execution (not proposer's fault)
+                let _ = self.refund_deposits(state, proposal_id);
+                events.push(GovernanceEvent::ExecutionFailed {
+                    id: proposal_id,
+                    error: e.clone(),
+                });
+                Err(e)
             }
         }
     }
 
-    /// Apply parameter changes with enhanced governance event emission
-    fn apply_parameter_change(&mut self, key: &str, value: &str) -> Result<(), String> {
-        // Validate that the parameter is allowed to be changed
-        let old_value = self.get_parameter_value(key)?;
-
-        match key {
-            "gas_limit" => {
-                let gas_limit: u64 = value
-                    .parse()
-                    .map_err(|_| "Invalid gas_limit value: must be a valid u64".to_string())?;
-
-                // Validation: gas limit should be reasonable (between 1K and 100M)
-                if !(1_000..=100_000_000).contains(&gas_limit) {
-                    return Err("gas_limit must be between 1,000 and 100,000,000".to_string());
-                }
-
-                self.config.gas_limit = gas_limit;
-                self.store_config()?;
-
-                // Emit parameter change event
-                self.emit_event(GovernanceEvent::ParameterChanged {
-                    key: key.to_string(),
-                    old_value,
-                    new_value: value.to_string(),
+    fn apply_proposal(
+        &mut self,
+        state: &mut State,
+        proposal_id: u64,
+        proposal_type: &ProposalType,
+        events: &mut Vec<GovernanceEvent>,
+    ) -> Result<(), String> {
+        match proposal_type {
+            ProposalType::ParameterChange { key, value } => {
+                self.apply_parameter_change(state, key, value, events)
+            }
+            ProposalType::Text => Ok(()),
+            ProposalType::CommunitySpend {
+                recipient,
+                amount,
+                denom,
+            } => {
+                send_from_module(state, COMMUNITY_POOL, recipient, denom, *amount)?;
+                events.push(GovernanceEvent::CommunitySpend {
+                    proposal_id,
+                    recipient: recipient.clone(),
+                    amount: *amount,
+                    denom: denom.clone(),
                 });
-                let _ = self.write_governance_evidence();
                 Ok(())
             }
-            "consensus.max_gas_per_block" => {
-                let max_gas_per_block: u64 = value.parse().map_err(|_| {
-                    "Invalid consensus.max_gas_per_block value: must be a valid u64".to_string()
-                })?;
-
-                // Validation: max gas per block should be reasonable (between 1M and 1B)
-                if !(1_000_000..=1_000_000_000).contains(&max_gas_per_block) {
-                    return Err(
-                        "consensus.max_gas_per_block must be between 1,000,000 and 1,000,000,000"
-                            .to_string(),
-                    );
+            ProposalType::SoftwareUpgrade { name, height, info } => {
+                let current = self.storage.height();
+                if *height <= current {
+                    return Err(format!(
+                        "upgrade height {height} has already been reached (chain height {current})"
+                    ));
                 }
-
-                self.config.max_gas_per_block = max_gas_per_block;
-                self.store_config()?;
-
-                // Emit parameter change event
-                self.emit_event(GovernanceEvent::ParameterChanged {
-                    key: key.to_string(),
-                    old_value,
-                    new_value: value.to_string(),
+                let plan = UpgradePlan {
+                    name: name.clone(),
+                    height: *height,
+                    info: info.clone(),
+                    proposal_id,
+                };
+                UpgradeModule::with_store(&mut *state).schedule(&plan)?;
+                events.push(GovernanceEvent::UpgradeScheduled {
+                    proposal_id,
+                    name: name.clone(),
+                    height: *height,
                 });
-                let _ = self.write_governance_evidence();
                 Ok(())
             }
-            "staking_reward_rate" => {
-                // Parse as decimal fraction (e.g. "0.05" for 5%) then convert to basis points
-                let rate: f64 = value.parse().map_err(|_| {
-                    "Invalid staking_reward_rate: must be decimal fraction".to_string()
-                })?;
-                if !(0.0..=1.0).contains(&rate) {
-                    return Err("staking_reward_rate must be between 0.0 and 1.0".to_string());
-                }
-                let bps = (rate * 10_000.0).round() as u64; // basis points
-                {
-                    let mut staking = self.staking.lock().unwrap();
-                    staking.set_reward_rate_bps(bps);
-                }
-                self.emit_event(GovernanceEvent::ParameterChanged {
-                    key: key.to_string(),
-                    old_value,
-                    new_value: value.to_string(),
+            ProposalType::HaltBridge { halted } => {
+                BridgeStore::set_halted_in(&mut *state, *halted)
+                    .map_err(|e| format!("Failed to set bridge halt: {e}"))?;
+                events.push(GovernanceEvent::BridgeHaltChanged {
+                    proposal_id,
+                    halted: *halted,
+                });
+                Ok(())
+            }
+            ProposalType::PauseModule { module, paused } => {
+                PauseModule::with_store(&mut *state).set_paused(module, *paused)?;
+                events.push(GovernanceEvent::ModulePauseChanged {
+                    proposal_id,
+                    module: module.clone(),
+                    paused: *paused,
                 });
-                let _ = self.write_governance_evidence();
                 Ok(())
             }
-            _ => Err(format!(
-                "Parameter '{}' is not governable. Allowed parameters: {:?}",
-                key,
-                self.get_governable_parameters()
-            )),
         }
     }
 
-    fn get_parameter_value(&self, key: &str) -> Result<String, String> {
-        match key {
-            "gas_limit" => Ok(self.config.gas_limit.to_string()),
-            "consensus.max_gas_per_block" => Ok(self.config.max_gas_per_block.to_string()),
-            "staking_reward_rate" => {
-                let staking = self.staking.lock().unwrap();
-                let bps = staking.get_reward_rate_bps();
-                // Convert back to decimal fraction string
-                let frac = (bps as f64) / 10_000.0;
-                Ok(format!("{frac:.4}"))
+    /// Apply parameter changes with enhanced governance event emission. The value is
+    /// stored in the parameter registry, which the node applies to every module when the
+    /// next block begins; governance and staking, which this module holds, change now
+    /// (execution runs after the block is committed, so also from the next block). Gas
+    /// costs instead get a new gas schedule version activating at the next block.
+    fn apply_parameter_change(
+        &mut self,
+        state: &mut State,
+        key: &str,
+        value: &str,
+        events: &mut Vec<GovernanceEvent>,
+    ) -> Result<(), String> {
+        let spec = params::spec(key)?;
+        spec.check(value)?;
+        let old_value = self.get_parameter_value(key)?;
+
+        if spec.module == "gas" {
+            let activation_height = self.storage.height() + 1;
+            GasScheduleModule::with_store(&mut *state).set_param(key, value, activation_height)?;
+        } else {
+            ParamsModule::with_store(&mut *state).set(key, value)?;
+        }
+        match spec.module {
+            "governance" => {
+                self.config.set_param(key, value)?;
+                self.store_config(&mut *state)?;
             }
-            _ => Err(format!("Unknown parameter: {key}")),
+            "staking" => StakingModule::with_store(&mut *state).set_param(key, value)?,
+            _ => {}
         }
+
+        // Emit parameter change event
+        events.push(GovernanceEvent::ParameterChanged {
+            key: key.to_string(),
+            old_value,
+            new_value: value.to_string(),
+        });
+        Ok(())
+    }
+
+    fn get_parameter_value(&self, key: &str) -> Result<String, String> {
+        let spec = params::spec(key).map_err(|_| format!("Unknown parameter: {key}"))?;
+        let value = match spec.module {
+            "governance" => self.config.get_param(key),
+            "staking" => self.staking.lock().unwrap().get_param(key),
+            "gas" => GasScheduleModule::new(self.storage.clone())
+                .latest()
+                .schedule
+                .get_param(key),
+            // Other modules live outside governance; report the last governed value
+            _ => ParamsModule::new(self.storage.clone()).get(key),
+        };
+        Ok(value.unwrap_or_else(|| "default".to_string()))
     }
 
     pub fn get_governable_parameters(&self) -> Vec<String> {
-        vec![
-            "gas_limit".to_string(),
-            "consensus.max_gas_per_block".to_string(),
-            "staking_reward_rate".to_string(),
-        ]
+        params::PARAMS
+            .iter()
+            .map(|spec| spec.key.to_string())
+            .collect()
+    }
+
+    /// Apply governed values of governance parameters (at block begin)
+    pub fn apply_params(&mut self, values: &BTreeMap<String, String>) -> Result<(), String> {
+        params::apply_params(&mut self.config, values)
     }
 
     /// Get current value of a governance parameter
@@ -670,6 +1804,11 @@ impl GovernanceModule {
         self.events.clear();
     }
 
+    /// Remove and return the events emitted since the last call
+    pub fn take_events(&mut self) -> Vec<GovernanceEvent> {
+        std::mem::take(&mut self.events)
+    }
+
     /// Public method to get proposal (exposed for RPC)
     pub fn get_proposal(&self, proposal_id: u64) -> Result<Option<Proposal>, String> {
         self._get_proposal(proposal_id)
@@ -677,17 +1816,8 @@ impl GovernanceModule {
 
     /// Get all proposals (for API endpoint)
     pub fn get_all_proposals(&self) -> Result<Vec<Proposal>, String> {
-        let last_id = self
-            .storage
-            .db
-            .get("gov:last_proposal_id")
-            .ok()
-            .flatten()
-            .and_then(|b| bincode::deserialize::<u64>(&b).ok())
-            .unwrap_or(0);
-
         let mut proposals = Vec::new();
-        for id in 1..=last_id {
+        for id in self.get_all_proposal_ids()? {
             if let Some(proposal) = self._get_proposal(id)? {
                 proposals.push(proposal);
             }
@@ -702,187 +1832,83 @@ impl GovernanceModule {
 
     // Storage helper methods
 
-    fn next_proposal_id(&self) -> u64 {
-        let last_id = self
-            .storage
-            .db
-            .get("gov:last_proposal_id")
-            .ok()
-            .flatten()
-            .and_then(|b| bincode::deserialize::<u64>(&b).ok())
-            .unwrap_or(0);
-
-        let next_id = last_id + 1;
-        let _ = self.storage.db.put(
-            "gov:last_proposal_id",
-            bincode::serialize(&next_id).unwrap(),
-        );
-        next_id
-    }
-
-    fn store_proposal(&self, proposal: &Proposal) -> Result<(), String> {
-        let key = format!("gov:proposal:{}", proposal.id);
-        let data = bincode::serialize(proposal)
-            .map_err(|e| format!("Failed to serialize proposal: {e}"))?;
-        self.storage
-            .db
-            .put(key, data)
-            .map_err(|e| format!("Failed to store proposal: {e}"))?;
-        Ok(())
+    /// Governance store writing straight to storage (end-block and RPC paths)
+    fn store(&self) -> GovernanceStore {
+        GovernanceStore::new(self.storage.clone())
     }
 
     fn _get_proposal(&self, proposal_id: u64) -> Result<Option<Proposal>, String> {
-        let key = format!("gov:proposal:{proposal_id}");
-        match self.storage.db.get(key) {
-            Ok(Some(data)) => {
-                let proposal = bincode::deserialize::<Proposal>(&data)
-                    .map_err(|e| format!("Failed to deserialize proposal: {e}"))?;
-                Ok(Some(proposal))
-            }
-            Ok(None) => Ok(None),
-            Err(e) => Err(format!("Failed to get proposal: {e}")),
-        }
-    }
-
-    fn store_vote(&self, vote: &Vote) -> Result<(), String> {
-        let key = format!("gov:vote:{}:{}", vote.proposal_id, vote.voter);
-        let data =
-            bincode::serialize(vote).map_err(|e| format!("Failed to serialize vote: {e}"))?;
-        self.storage
-            .db
-            .put(key, data)
-            .map_err(|e| format!("Failed to store vote: {e}"))?;
-        Ok(())
-    }
-
-    fn has_voted(&self, proposal_id: u64, voter: &str) -> Result<bool, String> {
-        let key = format!("gov:vote:{proposal_id}:{voter}");
-        Ok(self
-            .storage
-            .db
-            .get(key)
-            .map_err(|e| format!("Failed to check vote: {e}"))?
-            .is_some())
+        self.store().proposal(proposal_id)
     }
 
     fn _get_proposal_votes(&self, proposal_id: u64) -> Result<Vec<Vote>, String> {
-        let _prefix = format!("gov:vote:{proposal_id}:");
-        let mut votes = Vec::new();
-
-        // This is a simplified implementation that scans all possible vote keys
-        // In a production system, you'd want a more efficient prefix scan
-        // For now, we'll iterate through potential voter addresses
-
-        // Get all accounts to check for votes
-        let state = self.state.lock().unwrap();
-        for voter_addr in state.accounts.keys() {
-            let vote_key = format!("gov:vote:{proposal_id}:{voter_addr}");
-            if let Ok(Some(data)) = self.storage.db.get(vote_key) {
-                if let Ok(vote) = bincode::deserialize::<Vote>(&data) {
-                    votes.push(vote);
-                }
-            }
-        }
-
-        Ok(votes)
+        Ok(self.store().votes(proposal_id))
     }
 
     fn get_all_proposal_ids(&self) -> Result<Vec<u64>, String> {
-        let last_id = self
-            .storage
-            .db
-            .get("gov:last_proposal_id")
-            .ok()
-            .flatten()
-            .and_then(|b| bincode::deserialize::<u64>(&b).ok())
-            .unwrap_or(0);
-
-        Ok((1..=last_id).collect())
+        Ok((1..=self.store().last_proposal_id()).collect())
     }
 
-    fn store_config(&self) -> Result<(), String> {
+    fn store_config(&self, mut kv: impl KvStore) -> Result<(), String> {
         let data = bincode::serialize(&self.config)
             .map_err(|e| format!("Failed to serialize config: {e}"))?;
-        self.storage
-            .db
-            .put("gov:config", data)
-            .map_err(|e| format!("Failed to store config: {e}"))?;
-        Ok(())
+        kv.kv_put("gov:config", data)
+            .map_err(|e| format!("Failed to store config: {e}"))
     }
 
     fn emit_event(&mut self, event: GovernanceEvent) {
         // Keep in memory
         self.events.push(event.clone());
         // Append to evidence log (best-effort)
-        let _ = Self::append_event_to_evidence_log(&event);
-    }
-
-    // Deposit storage and retrieval functions
-
-    fn store_deposit(&self, deposit: &Deposit) -> Result<(), String> {
-        let key = format!("gov:deposit:{}:{}", deposit.proposal_id, deposit.depositor);
-        let data =
-            bincode::serialize(deposit).map_err(|e| format!("Failed to serialize deposit: {e}"))?;
-        self.storage
-            .db
-            .put(key, data)
-            .map_err(|e| format!("Failed to store deposit: {e}"))?;
-        Ok(())
+        if self.write_evidence {
+            let _ = Self::append_event_to_evidence_log(&event);
+        }
     }
 
-    fn get_proposal_deposits(&self, proposal_id: u64) -> Result<Vec<Deposit>, String> {
-        let mut deposits = Vec::new();
-
-        // Get all accounts to check for deposits (similar to vote retrieval)
-        let state = self.state.lock().unwrap();
-        for depositor_addr in state.accounts.keys() {
-            let deposit_key = format!("gov:deposit:{proposal_id}:{depositor_addr}");
-            if let Ok(Some(data)) = self.storage.db.get(deposit_key) {
-                if let Ok(deposit) = bincode::deserialize::<Deposit>(&data) {
-                    deposits.push(deposit);
-                }
-            }
+    /// Record events of a committed transaction
+    pub fn record_events(&mut self, events: Vec<GovernanceEvent>) {
+        if events.is_empty() {
+            return;
+        }
+        for event in events {
+            self.emit_event(event);
+        }
+        if self.write_evidence {
+            let _ = self.write_governance_evidence();
         }
-
-        Ok(deposits)
     }
 
     /// Refund deposits to depositors (for successful or failed execution)
-    fn refund_deposits(&mut self, proposal_id: u64) -> Result<(), String> {
-        let deposits = self.get_proposal_deposits(proposal_id)?;
-
-        {
-            let mut state = self.state.lock().unwrap();
-            for deposit in deposits {
-                let mut account = state.get_account(&deposit.depositor);
-                account.add_balance(&deposit.denom, deposit.amount);
-                let account_clone = account.clone();
-                state.accounts.insert(deposit.depositor.clone(), account);
-                let _ = state
-                    .storage
-                    .set_balances_db(&deposit.depositor, &account_clone.balances);
-            }
+    fn refund_deposits(&self, state: &mut State, proposal_id: u64) -> Result<(), String> {
+        for deposit in GovernanceStore::with_store(&mut *state).deposits(proposal_id) {
+            send_from_module(
+                state,
+                GOV_DEPOSITS,
+                &deposit.depositor,
+                &deposit.denom,
+                deposit.amount,
+            )?;
         }
-
         Ok(())
     }
 
     /// Burn deposits (for rejected or failed proposals)
-    fn burn_deposits(&mut self, proposal_id: u64) -> Result<(), String> {
-        let deposits = self.get_proposal_deposits(proposal_id)?;
-
-        // Deposits are already deducted from accounts, so burning means not refunding them
-        // In a production system, you might want to emit specific burn events or track burned amounts
-
-        // Emit events for each burned deposit
-        for deposit in deposits {
-            self.emit_event(GovernanceEvent::DepositBurned {
+    fn burn_deposits(
+        &self,
+        state: &mut State,
+        proposal_id: u64,
+        events: &mut Vec<GovernanceEvent>,
+    ) -> Result<(), String> {
+        // Deposits are held by the gov_deposits module account; burning destroys them
+        // there instead of refunding them
+        for deposit in GovernanceStore::with_store(&mut *state).deposits(proposal_id) {
+            burn_from_module(state, GOV_DEPOSITS, &deposit.denom, deposit.amount)?;
+            events.push(GovernanceEvent::DepositBurned {
                 proposal_id,
                 depositor: deposit.depositor,
                 amount: deposit.amount,
             });
         }
-
         Ok(())
     }
 }
@@ -930,7 +1956,10 @@ impl GovernanceModule {
                     "id": p.id,
                     "title": p.title,
                     "description": p.description,
-                    "type": match &p.proposal_type { ProposalType::ParameterChange { key, value } => serde_json::json!({"parameter_change": {"key": key, "value": value}}) },
+                    "type": match &p.proposal_type {
+                        ProposalType::ParameterChange { key, value } => serde_json::json!({"parameter_change": {"key": key, "value": value}}),
+                        other => serde_json::to_value(other).unwrap_or_default(),
+                    },
                     "status": format!("{:?}", p.status),
                     "total_deposit": p.total_deposit.to_string(),
                     "submit_height": p.submit_height,
@@ -986,59 +2015,118 @@ impl GovernanceModule {
     }
 }
 
-/// Gas accounting constants for governance operations
-pub const GAS_SUBMIT_PROPOSAL: u64 = 50_000;
-pub const GAS_DEPOSIT: u64 = 30_000;
-pub const GAS_VOTE: u64 = 20_000;
-pub const GAS_TALLY: u64 = 10_000;
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::storage::state::Storage;
-    use std::sync::{Arc, Mutex};
-    use tempfile::TempDir;
-
-    fn setup_test_governance() -> (GovernanceModule, TempDir) {
-        let temp_dir = TempDir::new().unwrap();
-        let storage = Arc::new(Storage::open(temp_dir.path().to_path_buf()).unwrap()); // use open instead of new
-        let state = Arc::new(Mutex::new(State::new(storage.clone())));
-
-        // Provide minimal staking module instance using real constructor (no Default implementation available)
-        let staking_storage = storage.clone();
-        let staking = Arc::new(Mutex::new(StakingModule::new(staking_storage)));
-
-        let governance = GovernanceModule::new(storage, state, staking);
-        (governance, temp_dir)
-    }
-
-    #[test]
-    fn test_submit_proposal() {
-        let (mut governance, _temp_dir) = setup_test_governance();
-
-        let proposal_id = governance
-            .submit_proposal(
-                100,
-                "Test Proposal".to_string(),
-                "Test Description".to_string(),
-                ProposalType::ParameterChange {
-                    key: "gas_limit".to_string(),
-                    value: "500000".to_string(),
-                },
-            )
-            .unwrap();
-
-        assert_eq!(proposal_id, 1);
-
-        let proposal = governance.get_proposal(proposal_id).unwrap().unwrap();
-        assert_eq!(proposal.title, "Test Proposal");
-        assert_eq!(proposal.status, ProposalStatus::DepositPeriod);
-        assert_eq!(proposal.submit_height, 100);
-    }
-
-    #[test]
-    fn test_deposit_transitions_to_voting() {
-        let (mut governance, _temp_dir) = setup_test_governance();
+impl GovernanceModule {
+    /// Apply environment variable overrides for governance parameters (runtime init)
+    /// Recognized env vars (all optional):
+    ///  DYT_GOV_MIN_DEPOSIT (u128, micro udgt)
+    ///  DYT_GOV_DEPOSIT_PERIOD (u64, blocks)
+    ///  DYT_GOV_VOTING_PERIOD (u64, blocks)
+    ///  DYT_GOV_QUORUM_BPS (u64, basis points)
+    ///  DYT_GOV_THRESHOLD_BPS (u64, basis points)
+    ///  DYT_GOV_VETO_BPS (u64, basis points)
+    ///  DYT_GOV_LIQUID_BALANCE_VOTING (bool)
+    pub fn apply_env_overrides(&mut self) {
+        use std::env;
+        let mut changed = false;
+        if let Ok(raw) = env::var("DYT_GOV_MIN_DEPOSIT") {
+            if let Ok(v) = raw.parse::<u128>() {
+                self.config.min_deposit = v;
+                changed = true;
+            }
+        }
+        if let Ok(raw) = env::var("DYT_GOV_DEPOSIT_PERIOD") {
+            if let Ok(v) = raw.parse::<u64>() {
+                self.config.deposit_period = v;
+                changed = true;
+            }
+        }
+        if let Ok(raw) = env::var("DYT_GOV_VOTING_PERIOD") {
+            if let Ok(v) = raw.parse::<u64>() {
+                self.config.voting_period = v;
+                changed = true;
+            }
+        }
+        if let Ok(raw) = env::var("DYT_GOV_QUORUM_BPS") {
+            if let Ok(v) = raw.parse::<u128>() {
+                self.config.quorum = v;
+                changed = true;
+            }
+        }
+        if let Ok(raw) = env::var("DYT_GOV_THRESHOLD_BPS") {
+            if let Ok(v) = raw.parse::<u128>() {
+                self.config.threshold = v;
+                changed = true;
+            }
+        }
+        if let Ok(raw) = env::var("DYT_GOV_VETO_BPS") {
+            if let Ok(v) = raw.parse::<u128>() {
+                self.config.veto_threshold = v;
+                changed = true;
+            }
+        }
+        if let Ok(raw) = env::var("DYT_GOV_LIQUID_BALANCE_VOTING") {
+            self.config.liquid_balance_voting = raw == "1" || raw.to_lowercase() == "true";
+            changed = true;
+        }
+        if changed {
+            let _ = self.store_config(self.storage.clone());
+        }
+    }
+}
+
+/// Gas accounting constants for governance operations
+pub const GAS_SUBMIT_PROPOSAL: u64 = 50_000;
+pub const GAS_DEPOSIT: u64 = 30_000;
+pub const GAS_VOTE: u64 = 20_000;
+pub const GAS_TALLY: u64 = 10_000;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::state::Storage;
+    use std::sync::{Arc, Mutex};
+    use tempfile::TempDir;
+
+    fn setup_test_governance() -> (GovernanceModule, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Arc::new(Storage::open(temp_dir.path().to_path_buf()).unwrap()); // use open instead of new
+        let state = Arc::new(Mutex::new(State::new(storage.clone())));
+
+        // Provide minimal staking module instance using real constructor (no Default implementation available)
+        let staking_storage = storage.clone();
+        let staking = Arc::new(Mutex::new(StakingModule::new(staking_storage)));
+
+        let governance = GovernanceModule::new(storage, state, staking);
+        (governance, temp_dir)
+    }
+
+    #[test]
+    fn test_submit_proposal() {
+        let (mut governance, _temp_dir) = setup_test_governance();
+
+        let proposal_id = governance
+            .submit_proposal(
+                100,
+                "Test Proposal".to_string(),
+                "Test Description".to_string(),
+                ProposalType::ParameterChange {
+                    key: "gas_limit".to_string(),
+                    value: "500000".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(proposal_id, 1);
+
+        let proposal = governance.get_proposal(proposal_id).unwrap().unwrap();
+        assert_eq!(proposal.title, "Test Proposal");
+        assert_eq!(proposal.status, ProposalStatus::DepositPeriod);
+        assert_eq!(proposal.submit_height, 100);
+    }
+
+    #[test]
+    fn test_deposit_transitions_to_voting() {
+        let (mut governance, _temp_dir) = setup_test_governance();
 
         // Setup account with DGT balance
         {
@@ -1080,7 +2168,8 @@ mod tests {
     fn test_vote_with_dgt_weight() {
         let (mut governance, _temp_dir) = setup_test_governance();
 
-        // Setup account with DGT balance
+        // Setup account with DGT balance; it votes with it since it has no stake
+        governance.config.liquid_balance_voting = true;
         {
             let mut state = governance.state.lock().unwrap();
             let mut account = state.get_account("voter1");
@@ -1106,7 +2195,7 @@ mod tests {
             proposal.status = ProposalStatus::VotingPeriod;
             proposal.voting_start_height = 150;
             proposal.voting_end_height = 150 + governance.config.voting_period;
-            governance.store_proposal(&proposal).unwrap();
+            governance.store().put_proposal(&proposal).unwrap();
         }
 
         governance
@@ -1124,7 +2213,8 @@ mod tests {
     fn test_no_with_veto_vote() {
         let (mut governance, _temp_dir) = setup_test_governance();
 
-        // Setup account with DGT balance
+        // Setup account with DGT balance; it votes with it since it has no stake
+        governance.config.liquid_balance_voting = true;
         {
             let mut state = governance.state.lock().unwrap();
             let mut account = state.get_account("voter1");
@@ -1150,7 +2240,7 @@ mod tests {
             proposal.status = ProposalStatus::VotingPeriod;
             proposal.voting_start_height = 150;
             proposal.voting_end_height = 150 + governance.config.voting_period;
-            governance.store_proposal(&proposal).unwrap();
+            governance.store().put_proposal(&proposal).unwrap();
         }
 
         governance
@@ -1165,87 +2255,684 @@ mod tests {
     }
 
     #[test]
-    fn test_parameter_change_execution() {
+    fn test_votes_use_snapshot_from_voting_start() {
         let (mut governance, _temp_dir) = setup_test_governance();
+        governance.config.liquid_balance_voting = true;
+        governance
+            .state
+            .lock()
+            .unwrap()
+            .credit("voter1", "udgt", 2_000_000_000);
+        let submit = |governance: &mut GovernanceModule| {
+            governance
+                .submit_proposal(
+                    100,
+                    "Test Proposal".to_string(),
+                    "Test Description".to_string(),
+                    ProposalType::Text,
+                )
+                .unwrap()
+        };
 
-        // Test gas_limit parameter change
+        let proposal_id = submit(&mut governance);
         governance
-            .apply_parameter_change("gas_limit", "100000")
+            .deposit(110, "voter1", proposal_id, 1_000_000_000, "udgt")
             .unwrap();
-        assert_eq!(governance.config.gas_limit, 100000);
+        assert_eq!(
+            governance.snapshot_total_power(proposal_id),
+            Some(1_000_000_000)
+        );
 
-        // Test consensus.max_gas_per_block parameter change
+        // Tokens moved after voting started carry no votes
+        governance
+            .state
+            .lock()
+            .unwrap()
+            .set_balance("voter1", "udgt", 0);
+        governance
+            .state
+            .lock()
+            .unwrap()
+            .credit("voter2", "udgt", 1_000_000_000);
+        assert!(governance
+            .vote(120, "voter2", proposal_id, VoteOption::No)
+            .is_err());
         governance
-            .apply_parameter_change("consensus.max_gas_per_block", "20000000")
+            .vote(120, "voter1", proposal_id, VoteOption::Yes)
             .unwrap();
-        assert_eq!(governance.config.max_gas_per_block, 20000000);
+        let tally = governance.tally(proposal_id).unwrap();
+        assert_eq!((tally.yes, tally.no), (1_000_000_000, 0));
 
-        // Test staking_reward_rate parameter change
+        // Without liquid balance voting only bonded stake counts
+        governance.config.liquid_balance_voting = false;
+        governance
+            .staking
+            .lock()
+            .unwrap()
+            .update_delegator_stake("staker", 300);
+        let proposal_id = submit(&mut governance);
+        governance
+            .deposit(130, "voter2", proposal_id, 1_000_000_000, "udgt")
+            .unwrap();
+        assert_eq!(governance.snapshot_total_power(proposal_id), Some(300));
+        assert!(governance
+            .vote(140, "voter1", proposal_id, VoteOption::Yes)
+            .is_err());
         governance
-            .apply_parameter_change("staking_reward_rate", "0.10")
+            .vote(140, "staker", proposal_id, VoteOption::Yes)
             .unwrap();
+        assert_eq!(governance.tally(proposal_id).unwrap().yes, 300);
+    }
+
+    #[test]
+    fn test_delegators_inherit_validator_vote_unless_they_vote() {
+        let (mut governance, _temp_dir) = setup_test_governance();
         {
-            let staking = governance.staking.lock().unwrap();
-            assert_eq!(staking.get_reward_rate_bps(), 1000); // 10% as basis points
+            let mut staking = governance.staking.lock().unwrap();
+            staking.register_validator("val1");
+            staking.register_validator("val2");
+            staking.delegate("d1", "val1", 300).unwrap();
+            staking.delegate("d2", "val1", 200).unwrap();
+            staking.delegate("val1", "val1", 100).unwrap();
+            staking.delegate("d3", "val2", 400).unwrap();
+        }
+        governance
+            .state
+            .lock()
+            .unwrap()
+            .credit("proposer", "udgt", 1_000_000_000);
+        let proposal_id = governance
+            .submit_proposal(
+                100,
+                "Signal".to_string(),
+                "Test Description".to_string(),
+                ProposalType::Text,
+            )
+            .unwrap();
+        governance
+            .deposit(110, "proposer", proposal_id, 1_000_000_000, "udgt")
+            .unwrap();
+
+        governance
+            .vote(120, "val1", proposal_id, VoteOption::Yes)
+            .unwrap();
+        governance
+            .vote(120, "d2", proposal_id, VoteOption::No)
+            .unwrap();
+        governance
+            .vote(120, "d3", proposal_id, VoteOption::Abstain)
+            .unwrap();
+
+        let (tally, validators) = governance.tally_with_breakdown(proposal_id).unwrap();
+        // val1's own 100 plus d1's inherited 300; d2 overrides with No
+        assert_eq!(tally.yes, 400);
+        assert_eq!(tally.no, 200);
+        assert_eq!(tally.abstain, 400);
+        assert_eq!(tally.total_voting_power, 1_000);
+        assert_eq!(
+            validators,
+            vec![
+                ValidatorTally {
+                    validator: "val1".to_string(),
+                    delegated_power: 600,
+                    overridden_power: 300,
+                    inherited_power: 300,
+                    vote: vec![WeightedVoteOption {
+                        option: VoteOption::Yes,
+                        weight: WEIGHT_ONE,
+                    }],
+                },
+                ValidatorTally {
+                    validator: "val2".to_string(),
+                    delegated_power: 400,
+                    overridden_power: 400,
+                    inherited_power: 0,
+                    vote: Vec::new(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_weighted_vote_splits_power() {
+        assert_eq!(parse_weight("0.25").unwrap(), WEIGHT_ONE / 4);
+        assert_eq!(parse_weight("1").unwrap(), WEIGHT_ONE);
+        assert!(parse_weight("0.1234567890123456789").is_err());
+        assert!(parse_weight("-0.5").is_err());
+        assert_eq!(format_weight(WEIGHT_ONE * 3 / 10), "0.3");
+        assert_eq!(format_weight(WEIGHT_ONE), "1");
+
+        let (mut governance, _temp_dir) = setup_test_governance();
+        {
+            let mut staking = governance.staking.lock().unwrap();
+            staking.register_validator("val1");
+            staking.delegate("custodian", "val1", 1_000).unwrap();
+            staking.delegate("d1", "val1", 500).unwrap();
         }
+        governance
+            .state
+            .lock()
+            .unwrap()
+            .credit("proposer", "udgt", 1_000_000_000);
+        let proposal_id = governance
+            .submit_proposal(
+                100,
+                "Signal".to_string(),
+                "Test Description".to_string(),
+                ProposalType::Text,
+            )
+            .unwrap();
+        governance
+            .deposit(110, "proposer", proposal_id, 1_000_000_000, "udgt")
+            .unwrap();
 
-        // Test invalid parameter
+        let split = |shares: &[(VoteOption, &str)]| -> Vec<WeightedVoteOption> {
+            shares
+                .iter()
+                .map(|(option, weight)| WeightedVoteOption {
+                    option: option.clone(),
+                    weight: parse_weight(weight).unwrap(),
+                })
+                .collect()
+        };
+        let short = split(&[(VoteOption::Yes, "0.5"), (VoteOption::No, "0.4")]);
         assert!(governance
-            .apply_parameter_change("invalid_param", "123")
+            .weighted_vote(120, "custodian", proposal_id, short)
             .is_err());
+        let duplicate = split(&[(VoteOption::Yes, "0.5"), (VoteOption::Yes, "0.5")]);
+        assert!(governance
+            .weighted_vote(120, "custodian", proposal_id, duplicate)
+            .is_err());
+        let options = split(&[(VoteOption::Yes, "0.7"), (VoteOption::No, "0.3")]);
+        governance
+            .weighted_vote(120, "custodian", proposal_id, options)
+            .unwrap();
+        // The validator's inherited power is split the same way
+        let options = split(&[
+            (VoteOption::Abstain, "0.2"),
+            (VoteOption::NoWithVeto, "0.8"),
+        ]);
+        governance
+            .weighted_vote(120, "val1", proposal_id, options)
+            .unwrap();
+
+        let vote = &governance.get_proposal_votes(proposal_id).unwrap()[0];
+        assert_eq!(vote.option, VoteOption::Yes);
+        let tally = governance.tally(proposal_id).unwrap();
+        assert_eq!((tally.yes, tally.no), (700, 300));
+        assert_eq!((tally.abstain, tally.no_with_veto), (100, 400));
     }
-}
 
-impl GovernanceModule {
-    /// Apply environment variable overrides for governance parameters (runtime init)
-    /// Recognized env vars (all optional):
-    ///  DYT_GOV_MIN_DEPOSIT (u128, micro udgt)
-    ///  DYT_GOV_DEPOSIT_PERIOD (u64, blocks)
-    ///  DYT_GOV_VOTING_PERIOD (u64, blocks)
-    ///  DYT_GOV_QUORUM_BPS (u64, basis points)
-    ///  DYT_GOV_THRESHOLD_BPS (u64, basis points)
-    ///  DYT_GOV_VETO_BPS (u64, basis points)
-    pub fn apply_env_overrides(&mut self) {
-        use std::env;
-        let mut changed = false;
-        if let Ok(raw) = env::var("DYT_GOV_MIN_DEPOSIT") {
-            if let Ok(v) = raw.parse::<u128>() {
-                self.config.min_deposit = v;
-                changed = true;
-            }
+    #[test]
+    fn test_legacy_votes_decode_as_single_option() {
+        let bytes =
+            bincode::serialize(&(7u64, "voter1".to_string(), VoteOption::No, 42u128)).unwrap();
+        let vote = Vote::decode(&bytes).unwrap();
+        assert_eq!((vote.proposal_id, vote.weight), (7, 42));
+        assert_eq!(
+            vote.options,
+            vec![WeightedVoteOption {
+                option: VoteOption::No,
+                weight: WEIGHT_ONE,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_end_block_writes_through_an_overlay() {
+        let (mut governance, _temp_dir) = setup_test_governance();
+        crate::runtime::module_accounts::mint_to_module(
+            &mut governance.state.lock().unwrap(),
+            COMMUNITY_POOL,
+            "udgt",
+            1_000,
+        )
+        .unwrap();
+        let spend = governance
+            .submit_proposal(
+                100,
+                "Spend".to_string(),
+                "Description".to_string(),
+                ProposalType::CommunitySpend {
+                    recipient: "grantee".to_string(),
+                    amount: 400,
+                    denom: "udgt".to_string(),
+                },
+            )
+            .unwrap();
+        let mut proposal = governance.get_proposal(spend).unwrap().unwrap();
+        proposal.status = ProposalStatus::Passed;
+        governance.store().put_proposal(&proposal).unwrap();
+
+        // Processing the proposal writes only to the overlay; dropping it leaves nothing
+        let state = governance.state.clone();
+        {
+            let base = state.lock().unwrap();
+            let mut overlay = StateOverlay::new(&base);
+            let mut events = Vec::new();
+            governance
+                .end_block_proposal(&mut overlay, 101, spend, &mut events)
+                .unwrap();
+            assert_eq!(overlay.balance_of("grantee", "udgt"), 400);
+            assert!(events
+                .iter()
+                .any(|e| matches!(e, GovernanceEvent::ProposalExecuted { id } if *id == spend)));
         }
-        if let Ok(raw) = env::var("DYT_GOV_DEPOSIT_PERIOD") {
-            if let Ok(v) = raw.parse::<u64>() {
-                self.config.deposit_period = v;
-                changed = true;
-            }
+        let proposal = governance.get_proposal(spend).unwrap().unwrap();
+        assert_eq!(proposal.status, ProposalStatus::Passed);
+        assert_eq!(state.lock().unwrap().balance_of("grantee", "udgt"), 0);
+        assert!(!governance
+            .get_events()
+            .iter()
+            .any(|e| matches!(e, GovernanceEvent::ProposalExecuted { .. })));
+
+        // end_block commits it
+        governance.end_block(101).unwrap();
+        let proposal = governance.get_proposal(spend).unwrap().unwrap();
+        assert_eq!(proposal.status, ProposalStatus::Executed);
+        assert_eq!(state.lock().unwrap().balance_of("grantee", "udgt"), 400);
+    }
+
+    #[test]
+    fn test_community_spend_and_upgrade_execution() {
+        let (mut governance, _temp_dir) = setup_test_governance();
+        {
+            let mut state = governance.state.lock().unwrap();
+            crate::runtime::module_accounts::mint_to_module(
+                &mut state,
+                COMMUNITY_POOL,
+                "udgt",
+                1_000,
+            )
+            .unwrap();
         }
-        if let Ok(raw) = env::var("DYT_GOV_VOTING_PERIOD") {
-            if let Ok(v) = raw.parse::<u64>() {
-                self.config.voting_period = v;
-                changed = true;
+        let submit = |governance: &mut GovernanceModule, proposal_type| {
+            governance.submit_proposal(
+                100,
+                "Proposal".to_string(),
+                "Description".to_string(),
+                proposal_type,
+            )
+        };
+        let pass = |governance: &mut GovernanceModule, id| {
+            let mut proposal = governance.get_proposal(id).unwrap().unwrap();
+            proposal.status = ProposalStatus::Passed;
+            governance.store().put_proposal(&proposal).unwrap();
+        };
+
+        assert!(submit(
+            &mut governance,
+            ProposalType::CommunitySpend {
+                recipient: "grantee".to_string(),
+                amount: 0,
+                denom: "udgt".to_string(),
             }
+        )
+        .is_err());
+        let spend = submit(
+            &mut governance,
+            ProposalType::CommunitySpend {
+                recipient: "grantee".to_string(),
+                amount: 400,
+                denom: "udgt".to_string(),
+            },
+        )
+        .unwrap();
+        pass(&mut governance, spend);
+        governance.execute(spend).unwrap();
+        {
+            let mut state = governance.state.lock().unwrap();
+            assert_eq!(state.balance_of("grantee", "udgt"), 400);
         }
-        if let Ok(raw) = env::var("DYT_GOV_QUORUM_BPS") {
-            if let Ok(v) = raw.parse::<u128>() {
-                self.config.quorum = v;
-                changed = true;
+
+        // Spending more than the pool holds fails execution
+        let overspend = submit(
+            &mut governance,
+            ProposalType::CommunitySpend {
+                recipient: "grantee".to_string(),
+                amount: 601,
+                denom: "udgt".to_string(),
+            },
+        )
+        .unwrap();
+        pass(&mut governance, overspend);
+        assert!(governance.execute(overspend).is_err());
+        let proposal = governance.get_proposal(overspend).unwrap().unwrap();
+        assert_eq!(proposal.status, ProposalStatus::FailedExecution);
+
+        // The upgrade must come after the voting period
+        let voting_end = 100 + governance.config.deposit_period + governance.config.voting_period;
+        assert!(submit(
+            &mut governance,
+            ProposalType::SoftwareUpgrade {
+                name: "v2".to_string(),
+                height: voting_end,
+                info: String::new(),
             }
+        )
+        .is_err());
+        let upgrade = submit(
+            &mut governance,
+            ProposalType::SoftwareUpgrade {
+                name: "v2".to_string(),
+                height: voting_end + 10,
+                info: "https://example.com/v2".to_string(),
+            },
+        )
+        .unwrap();
+        let upgrades = UpgradeModule::new(governance.storage.clone());
+        assert!(upgrades.plan().is_none());
+        pass(&mut governance, upgrade);
+        governance.execute(upgrade).unwrap();
+        let plan = upgrades.plan().unwrap();
+        assert_eq!((plan.name.as_str(), plan.height), ("v2", voting_end + 10));
+
+        let text = submit(&mut governance, ProposalType::Text).unwrap();
+        pass(&mut governance, text);
+        governance.execute(text).unwrap();
+    }
+
+    fn apply_param(
+        governance: &mut GovernanceModule,
+        key: &str,
+        value: &str,
+    ) -> Result<(), String> {
+        let state = governance.state.clone();
+        let mut state = state.lock().unwrap();
+        governance.apply_parameter_change(&mut state, key, value, &mut Vec::new())
+    }
+
+    #[test]
+    fn test_parameter_change_execution() {
+        let (mut governance, _temp_dir) = setup_test_governance();
+
+        // Test gas_limit parameter change
+        apply_param(&mut governance, "gas_limit", "100000").unwrap();
+        assert_eq!(governance.config.gas_limit, 100000);
+
+        // Test consensus.max_gas_per_block parameter change
+        apply_param(&mut governance, "consensus.max_gas_per_block", "20000000").unwrap();
+        assert_eq!(governance.config.max_gas_per_block, 20000000);
+
+        // Test staking_reward_rate parameter change
+        apply_param(&mut governance, "staking_reward_rate", "0.10").unwrap();
+        {
+            let staking = governance.staking.lock().unwrap();
+            assert_eq!(staking.get_reward_rate_bps(), 1000); // 10% as basis points
         }
-        if let Ok(raw) = env::var("DYT_GOV_THRESHOLD_BPS") {
-            if let Ok(v) = raw.parse::<u128>() {
-                self.config.threshold = v;
-                changed = true;
-            }
+
+        // Test invalid parameter
+        assert!(apply_param(&mut governance, "invalid_param", "123").is_err());
+    }
+
+    #[test]
+    fn test_registered_parameters_of_other_modules() {
+        let (mut governance, _temp_dir) = setup_test_governance();
+
+        // Values are checked against the registry at submission
+        let out_of_bounds = ProposalType::ParameterChange {
+            key: "fee_burn.burn_rate_bps".to_string(),
+            value: "20000".to_string(),
+        };
+        assert!(governance
+            .submit_proposal(100, "Burn".to_string(), String::new(), out_of_bounds)
+            .is_err());
+
+        // Modules outside governance pick the value up from the registry
+        apply_param(&mut governance, "fee_burn.burn_rate_bps", "1000").unwrap();
+        let governed = ParamsModule::new(governance.storage.clone()).all();
+        assert_eq!(
+            governed.get("fee_burn.burn_rate_bps").map(String::as_str),
+            Some("1000")
+        );
+        let mut fee_burn = crate::runtime::fee_burn::FeeBurnConfig::default();
+        params::apply_params(&mut fee_burn, &governed).unwrap();
+        assert_eq!(fee_burn.burn_rate_bps, 1000);
+
+        // Gas costs get a new schedule version from the next block
+        apply_param(&mut governance, "gas.transfer_base", "800").unwrap();
+        let gas = GasScheduleModule::new(governance.storage.clone());
+        let next_height = governance.storage.height() + 1;
+        assert_eq!(gas.at_height(next_height).schedule.transfer_base, 800);
+        assert_eq!(
+            gas.at_height(next_height - 1).version,
+            crate::gas::GAS_TABLE_VERSION
+        );
+
+        // Governance values survive a restart through the registry
+        apply_param(&mut governance, "governance.voting_period", "50").unwrap();
+        let mut config = GovernanceConfig::default();
+        params::apply_params(
+            &mut config,
+            &ParamsModule::new(governance.storage.clone()).all(),
+        )
+        .unwrap();
+        assert_eq!(config.voting_period, 50);
+    }
+
+    #[test]
+    fn test_events_are_taken_as_typed_block_events() {
+        let (mut governance, _temp_dir) = setup_test_governance();
+        governance
+            .submit_proposal(
+                100,
+                "Signal".to_string(),
+                "Test Description".to_string(),
+                ProposalType::Text,
+            )
+            .unwrap();
+        let events = governance.take_events();
+        assert_eq!(events.len(), 1);
+        assert!(governance.get_events().is_empty());
+        assert_eq!(
+            events[0].to_event(),
+            Event::new("gov_proposal_submitted").attr("proposal_id", 1)
+        );
+
+        let rejected = GovernanceEvent::ProposalRejected {
+            id: 2,
+            reason: None,
+        };
+        assert_eq!(
+            rejected.to_event(),
+            Event::new("gov_proposal_rejected").attr("proposal_id", 2)
+        );
+        let changed = GovernanceEvent::ParameterChanged {
+            key: "gas_limit".to_string(),
+            old_value: "2000".to_string(),
+            new_value: "3000".to_string(),
+        };
+        assert_eq!(
+            changed.to_event(),
+            Event::new("gov_parameter_changed")
+                .attr("key", "gas_limit")
+                .attr("new_value", "3000")
+                .attr("old_value", "2000")
+        );
+    }
+
+    #[test]
+    fn test_expedited_proposal_converts_below_its_threshold() {
+        let (mut governance, _temp_dir) = setup_test_governance();
+        {
+            let mut staking = governance.staking.lock().unwrap();
+            staking.register_validator("val1");
+            staking.delegate("d1", "val1", 600).unwrap();
+            staking.delegate("d2", "val1", 400).unwrap();
         }
-        if let Ok(raw) = env::var("DYT_GOV_VETO_BPS") {
-            if let Ok(v) = raw.parse::<u128>() {
-                self.config.veto_threshold = v;
-                changed = true;
-            }
+        governance
+            .state
+            .lock()
+            .unwrap()
+            .credit("proposer", "udgt", 5_000_000_000);
+        let proposal_id = governance
+            .submit_proposal_on_track(
+                100,
+                "Signal".to_string(),
+                "Test Description".to_string(),
+                ProposalType::Text,
+                ProposalTrack::Expedited,
+            )
+            .unwrap();
+
+        // The standard minimum deposit is not enough
+        governance
+            .deposit(105, "proposer", proposal_id, 1_000_000_000, "udgt")
+            .unwrap();
+        let proposal = governance.get_proposal(proposal_id).unwrap().unwrap();
+        assert_eq!(proposal.status, ProposalStatus::DepositPeriod);
+        governance
+            .deposit(110, "proposer", proposal_id, 4_000_000_000, "udgt")
+            .unwrap();
+        let proposal = governance.get_proposal(proposal_id).unwrap().unwrap();
+        assert_eq!(proposal.status, ProposalStatus::VotingPeriod);
+        assert_eq!(proposal.voting_end_height, 160);
+
+        // 60% yes passes a standard proposal but not an expedited one
+        governance
+            .vote(120, "d1", proposal_id, VoteOption::Yes)
+            .unwrap();
+        governance
+            .vote(120, "d2", proposal_id, VoteOption::No)
+            .unwrap();
+        governance.end_block(161).unwrap();
+        let proposal = governance.get_proposal(proposal_id).unwrap().unwrap();
+        assert_eq!(proposal.status, ProposalStatus::VotingPeriod);
+        assert_eq!(proposal.track, ProposalTrack::Standard);
+        assert_eq!(proposal.voting_end_height, 410);
+
+        governance.end_block(411).unwrap();
+        let proposal = governance.get_proposal(proposal_id).unwrap().unwrap();
+        assert_eq!(proposal.status, ProposalStatus::Passed);
+    }
+
+    #[test]
+    fn test_unregistered_delegation_target_does_not_vote() {
+        let (mut governance, _temp_dir) = setup_test_governance();
+        {
+            let mut staking = governance.staking.lock().unwrap();
+            staking.register_validator("val1");
+            staking.delegate("d1", "val1", 600).unwrap();
+            // Stake attributed to an address that never registered (e.g. in genesis)
+            staking.update_delegator_stake("d2", 400);
+            staking.add_validator_delegation("d2", "rogue", 400);
+            staking.set_total_stake(1_000);
         }
-        if changed {
-            let _ = self.store_config();
+        crate::runtime::supply::mint_to(
+            &mut governance.state.lock().unwrap(),
+            "proposer",
+            "udgt",
+            5_000_000_000,
+        )
+        .unwrap();
+        let proposal_id = governance
+            .submit_proposal_on_track(
+                100,
+                "Emergency".to_string(),
+                "Test Description".to_string(),
+                ProposalType::HaltBridge { halted: true },
+                ProposalTrack::Emergency,
+            )
+            .unwrap();
+        governance
+            .deposit(110, "proposer", proposal_id, 5_000_000_000, "udgt")
+            .unwrap();
+
+        // The electorate is the registered validator set
+        assert_eq!(
+            governance.snapshot_validators(proposal_id),
+            BTreeMap::from([("val1".to_string(), 600)])
+        );
+        assert!(governance
+            .vote(110, "rogue", proposal_id, VoteOption::Yes)
+            .is_err());
+        assert_eq!(governance.emergency_tally(proposal_id).unwrap(), (0, 600));
+        let tally = governance.tally(proposal_id).unwrap();
+        assert_eq!(tally.total_voting_power, 0);
+
+        governance
+            .vote(111, "val1", proposal_id, VoteOption::Yes)
+            .unwrap();
+        assert_eq!(governance.emergency_tally(proposal_id).unwrap(), (600, 600));
+    }
+
+    #[test]
+    fn test_emergency_proposal_needs_validator_supermajority() {
+        let (mut governance, _temp_dir) = setup_test_governance();
+        {
+            let mut staking = governance.staking.lock().unwrap();
+            staking.register_validator("val1");
+            staking.register_validator("val2");
+            staking.register_validator("val3");
+            staking.delegate("d1", "val1", 500).unwrap();
+            staking.delegate("d2", "val2", 300).unwrap();
+            staking.delegate("d3", "val3", 200).unwrap();
+        }
+        crate::runtime::supply::mint_to(
+            &mut governance.state.lock().unwrap(),
+            "proposer",
+            "udgt",
+            10_000_000_000,
+        )
+        .unwrap();
+        let submit = |governance: &mut GovernanceModule, proposal_type| {
+            governance.submit_proposal_on_track(
+                100,
+                "Emergency".to_string(),
+                "Test Description".to_string(),
+                proposal_type,
+                ProposalTrack::Emergency,
+            )
+        };
+        assert!(submit(&mut governance, ProposalType::Text).is_err());
+        let governance_pause = ProposalType::PauseModule {
+            module: "governance".to_string(),
+            paused: true,
+        };
+        assert!(submit(&mut governance, governance_pause).is_err());
+
+        let staking_pause = ProposalType::PauseModule {
+            module: "staking".to_string(),
+            paused: true,
+        };
+        let pause_id = submit(&mut governance, staking_pause).unwrap();
+        let halt_id = submit(&mut governance, ProposalType::HaltBridge { halted: true }).unwrap();
+        for id in [pause_id, halt_id] {
+            governance
+                .deposit(110, "proposer", id, 5_000_000_000, "udgt")
+                .unwrap();
         }
+
+        // Only validators vote, each with its delegated stake
+        assert!(governance
+            .vote(110, "d1", pause_id, VoteOption::Yes)
+            .is_err());
+        governance
+            .vote(110, "val1", pause_id, VoteOption::Yes)
+            .unwrap();
+        governance
+            .vote(110, "val3", halt_id, VoteOption::Yes)
+            .unwrap();
+        governance.end_block(111).unwrap();
+        assert_eq!(governance.emergency_tally(pause_id).unwrap(), (500, 1_000));
+        let proposal = governance.get_proposal(pause_id).unwrap().unwrap();
+        assert_eq!(proposal.status, ProposalStatus::VotingPeriod);
+
+        // Passing executes at once, before the voting period ends
+        governance
+            .vote(112, "val2", pause_id, VoteOption::Yes)
+            .unwrap();
+        governance.end_block(112).unwrap();
+        let proposal = governance.get_proposal(pause_id).unwrap().unwrap();
+        assert_eq!(proposal.status, ProposalStatus::Executed);
+        assert!(PauseModule::new(governance.storage.clone()).is_paused("staking"));
+
+        governance.end_block(161).unwrap();
+        let proposal = governance.get_proposal(halt_id).unwrap().unwrap();
+        assert_eq!(proposal.status, ProposalStatus::Rejected);
+        let bridge = BridgeStore {
+            db: &governance.storage.db,
+        };
+        assert!(!bridge.is_halted());
     }
 }
@@ -1,10 +1,22 @@
 pub mod bridge;
+pub mod denoms;
+pub mod distribution;
 pub mod emission;
 pub mod fee_burn;
+pub mod fee_grant;
+pub mod gas_schedule;
 pub mod governance;
+pub mod module_accounts;
 #[cfg(feature = "oracle")]
 pub mod oracle;
+pub mod params;
+pub mod pause;
+pub mod schedule;
 pub mod staking;
+pub mod supply;
+pub mod token_factory;
+pub mod upgrade;
+pub mod vesting;
 pub mod dead_man_switch;
 #[cfg(feature = "contracts")]
 pub mod wasm;
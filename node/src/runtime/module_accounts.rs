@@ -0,0 +1,158 @@
+use crate::addr::get_address;
+use crate::runtime::supply::{mint_to, SupplyModule};
+use crate::state::State;
+use crate::storage::state::Storage;
+use std::collections::BTreeMap;
+
+/// Charged transaction fees awaiting the end-block burn and distribution
+pub const FEE_COLLECTOR: &str = "fee_collector";
+/// Community tax on distributed fees
+pub const COMMUNITY_POOL: &str = "community_pool";
+/// Stake bonded through `Delegate` (and genesis delegations)
+pub const STAKING_BONDED: &str = "staking_bonded";
+/// Governance deposits not yet refunded or burned
+pub const GOV_DEPOSITS: &str = "gov_deposits";
+/// Funds escrowed by scheduled transfers
+pub const SCHEDULE_ESCROW: &str = "schedule_escrow";
+/// Native tokens locked for outbound bridge transfers
+pub const BRIDGE_ESCROW: &str = "bridge_escrow";
+/// `EmissionEngine` pools; each is backed by the `emission_{pool}` module account
+pub const EMISSION_POOLS: [&str; 4] = [
+    "block_rewards",
+    "staking_rewards",
+    "ai_module_incentives",
+    "bridge_operations",
+];
+
+/// Module account backing an emission pool
+pub fn emission_account(pool: &str) -> String {
+    format!("emission_{pool}")
+}
+
+/// Every module account name
+pub fn module_accounts() -> Vec<String> {
+    [
+        FEE_COLLECTOR,
+        COMMUNITY_POOL,
+        STAKING_BONDED,
+        GOV_DEPOSITS,
+        SCHEDULE_ESCROW,
+        BRIDGE_ESCROW,
+    ]
+    .into_iter()
+    .map(str::to_string)
+    .chain(EMISSION_POOLS.iter().map(|pool| emission_account(pool)))
+    .collect()
+}
+
+/// Address of a module account. It is derived like a user address but from a name rather
+/// than a public key, so no key can sign for it; only the runtime moves its funds.
+pub fn module_address(name: &str) -> String {
+    get_address(format!("module:{name}").as_bytes())
+}
+
+/// Name of the module account at `address`, if it is one
+pub fn module_name(address: &str) -> Option<String> {
+    module_accounts()
+        .into_iter()
+        .find(|name| module_address(name) == address)
+}
+
+pub fn module_balance(state: &mut State, module: &str, denom: &str) -> u128 {
+    state.balance_of(&module_address(module), denom)
+}
+
+/// Balances of every module account, read from storage without going through `State`
+pub fn module_balances(storage: &Storage) -> BTreeMap<String, BTreeMap<String, u128>> {
+    module_accounts()
+        .into_iter()
+        .map(|name| {
+            let balances = storage.get_balances_db(&module_address(&name));
+            (name, balances)
+        })
+        .collect()
+}
+
+fn debit(state: &mut State, address: &str, denom: &str, amount: u128) -> Result<(), String> {
+    let balance = state.balance_of(address, denom);
+    if balance < amount {
+        return Err(format!("insufficient {denom} in {address}: {balance} < {amount}"));
+    }
+    state.set_balance(address, denom, balance - amount);
+    Ok(())
+}
+
+/// Move `amount` from an account into a module account
+pub fn send_to_module(
+    state: &mut State,
+    from: &str,
+    module: &str,
+    denom: &str,
+    amount: u128,
+) -> Result<(), String> {
+    debit(state, from, denom, amount)?;
+    state.credit(&module_address(module), denom, amount);
+    Ok(())
+}
+
+/// Move `amount` out of a module account to `to`
+pub fn send_from_module(
+    state: &mut State,
+    module: &str,
+    to: &str,
+    denom: &str,
+    amount: u128,
+) -> Result<(), String> {
+    debit(state, &module_address(module), denom, amount)
+        .map_err(|_| format!("module account {module} holds less than {amount} {denom}"))?;
+    state.credit(to, denom, amount);
+    Ok(())
+}
+
+/// Create `amount` new tokens in a module account
+pub fn mint_to_module(state: &mut State, module: &str, denom: &str, amount: u128) -> Result<(), String> {
+    mint_to(state, &module_address(module), denom, amount)
+}
+
+/// Destroy `amount` held by a module account
+pub fn burn_from_module(state: &mut State, module: &str, denom: &str, amount: u128) -> Result<(), String> {
+    debit(state, &module_address(module), denom, amount)
+        .map_err(|_| format!("module account {module} holds less than {amount} {denom}"))?;
+    SupplyModule::with_store(state).burn(denom, amount)
+}
+
+/// End-block fee accounting: the block's charged fees were debited from their payers and
+/// land in the fee collector, which then burns its configured share
+pub fn collect_block_fees(state: &mut State, charged: u128, burned: u128) -> Result<(), String> {
+    state.credit(&module_address(FEE_COLLECTOR), "udgt", charged);
+    burn_from_module(state, FEE_COLLECTOR, "udgt", burned.min(charged))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::addr::validate_address;
+    use crate::runtime::supply::check_invariants;
+
+    #[test]
+    fn test_module_account_transfers_keep_supply() {
+        let mut state = State::new_for_test();
+        let fees = module_address(FEE_COLLECTOR);
+        assert!(validate_address(&fees));
+        assert_eq!(module_name(&fees).as_deref(), Some(FEE_COLLECTOR));
+        assert_eq!(module_name("dyt1someone"), None);
+
+        mint_to(&mut state, "alice", "udgt", 1_000).unwrap();
+        send_to_module(&mut state, "alice", GOV_DEPOSITS, "udgt", 400).unwrap();
+        assert!(send_from_module(&mut state, GOV_DEPOSITS, "alice", "udgt", 401).is_err());
+        send_from_module(&mut state, GOV_DEPOSITS, "alice", "udgt", 100).unwrap();
+        assert_eq!(module_balance(&mut state, GOV_DEPOSITS, "udgt"), 300);
+
+        // 50 of alice's balance paid as fees, 20 of them burned
+        state.set_balance("alice", "udgt", 650);
+        collect_block_fees(&mut state, 50, 20).unwrap();
+        assert_eq!(module_balance(&mut state, FEE_COLLECTOR, "udgt"), 30);
+        assert_eq!(SupplyModule::new(state.storage.clone()).supply("udgt"), 980);
+        assert!(check_invariants(&state.storage).iter().all(|c| c.holds()));
+    }
+}
@@ -1,5 +1,7 @@
 use crate::rpc::errors::ApiError;
 use crate::rpc::RpcContext;
+use crate::runtime::params::{load_params, parse, GovernedParams};
+use crate::runtime::pause::PauseModule;
 use crate::storage::oracle::{AiRiskRecord, OracleStore};
 use axum::{Extension, Json};
 use base64::engine::general_purpose::STANDARD as B64;
@@ -23,6 +25,36 @@ pub struct OracleAiRiskBatchInput {
     pub records: Vec<OracleAiRiskInput>,
 }
 
+/// Governed oracle parameters
+#[derive(Debug, Clone)]
+pub struct OracleParams {
+    pub max_batch_size: usize,
+}
+
+impl Default for OracleParams {
+    fn default() -> Self {
+        Self {
+            max_batch_size: 1_000,
+        }
+    }
+}
+
+impl GovernedParams for OracleParams {
+    const MODULE: &'static str = "oracle";
+
+    fn get_param(&self, key: &str) -> Option<String> {
+        (key == "oracle.max_batch_size").then(|| self.max_batch_size.to_string())
+    }
+
+    fn set_param(&mut self, key: &str, value: &str) -> Result<(), String> {
+        if key != "oracle.max_batch_size" {
+            return Err(format!("Unknown parameter: {key}"));
+        }
+        self.max_batch_size = parse(key, value)?;
+        Ok(())
+    }
+}
+
 /// Verify ML-DSA-87 (Dilithium5) signature for oracle data
 ///
 /// # Arguments
@@ -147,6 +179,10 @@ pub async fn post_ai_risk(
 ) -> Result<Json<serde_json::Value>, ApiError> {
     let start_time = std::time::SystemTime::now();
 
+    PauseModule::new(ctx.storage.clone())
+        .ensure_active("oracle")
+        .map_err(ApiError::BadRequest)?;
+
     if !(0.0..=1.0).contains(&inp.risk_score) {
         ctx.metrics.record_oracle_submission("error");
         return Err(ApiError::Internal);
@@ -204,6 +240,19 @@ pub async fn post_ai_risk_batch(
 ) -> Result<Json<serde_json::Value>, ApiError> {
     let start_time = std::time::SystemTime::now();
 
+    PauseModule::new(ctx.storage.clone())
+        .ensure_active("oracle")
+        .map_err(ApiError::BadRequest)?;
+    let params: OracleParams = load_params(ctx.storage.clone());
+    if inp.records.len() > params.max_batch_size {
+        ctx.metrics.record_oracle_submission("error");
+        return Err(ApiError::BadRequest(format!(
+            "batch of {} records exceeds oracle.max_batch_size {}",
+            inp.records.len(),
+            params.max_batch_size
+        )));
+    }
+
     let configured_pk = std::env::var("AI_ORACLE_PUBKEY").ok();
     let store = OracleStore {
         db: &ctx.storage.db,
@@ -0,0 +1,415 @@
+use crate::state::KvStore;
+use crate::storage::state::Storage;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Type and bounds of a governed parameter's value
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ParamKind {
+    /// Unsigned integer in `min..=max`
+    Uint {
+        min: u128,
+        max: u128,
+    },
+    /// Decimal fraction in `0.0..=1.0`
+    Fraction,
+    Bool,
+    /// `parts` comma-separated unsigned shares summing to `total`
+    Shares {
+        parts: usize,
+        total: u128,
+    },
+    /// `numerator/denominator` with `0 < numerator <= denominator`
+    Ratio,
+}
+
+impl ParamKind {
+    pub fn check(&self, value: &str) -> Result<(), String> {
+        match *self {
+            ParamKind::Uint { min, max } => {
+                let v: u128 = value
+                    .parse()
+                    .map_err(|_| "must be an unsigned integer".to_string())?;
+                if !(min..=max).contains(&v) {
+                    return Err(format!("must be between {min} and {max}"));
+                }
+            }
+            ParamKind::Fraction => {
+                let v: f64 = value
+                    .parse()
+                    .map_err(|_| "must be a decimal fraction".to_string())?;
+                if !(0.0..=1.0).contains(&v) {
+                    return Err("must be between 0.0 and 1.0".to_string());
+                }
+            }
+            ParamKind::Bool => {
+                value
+                    .parse::<bool>()
+                    .map_err(|_| "must be true or false".to_string())?;
+            }
+            ParamKind::Shares { parts, total } => {
+                let shares = parse_shares(value)?;
+                if shares.len() != parts {
+                    return Err(format!("must have {parts} comma-separated shares"));
+                }
+                if shares.iter().try_fold(0u128, |acc, s| acc.checked_add(*s)) != Some(total) {
+                    return Err(format!("shares must sum to {total}"));
+                }
+            }
+            ParamKind::Ratio => {
+                let (numerator, denominator) = parse_ratio(value)?;
+                if numerator == 0 || numerator > denominator {
+                    return Err("must satisfy 0 < numerator <= denominator".to_string());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A parameter some module lets governance change
+#[derive(Debug, Serialize)]
+pub struct ParamSpec {
+    pub key: &'static str,
+    /// Module that owns the value (see `GovernedParams::MODULE`)
+    pub module: &'static str,
+    #[serde(flatten)]
+    pub kind: ParamKind,
+    pub description: &'static str,
+}
+
+impl ParamSpec {
+    pub fn check(&self, value: &str) -> Result<(), String> {
+        self.kind
+            .check(value)
+            .map_err(|e| format!("Invalid {}: {e}", self.key))
+    }
+}
+
+const fn uint(
+    key: &'static str,
+    module: &'static str,
+    min: u128,
+    max: u128,
+    description: &'static str,
+) -> ParamSpec {
+    ParamSpec {
+        key,
+        module,
+        kind: ParamKind::Uint { min, max },
+        description,
+    }
+}
+
+const BPS: u128 = 10_000;
+const GAS_MAX: u128 = 10_000_000;
+
+/// Every governed parameter. A module adds its parameters here and implements
+/// `GovernedParams` for the struct holding their values.
+pub const PARAMS: &[ParamSpec] = &[
+    // governance
+    uint("gas_limit", "governance", 1_000, 100_000_000, "Default transaction gas limit"),
+    uint("consensus.max_gas_per_block", "governance", 1_000_000, 1_000_000_000, "Maximum gas per block"),
+    uint("governance.min_deposit", "governance", 1, u128::MAX, "Deposit (udgt) a proposal needs to enter voting"),
+    uint("governance.deposit_period", "governance", 1, 1_000_000, "Deposit period in blocks"),
+    uint("governance.voting_period", "governance", 1, 1_000_000, "Voting period in blocks"),
+    uint("governance.quorum", "governance", 0, BPS, "Participation required, in basis points of voting power"),
+    uint("governance.threshold", "governance", 0, BPS, "Yes share required to pass, in basis points"),
+    uint("governance.veto_threshold", "governance", 0, BPS, "NoWithVeto share that vetoes, in basis points"),
+    ParamSpec {
+        key: "governance.liquid_balance_voting",
+        module: "governance",
+        kind: ParamKind::Bool,
+        description: "Accounts without stake vote with their liquid udgt",
+    },
+    uint("governance.expedited_min_deposit", "governance", 1, u128::MAX, "Deposit (udgt) an expedited or emergency proposal needs to enter voting"),
+    uint("governance.expedited_voting_period", "governance", 1, 1_000_000, "Voting period of expedited proposals in blocks"),
+    uint("governance.expedited_threshold", "governance", 0, BPS, "Yes share an expedited proposal needs to pass, in basis points"),
+    uint("governance.emergency_voting_period", "governance", 1, 1_000_000, "Blocks validators have to pass an emergency proposal"),
+    uint("governance.emergency_threshold", "governance", 0, BPS, "Share of validator-delegated stake that must vote yes on an emergency proposal, in basis points"),
+    // staking
+    ParamSpec {
+        key: "staking_reward_rate",
+        module: "staking",
+        kind: ParamKind::Fraction,
+        description: "Per-block staking reward rate",
+    },
+    // mempool
+    uint("mempool.max_tx_bytes", "mempool", 1_024, 16 * 1024 * 1024, "Largest accepted transaction, in bytes"),
+    uint("mempool.min_gas_price", "mempool", 0, u64::MAX as u128, "Lowest accepted gas price"),
+    uint("mempool.max_txs", "mempool", 1, 1_000_000, "Maximum pending transactions"),
+    uint("mempool.max_bytes", "mempool", 1024 * 1024, 1024 * 1024 * 1024, "Maximum pending bytes"),
+    // gas schedule (a change adds a gas schedule version rather than a `params:` value)
+    uint("gas.transfer_base", "gas", 0, GAS_MAX, "Intrinsic gas of a transfer"),
+    uint("gas.governance_proposal_create_base", "gas", 0, GAS_MAX, "Intrinsic gas of a proposal submission"),
+    uint("gas.governance_vote_base", "gas", 0, GAS_MAX, "Intrinsic gas of a vote"),
+    uint("gas.staking_delegate_base", "gas", 0, GAS_MAX, "Intrinsic gas of a delegation"),
+    uint("gas.staking_undelegate_base", "gas", 0, GAS_MAX, "Intrinsic gas of an undelegation"),
+    uint("gas.oracle_publish_base", "gas", 0, GAS_MAX, "Intrinsic gas of an oracle publish"),
+    uint("gas.contract_instantiate_base", "gas", 0, GAS_MAX, "Intrinsic gas of a contract instantiation"),
+    uint("gas.contract_call_base", "gas", 0, GAS_MAX, "Intrinsic gas of a contract call"),
+    uint("gas.contract_migrate_base", "gas", 0, GAS_MAX, "Intrinsic gas of a contract migration"),
+    uint("gas.per_byte", "gas", 0, GAS_MAX, "Gas per encoded transaction byte"),
+    uint("gas.per_additional_signature", "gas", 0, GAS_MAX, "Gas per signature beyond the first"),
+    uint("gas.per_kv_read", "gas", 0, GAS_MAX, "Gas per state read"),
+    uint("gas.per_kv_write", "gas", 0, GAS_MAX, "Gas per state write"),
+    uint("gas.per_event", "gas", 0, GAS_MAX, "Gas per emitted event"),
+    uint("gas.per_vm_instruction", "gas", 0, GAS_MAX, "Gas per contract VM instruction"),
+    uint("gas.dms_register_base", "gas", 0, GAS_MAX, "Gas of a dead man's switch registration"),
+    uint("gas.dms_ping_base", "gas", 0, GAS_MAX, "Gas of a dead man's switch ping"),
+    uint("gas.dms_claim_base", "gas", 0, GAS_MAX, "Gas of a dead man's switch claim"),
+    uint("gas.min_gas_charge_bps", "gas", 0, BPS, "Share of the gas limit a tx is charged at least"),
+    // fee burn
+    uint("fee_burn.burn_rate_bps", "fee_burn", 0, BPS, "Share of fees burned, in basis points"),
+    uint("fee_burn.min_burn_threshold", "fee_burn", 0, u128::MAX, "Smallest fee that is burned from"),
+    ParamSpec {
+        key: "fee_burn.enabled",
+        module: "fee_burn",
+        kind: ParamKind::Bool,
+        description: "Whether fees are burned",
+    },
+    // emission
+    ParamSpec {
+        key: "emission.breakdown",
+        module: "emission",
+        kind: ParamKind::Shares {
+            parts: 4,
+            total: 100,
+        },
+        description: "Percent of emission to block_rewards,staking_rewards,ai_module_incentives,bridge_operations",
+    },
+    // bridge
+    ParamSpec {
+        key: "bridge.quorum",
+        module: "bridge",
+        kind: ParamKind::Ratio,
+        description: "Share of bridge validators that must sign a message",
+    },
+    // oracle
+    uint("oracle.max_batch_size", "oracle", 1, 100_000, "Most records accepted in one AI risk batch"),
+    // dead man switch
+    uint("dms.min_period_blocks", "dms", 1, u64::MAX as u128, "Shortest inactivity period of a dead man switch"),
+];
+
+/// Registered spec of `key`
+pub fn spec(key: &str) -> Result<&'static ParamSpec, String> {
+    PARAMS.iter().find(|spec| spec.key == key).ok_or_else(|| {
+        format!(
+            "Parameter '{}' is not governable. Allowed parameters: {:?}",
+            key,
+            PARAMS.iter().map(|spec| spec.key).collect::<Vec<_>>()
+        )
+    })
+}
+
+/// Parse a value that already passed `ParamSpec::check`
+pub fn parse<T: std::str::FromStr>(key: &str, value: &str) -> Result<T, String> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| format!("Invalid {key}: {value}"))
+}
+
+pub fn parse_shares(value: &str) -> Result<Vec<u128>, String> {
+    value
+        .split(',')
+        .map(|s| {
+            s.trim()
+                .parse::<u128>()
+                .map_err(|_| "shares must be unsigned integers".to_string())
+        })
+        .collect()
+}
+
+pub fn parse_ratio(value: &str) -> Result<(u64, u64), String> {
+    let (numerator, denominator) = value
+        .split_once('/')
+        .ok_or_else(|| "must be numerator/denominator".to_string())?;
+    let parse = |s: &str| {
+        s.trim()
+            .parse::<u64>()
+            .map_err(|_| "numerator and denominator must be unsigned integers".to_string())
+    };
+    Ok((parse(numerator)?, parse(denominator)?))
+}
+
+/// Holder of a module's governed parameter values
+pub trait GovernedParams {
+    /// `ParamSpec::module` of the parameters this type holds
+    const MODULE: &'static str;
+
+    fn get_param(&self, key: &str) -> Option<String>;
+
+    /// Set a value that already passed `ParamSpec::check`
+    fn set_param(&mut self, key: &str, value: &str) -> Result<(), String>;
+}
+
+/// Apply the governed values of `target`'s module
+pub fn apply_params<T: GovernedParams>(
+    target: &mut T,
+    values: &BTreeMap<String, String>,
+) -> Result<(), String> {
+    for spec in PARAMS.iter().filter(|spec| spec.module == T::MODULE) {
+        if let Some(value) = values.get(spec.key) {
+            target.set_param(spec.key, value)?;
+        }
+    }
+    Ok(())
+}
+
+/// Module defaults with the governed values applied
+pub fn load_params<T: GovernedParams + Default, K: KvStore>(kv: K) -> T {
+    let mut params = T::default();
+    // Stored values were checked when governance set them
+    let _ = apply_params(&mut params, &ParamsModule::with_store(kv).all());
+    params
+}
+
+/// A registered parameter with the value the next block uses
+#[derive(Debug, Serialize)]
+pub struct ParamValue {
+    #[serde(flatten)]
+    pub spec: &'static ParamSpec,
+    pub value: String,
+}
+
+/// Current values of `live`'s module: the governed value if one is set, otherwise the
+/// module's own configuration
+pub fn current_values<T: GovernedParams>(
+    live: &T,
+    values: &BTreeMap<String, String>,
+) -> Vec<ParamValue> {
+    PARAMS
+        .iter()
+        .filter(|spec| spec.module == T::MODULE)
+        .map(|spec| ParamValue {
+            spec,
+            value: values
+                .get(spec.key)
+                .cloned()
+                .or_else(|| live.get_param(spec.key))
+                .unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// Parameter values set by governance. Modules keep their own configuration; the node
+/// applies these values to them when a block begins, so a change made while executing
+/// block N takes effect from block N+1.
+pub struct ParamsModule<K = Arc<Storage>> {
+    kv: K,
+}
+
+// Key layout:
+//   params:{key}  -> UTF-8 value (only parameters governance has changed)
+impl ParamsModule {
+    pub fn new(storage: Arc<Storage>) -> Self {
+        Self { kv: storage }
+    }
+}
+
+impl<K: KvStore> ParamsModule<K> {
+    pub fn with_store(kv: K) -> Self {
+        Self { kv }
+    }
+
+    pub fn get(&self, key: &str) -> Option<String> {
+        self.kv
+            .kv_get(&format!("params:{key}"))
+            .and_then(|v| String::from_utf8(v).ok())
+    }
+
+    /// Check `value` against the registry and store it
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        spec(key)?.check(value)?;
+        self.kv
+            .kv_put(&format!("params:{key}"), value.as_bytes().to_vec())
+    }
+
+    /// Every governed value, by key
+    pub fn all(&self) -> BTreeMap<String, String> {
+        self.kv
+            .kv_scan_prefix("params:")
+            .into_iter()
+            .filter_map(|(k, v)| {
+                Some((
+                    k.trim_start_matches("params:").to_string(),
+                    String::from_utf8(v).ok()?,
+                ))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gas::GasSchedule;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_registry_checks_type_and_bounds() {
+        let dir = tempdir().unwrap();
+        let storage = Arc::new(Storage::open(dir.path().join("test.db")).unwrap());
+        let mut params = ParamsModule::new(storage.clone());
+
+        assert!(params.set("unknown.key", "1").is_err());
+        assert!(params.set("fee_burn.burn_rate_bps", "10001").is_err());
+        assert!(params.set("fee_burn.burn_token", "uabc").is_err());
+        assert!(params.set("emission.breakdown", "60,25,10,6").is_err());
+        assert!(params.set("bridge.quorum", "4/3").is_err());
+        assert!(params.set("fee_burn.enabled", "yes").is_err());
+        assert!(params.all().is_empty());
+
+        params.set("gas.transfer_base", "700").unwrap();
+        params.set("emission.breakdown", "50,30,10,10").unwrap();
+        let schedule: GasSchedule = load_params(storage.clone());
+        assert_eq!(schedule.transfer_base, 700);
+        assert_eq!(schedule.per_byte, GasSchedule::default().per_byte);
+
+        let values = current_values(&GasSchedule::default(), &params.all());
+        let transfer = values
+            .iter()
+            .find(|v| v.spec.key == "gas.transfer_base")
+            .unwrap();
+        assert_eq!(transfer.value, "700");
+        let per_byte = values
+            .iter()
+            .find(|v| v.spec.key == "gas.per_byte")
+            .unwrap();
+        assert_eq!(per_byte.value, GasSchedule::default().per_byte.to_string());
+    }
+
+    #[test]
+    fn test_every_param_has_a_holder() {
+        use crate::mempool::MempoolConfig;
+        use crate::runtime::emission::EmissionBreakdown;
+        use crate::runtime::fee_burn::FeeBurnConfig;
+        use crate::runtime::governance::GovernanceConfig;
+
+        fn held<T: GovernedParams>(live: &T) -> Vec<&'static str> {
+            PARAMS
+                .iter()
+                .filter(|spec| spec.module == T::MODULE && live.get_param(spec.key).is_some())
+                .map(|spec| spec.key)
+                .collect()
+        }
+        let dir = tempdir().unwrap();
+        let storage = Arc::new(Storage::open(dir.path().join("test.db")).unwrap());
+        let mut keys = held(&GovernanceConfig::default());
+        keys.extend(held(&crate::runtime::staking::StakingModule::new(storage)));
+        keys.extend(held(&MempoolConfig::default()));
+        keys.extend(held(&GasSchedule::default()));
+        keys.extend(held(&FeeBurnConfig::default()));
+        keys.extend(held(&EmissionBreakdown::default()));
+        keys.extend(held(&crate::runtime::bridge::BridgeParams::default()));
+        keys.extend(held(&crate::runtime::dead_man_switch::DmsParams::default()));
+        #[cfg(feature = "oracle")]
+        keys.extend(held(&crate::runtime::oracle::OracleParams::default()));
+        #[cfg(not(feature = "oracle"))]
+        keys.push("oracle.max_batch_size");
+        assert_eq!(keys.len(), PARAMS.len());
+    }
+}
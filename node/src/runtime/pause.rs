@@ -0,0 +1,77 @@
+use crate::state::KvStore;
+use crate::storage::state::Storage;
+use std::sync::Arc;
+
+/// Modules an emergency governance proposal can pause. Governance and plain transfers are
+/// left out so the chain can always vote to unpause; the bridge has its own halt switch.
+pub const PAUSABLE_MODULES: &[&str] = &[
+    "staking",
+    "dms",
+    "fee_grant",
+    "schedule",
+    "token_factory",
+    "contracts",
+    "oracle",
+];
+
+/// Reject names that are not in `PAUSABLE_MODULES`
+pub fn check_pausable(module: &str) -> Result<(), String> {
+    if PAUSABLE_MODULES.contains(&module) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Module '{module}' cannot be paused. Pausable modules: {PAUSABLE_MODULES:?}"
+        ))
+    }
+}
+
+/// Module pause switches set by governance
+pub struct PauseModule<K = Arc<Storage>> {
+    kv: K,
+}
+
+// Key layout:
+//   pause:{module}  -> 1 while the module is paused (deleted on unpause)
+impl PauseModule {
+    pub fn new(storage: Arc<Storage>) -> Self {
+        Self { kv: storage }
+    }
+}
+
+impl<K: KvStore> PauseModule<K> {
+    pub fn with_store(kv: K) -> Self {
+        Self { kv }
+    }
+
+    pub fn is_paused(&self, module: &str) -> bool {
+        self.kv.kv_get(&format!("pause:{module}")).is_some()
+    }
+
+    pub fn set_paused(&mut self, module: &str, paused: bool) -> Result<(), String> {
+        check_pausable(module)?;
+        let key = format!("pause:{module}");
+        if paused {
+            self.kv.kv_put(&key, vec![1])
+        } else {
+            self.kv.kv_delete(&key)
+        }
+    }
+
+    /// Paused modules in key order
+    pub fn paused(&self) -> Vec<String> {
+        self.kv
+            .kv_scan_prefix("pause:")
+            .into_iter()
+            .map(|(k, _)| k.trim_start_matches("pause:").to_string())
+            .collect()
+    }
+
+    /// Error for work routed to `module` while it is paused
+    pub fn ensure_active(&self, module: &str) -> Result<(), String> {
+        if self.is_paused(module) {
+            Err(format!("module {module} is paused by governance"))
+        } else {
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,235 @@
+use crate::runtime::module_accounts::{send_from_module, SCHEDULE_ESCROW};
+use crate::state::{KvStore, State};
+use crate::storage::events::Event;
+use crate::storage::state::Storage;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Funds escrowed by a `ScheduleTransfer` message until `release_height`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScheduledTransfer {
+    pub id: u64,
+    pub from: String,
+    pub to: String,
+    pub denom: String,
+    pub amount: u128,
+    /// First block height at which the funds are released to `to`
+    pub release_height: u64,
+    /// Whether `from` may cancel (and get the funds back) before release
+    pub cancellable: bool,
+    pub created_height: u64,
+}
+
+/// Scheduled transfer module over a KV store (see `DeadManSwitchModule`). It only keeps the
+/// schedule records; the caller moves the escrowed balances in and out of the
+/// `schedule_escrow` module account.
+pub struct ScheduleModule<K = Arc<Storage>> {
+    kv: K,
+}
+
+// Key layout:
+//   sched:{id:016x}                      -> bincode(ScheduledTransfer)
+//   sched_due:{release:016x}:{id:016x}   -> [] (release queue)
+//   sched_addr:{address}:{id:016x}       -> [] (sender and recipient index)
+const NEXT_ID_KEY: &str = "sched_next_id";
+
+impl ScheduleModule {
+    pub fn new(storage: Arc<Storage>) -> Self {
+        Self { kv: storage }
+    }
+}
+
+impl<K: KvStore> ScheduleModule<K> {
+    pub fn with_store(kv: K) -> Self {
+        Self { kv }
+    }
+
+    fn key(id: u64) -> String {
+        format!("sched:{id:016x}")
+    }
+
+    fn due_key(release_height: u64, id: u64) -> String {
+        format!("sched_due:{release_height:016x}:{id:016x}")
+    }
+
+    fn addr_key(address: &str, id: u64) -> String {
+        format!("sched_addr:{address}:{id:016x}")
+    }
+
+    pub fn get(&self, id: u64) -> Option<ScheduledTransfer> {
+        self.kv
+            .kv_get(&Self::key(id))
+            .and_then(|v| bincode::deserialize(&v).ok())
+    }
+
+    /// Pending schedules where `address` is the sender or the recipient, oldest first
+    pub fn schedules_for(&self, address: &str) -> Vec<ScheduledTransfer> {
+        self.kv
+            .kv_scan_prefix(&format!("sched_addr:{address}:"))
+            .into_iter()
+            .filter_map(|(k, _)| {
+                let id = k.rsplit(':').next()?;
+                self.get(u64::from_str_radix(id, 16).ok()?)
+            })
+            .collect()
+    }
+
+    /// Schedules whose release height is at or below `height`, in release order
+    pub fn due(&self, height: u64) -> Vec<ScheduledTransfer> {
+        self.kv
+            .kv_scan_prefix("sched_due:")
+            .into_iter()
+            .filter_map(|(k, _)| {
+                let mut parts = k.trim_start_matches("sched_due:").split(':');
+                let release = u64::from_str_radix(parts.next()?, 16).ok()?;
+                let id = u64::from_str_radix(parts.next()?, 16).ok()?;
+                Some((release, id))
+            })
+            .take_while(|(release, _)| *release <= height)
+            .filter_map(|(_, id)| self.get(id))
+            .collect()
+    }
+
+    /// Record a new schedule; the caller has already debited `amount` from `from`
+    #[allow(clippy::too_many_arguments)]
+    pub fn create(
+        &mut self,
+        from: &str,
+        to: &str,
+        denom: &str,
+        amount: u128,
+        release_height: u64,
+        cancellable: bool,
+        current_height: u64,
+    ) -> Result<ScheduledTransfer, String> {
+        if amount == 0 {
+            return Err("Scheduled amount must be greater than 0".to_string());
+        }
+        if from == to {
+            return Err("Cannot schedule a transfer to yourself".to_string());
+        }
+        if release_height <= current_height {
+            return Err(format!(
+                "Release height {release_height} must be above the current height {current_height}"
+            ));
+        }
+        let id = self
+            .kv
+            .kv_get(NEXT_ID_KEY)
+            .and_then(|v| v.try_into().ok().map(u64::from_be_bytes))
+            .unwrap_or(1);
+        let schedule = ScheduledTransfer {
+            id,
+            from: from.to_string(),
+            to: to.to_string(),
+            denom: denom.to_string(),
+            amount,
+            release_height,
+            cancellable,
+            created_height: current_height,
+        };
+        self.kv
+            .kv_put(NEXT_ID_KEY, (id + 1).to_be_bytes().to_vec())?;
+        self.kv.kv_put(
+            &Self::key(id),
+            bincode::serialize(&schedule).map_err(|e| e.to_string())?,
+        )?;
+        self.kv.kv_put(&Self::due_key(release_height, id), Vec::new())?;
+        self.kv.kv_put(&Self::addr_key(from, id), Vec::new())?;
+        self.kv.kv_put(&Self::addr_key(to, id), Vec::new())?;
+        Ok(schedule)
+    }
+
+    /// Cancel a schedule before it matures; the caller refunds the returned amount to the sender
+    pub fn cancel(
+        &mut self,
+        sender: &str,
+        id: u64,
+        current_height: u64,
+    ) -> Result<ScheduledTransfer, String> {
+        let schedule = self.get(id).ok_or_else(|| format!("Schedule {id} not found"))?;
+        if schedule.from != sender {
+            return Err("Only the sender can cancel a schedule".to_string());
+        }
+        if !schedule.cancellable {
+            return Err(format!("Schedule {id} is not cancellable"));
+        }
+        if current_height >= schedule.release_height {
+            return Err(format!("Schedule {id} has already matured"));
+        }
+        self.remove(&schedule)?;
+        Ok(schedule)
+    }
+
+    fn remove(&mut self, schedule: &ScheduledTransfer) -> Result<(), String> {
+        self.kv.kv_delete(&Self::key(schedule.id))?;
+        self.kv
+            .kv_delete(&Self::due_key(schedule.release_height, schedule.id))?;
+        self.kv.kv_delete(&Self::addr_key(&schedule.from, schedule.id))?;
+        self.kv.kv_delete(&Self::addr_key(&schedule.to, schedule.id))
+    }
+}
+
+/// Begin-block hook: release every schedule maturing at or before `height` from escrow to its
+/// recipient. Returns one `schedule_release` event per released schedule.
+pub fn release_matured(state: &mut State, height: u64) -> Result<Vec<Event>, String> {
+    let released = {
+        let mut schedules = ScheduleModule::with_store(&mut *state);
+        let due = schedules.due(height);
+        for schedule in &due {
+            schedules.remove(schedule)?;
+        }
+        due
+    };
+    released
+        .into_iter()
+        .map(|s| {
+            send_from_module(state, SCHEDULE_ESCROW, &s.to, &s.denom, s.amount)?;
+            Ok(Event::new("schedule_release")
+                .attr("id", s.id)
+                .attr("from", &s.from)
+                .attr("to", &s.to)
+                .attr("amount", s.amount)
+                .attr("denom", &s.denom))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_create_cancel_and_release() {
+        let dir = tempdir().unwrap();
+        let storage = Arc::new(Storage::open(dir.path().join("test.db")).unwrap());
+        let mut state = State::new(storage.clone());
+        let mut schedules = ScheduleModule::new(storage.clone());
+        // Escrow for the two schedules below, as `ScheduleTransfer` would have moved it
+        let escrow = crate::runtime::module_accounts::module_address(SCHEDULE_ESCROW);
+        state.credit(&escrow, "udgt", 150);
+
+        assert!(schedules.create("alice", "bob", "udgt", 100, 10, true, 10).is_err());
+        let first = schedules.create("alice", "bob", "udgt", 100, 20, true, 10).unwrap();
+        let second = schedules.create("alice", "carol", "udgt", 50, 15, false, 10).unwrap();
+        assert_eq!((first.id, second.id), (1, 2));
+        assert_eq!(schedules.schedules_for("alice").len(), 2);
+        assert_eq!(schedules.schedules_for("bob"), vec![first.clone()]);
+
+        // Non-cancellable and foreign schedules cannot be cancelled
+        assert!(schedules.cancel("alice", second.id, 11).is_err());
+        assert!(schedules.cancel("bob", first.id, 11).is_err());
+
+        // Only the schedule maturing by height 15 is released
+        let events = release_matured(&mut state, 15).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].get("to"), Some("carol"));
+        assert_eq!(state.balance_of("carol", "udgt"), 50);
+        assert!(schedules.get(second.id).is_none());
+
+        assert_eq!(schedules.cancel("alice", first.id, 19).unwrap(), first);
+        assert!(schedules.schedules_for("bob").is_empty());
+        assert!(release_matured(&mut state, 100).unwrap().is_empty());
+    }
+}
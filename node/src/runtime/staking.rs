@@ -1,3 +1,5 @@
+use crate::runtime::params::{parse, GovernedParams};
+use crate::state::KvStore;
 use crate::storage::state::Storage;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -16,186 +18,186 @@ pub struct DelegatorRewardRecord {
     pub stake_amount: u128,
 }
 
-/// Simplified staking state for lean-launch node
+/// Simplified staking state for lean-launch node. Nothing is cached in memory, so a module
+/// built over a transaction's state overlay is reverted with it.
 #[derive(Debug, Clone)]
-pub struct StakingModule {
-    pub storage: Arc<Storage>,
-    /// Total stake across all validators (in uDGT)
-    pub total_stake: u128,
-    /// Global reward index (scaled by REWARD_SCALE)
-    pub reward_index: u128,
-    /// Pending staking emission when no stake exists
-    pub pending_staking_emission: u128,
-    /// Carry-over remainder of scaled emission not yet reflected in reward_index
-    /// This value is in units of (uDRT * REWARD_SCALE) modulo total_stake at last update,
-    /// but can be safely carried across stake changes as a count of leftover scaled units.
-    pub reward_index_residual: u128,
-    /// Governable per-block staking reward rate in basis points (scaled by 1e4). Example: 500 = 0.05 (5%).
-    pub reward_rate_bps: u64,
+pub struct StakingModule<K = Arc<Storage>> {
+    kv: K,
 }
 
+// Key layout:
+//   staking:total_stake                         -> total stake across all validators (uDGT)
+//   staking:reward_index                        -> global reward index (scaled by REWARD_SCALE)
+//   staking:pending_emission                    -> emission received while nothing was staked
+//   staking:reward_residual                     -> scaled emission not yet in reward_index
+//   staking:reward_rate_bps                     -> governable reward rate (u64, default 500)
+//   staking:delegator:{address}                 -> DelegatorRewardRecord
+//   staking:validator:{validator}               -> stake bonded to the registered validator
+//   staking:delegation:{delegator}:{validator}  -> stake the delegator bonded to the validator
 impl StakingModule {
     pub fn new(storage: Arc<Storage>) -> Self {
-        // Load existing state from storage
-        let total_stake = storage
-            .db
-            .get("staking:total_stake")
-            .ok()
-            .flatten()
-            .and_then(|v| bincode::deserialize::<u128>(&v).ok())
-            .unwrap_or(0);
+        Self { kv: storage }
+    }
+}
 
-        let reward_index = storage
-            .db
-            .get("staking:reward_index")
-            .ok()
-            .flatten()
-            .and_then(|v| bincode::deserialize::<u128>(&v).ok())
-            .unwrap_or(0);
+impl<K: KvStore> GovernedParams for StakingModule<K> {
+    const MODULE: &'static str = "staking";
 
-        let pending_staking_emission = storage
-            .db
-            .get("staking:pending_emission")
-            .ok()
-            .flatten()
-            .and_then(|v| bincode::deserialize::<u128>(&v).ok())
-            .unwrap_or(0);
+    fn get_param(&self, key: &str) -> Option<String> {
+        // Decimal fraction, e.g. "0.0500" for 500 bps
+        (key == "staking_reward_rate")
+            .then(|| format!("{:.4}", self.get_reward_rate_bps() as f64 / 10_000.0))
+    }
 
-        let reward_index_residual = storage
-            .db
-            .get("staking:reward_residual")
-            .ok()
-            .flatten()
-            .and_then(|v| bincode::deserialize::<u128>(&v).ok())
-            .unwrap_or(0);
-        // Load reward rate (default 0.05 = 500 bps)
-        let reward_rate_bps = storage
-            .db
-            .get("staking:reward_rate_bps")
-            .ok()
-            .flatten()
-            .and_then(|v| bincode::deserialize::<u64>(&v).ok())
-            .unwrap_or(500);
-
-        Self {
-            storage,
-            total_stake,
-            reward_index,
-            pending_staking_emission,
-            reward_index_residual,
-            reward_rate_bps,
+    fn set_param(&mut self, key: &str, value: &str) -> Result<(), String> {
+        if key != "staking_reward_rate" {
+            return Err(format!("Unknown parameter: {key}"));
+        }
+        let rate: f64 = parse(key, value)?;
+        let bps = (rate * 10_000.0).round() as u64;
+        if bps != self.get_reward_rate_bps() {
+            self.set_reward_rate_bps(bps);
         }
+        Ok(())
+    }
+}
+
+impl<K: KvStore> StakingModule<K> {
+    pub fn with_store(kv: K) -> Self {
+        Self { kv }
+    }
+
+    fn load_u128(&self, key: &str) -> u128 {
+        self.kv
+            .kv_get(key)
+            .and_then(|v| bincode::deserialize::<u128>(&v).ok())
+            .unwrap_or(0)
+    }
+
+    fn save_u128(&mut self, key: &str, value: u128) {
+        let _ = self.kv.kv_put(key, bincode::serialize(&value).unwrap());
+    }
+
+    /// Total stake across all validators (in uDGT)
+    pub fn total_stake(&self) -> u128 {
+        self.load_u128("staking:total_stake")
+    }
+
+    /// Global reward index (scaled by REWARD_SCALE)
+    pub fn reward_index(&self) -> u128 {
+        self.load_u128("staking:reward_index")
+    }
+
+    /// Pending staking emission when no stake exists
+    pub fn pending_staking_emission(&self) -> u128 {
+        self.load_u128("staking:pending_emission")
+    }
+
+    /// Carry-over remainder of scaled emission not yet reflected in reward_index.
+    /// This value is in units of (uDRT * REWARD_SCALE) modulo total_stake at last update,
+    /// but can be safely carried across stake changes as a count of leftover scaled units.
+    pub fn reward_index_residual(&self) -> u128 {
+        self.load_u128("staking:reward_residual")
     }
 
     /// Set new staking reward rate (basis points)
     pub fn set_reward_rate_bps(&mut self, new_bps: u64) {
-        self.reward_rate_bps = new_bps;
-        let _ = self.storage.db.put(
+        let _ = self.kv.kv_put(
             "staking:reward_rate_bps",
             bincode::serialize(&new_bps).unwrap(),
         );
     }
 
-    /// Get current staking reward rate (bps)
+    /// Get current staking reward rate (bps); governable per-block rate scaled by 1e4,
+    /// e.g. 500 = 0.05 (5%)
     pub fn get_reward_rate_bps(&self) -> u64 {
-        self.reward_rate_bps
+        self.kv
+            .kv_get("staking:reward_rate_bps")
+            .and_then(|v| bincode::deserialize::<u64>(&v).ok())
+            .unwrap_or(500)
     }
 
     /// Apply external emission from emission engine
     /// If total_stake > 0, update reward_index proportionally
     /// If total_stake == 0, accumulate in pending_staking_emission
     pub fn apply_external_emission(&mut self, amount: u128) {
-        if self.total_stake > 0 {
-            // Distribute current emission using carry-aware division for precision
-            let scaled = amount.saturating_mul(REWARD_SCALE);
-            let numerator = self.reward_index_residual.saturating_add(scaled);
-            let reward_per_unit = numerator / self.total_stake;
-            self.reward_index = self.reward_index.saturating_add(reward_per_unit);
-            self.reward_index_residual = numerator % self.total_stake;
-
-            // Apply any pending emission too using the same residual carry
-            if self.pending_staking_emission > 0 {
-                let scaled_pending = self.pending_staking_emission.saturating_mul(REWARD_SCALE);
-                let numerator_pending = self.reward_index_residual.saturating_add(scaled_pending);
-                let pending_per_unit = numerator_pending / self.total_stake;
-                self.reward_index = self.reward_index.saturating_add(pending_per_unit);
-                self.reward_index_residual = numerator_pending % self.total_stake;
-                self.pending_staking_emission = 0;
-                self.save_pending_emission();
-            }
+        let total_stake = self.total_stake();
+        if total_stake == 0 {
+            // No stake yet, accumulate for later distribution
+            let pending = self.pending_staking_emission().saturating_add(amount);
+            self.save_u128("staking:pending_emission", pending);
+            return;
+        }
+
+        // Distribute current emission using carry-aware division for precision
+        let mut reward_index = self.reward_index();
+        let scaled = amount.saturating_mul(REWARD_SCALE);
+        let numerator = self.reward_index_residual().saturating_add(scaled);
+        reward_index = reward_index.saturating_add(numerator / total_stake);
+        let mut residual = numerator % total_stake;
+
+        // Apply any pending emission too using the same residual carry
+        let pending = self.pending_staking_emission();
+        if pending > 0 {
+            let scaled_pending = pending.saturating_mul(REWARD_SCALE);
+            let numerator_pending = residual.saturating_add(scaled_pending);
+            reward_index = reward_index.saturating_add(numerator_pending / total_stake);
+            residual = numerator_pending % total_stake;
+            self.save_u128("staking:pending_emission", 0);
+        }
 
-            self.save_reward_index();
-            self.save_reward_residual();
-
-            // Record reward_index_after in the latest emission event for observability
-            let latest_height = {
-                // duplicated function logic to avoid a direct dependency on emission.rs
-                self.storage
-                    .db
-                    .get("emission:last_height")
-                    .ok()
-                    .flatten()
-                    .and_then(|v| {
-                        if v.len() == 8 {
-                            let mut a = [0u8; 8];
-                            a.copy_from_slice(&v);
-                            Some(u64::from_be_bytes(a))
-                        } else {
-                            None
-                        }
-                    })
-                    .unwrap_or(0)
-            };
-            if latest_height > 0 {
-                if let Some(mut event) = self
-                    .storage
-                    .db
-                    .get(format!("emission:event:{latest_height}"))
-                    .ok()
-                    .flatten()
-                    .and_then(|v| {
-                        bincode::deserialize::<crate::runtime::emission::EmissionEvent>(&v).ok()
-                    })
-                {
-                    event.reward_index_after = Some(self.reward_index);
-                    let _ = self.storage.db.put(
-                        format!("emission:event:{latest_height}"),
-                        bincode::serialize(&event).unwrap(),
-                    );
-                }
+        self.save_u128("staking:reward_index", reward_index);
+        self.save_u128("staking:reward_residual", residual);
+
+        // Record reward_index_after in the latest emission event for observability
+        let latest_height = {
+            // duplicated function logic to avoid a direct dependency on emission.rs
+            self.kv
+                .kv_get("emission:last_height")
+                .and_then(|v| {
+                    if v.len() == 8 {
+                        let mut a = [0u8; 8];
+                        a.copy_from_slice(&v);
+                        Some(u64::from_be_bytes(a))
+                    } else {
+                        None
+                    }
+                })
+                .unwrap_or(0)
+        };
+        if latest_height > 0 {
+            let key = format!("emission:event:{latest_height}");
+            if let Some(mut event) = self.kv.kv_get(&key).and_then(|v| {
+                bincode::deserialize::<crate::runtime::emission::EmissionEvent>(&v).ok()
+            }) {
+                event.reward_index_after = Some(reward_index);
+                let _ = self.kv.kv_put(&key, bincode::serialize(&event).unwrap());
             }
-        } else {
-            // No stake yet, accumulate for later distribution
-            self.pending_staking_emission = self.pending_staking_emission.saturating_add(amount);
-            self.save_pending_emission();
         }
     }
 
     /// Set total stake (called when validators register/delegate)
     pub fn set_total_stake(&mut self, stake: u128) {
-        self.total_stake = stake;
-        self.save_total_stake();
+        self.save_u128("staking:total_stake", stake);
 
         // If stake becomes > 0 and we have pending emission, apply it using carry-aware division
-        if stake > 0 && self.pending_staking_emission > 0 {
-            let scaled_pending = self.pending_staking_emission.saturating_mul(REWARD_SCALE);
-            let numerator = self.reward_index_residual.saturating_add(scaled_pending);
-            let pending_per_unit = numerator / stake;
-            self.reward_index = self.reward_index.saturating_add(pending_per_unit);
-            self.reward_index_residual = numerator % stake;
-            self.pending_staking_emission = 0;
-            self.save_reward_index();
-            self.save_pending_emission();
-            self.save_reward_residual();
+        let pending = self.pending_staking_emission();
+        if stake > 0 && pending > 0 {
+            let scaled_pending = pending.saturating_mul(REWARD_SCALE);
+            let numerator = self.reward_index_residual().saturating_add(scaled_pending);
+            let reward_index = self.reward_index().saturating_add(numerator / stake);
+            self.save_u128("staking:reward_index", reward_index);
+            self.save_u128("staking:pending_emission", 0);
+            self.save_u128("staking:reward_residual", numerator % stake);
         }
     }
 
     /// Get current reward statistics
     pub fn get_stats(&self) -> (u128, u128, u128) {
         (
-            self.total_stake,
-            self.reward_index,
-            self.pending_staking_emission,
+            self.total_stake(),
+            self.reward_index(),
+            self.pending_staking_emission(),
         )
     }
 
@@ -207,25 +209,19 @@ impl StakingModule {
     /// Load delegator reward record from storage
     pub fn load_delegator_record(&self, address: &str) -> DelegatorRewardRecord {
         let key = format!("staking:delegator:{address}");
-        self.storage
-            .db
-            .get(&key)
-            .ok()
-            .flatten()
+        self.kv
+            .kv_get(&key)
             .and_then(|v| bincode::deserialize::<DelegatorRewardRecord>(&v).ok())
-            .unwrap_or(DelegatorRewardRecord {
-                last_reward_index: self.reward_index,
+            .unwrap_or_else(|| DelegatorRewardRecord {
+                last_reward_index: self.reward_index(),
                 ..Default::default()
             })
     }
 
     /// Save delegator reward record to storage
-    fn save_delegator_record(&self, address: &str, record: &DelegatorRewardRecord) {
+    fn save_delegator_record(&mut self, address: &str, record: &DelegatorRewardRecord) {
         let key = format!("staking:delegator:{address}");
-        let _ = self
-            .storage
-            .db
-            .put(&key, bincode::serialize(record).unwrap());
+        let _ = self.kv.kv_put(&key, bincode::serialize(record).unwrap());
     }
 
     /// Update stake amount for a delegator (used when delegation changes)
@@ -233,7 +229,7 @@ impl StakingModule {
         // First settle any pending rewards before changing stake
         self.settle_delegator_rewards(address);
 
-        let current_reward_index = self.reward_index;
+        let current_reward_index = self.reward_index();
         let mut record = self.load_delegator_record(address);
         if record.last_reward_index == 0 {
             record.last_reward_index = current_reward_index;
@@ -245,19 +241,20 @@ impl StakingModule {
 
     /// Settle (accrue) rewards for a delegator based on current reward index
     pub fn settle_delegator_rewards(&mut self, address: &str) -> u128 {
+        let reward_index = self.reward_index();
         let mut record = self.load_delegator_record(address);
 
-        if record.stake_amount > 0 && self.reward_index > record.last_reward_index {
-            let delta_index = self.reward_index - record.last_reward_index;
+        if record.stake_amount > 0 && reward_index > record.last_reward_index {
+            let delta_index = reward_index - record.last_reward_index;
             let newly_accrued = (record.stake_amount * delta_index) / REWARD_SCALE;
             record.accrued_rewards = record.accrued_rewards.saturating_add(newly_accrued);
-            record.last_reward_index = self.reward_index;
+            record.last_reward_index = reward_index;
             self.save_delegator_record(address, &record);
             newly_accrued
         } else {
             // Update index even if no rewards to prevent future issues
-            if self.reward_index > record.last_reward_index {
-                record.last_reward_index = self.reward_index;
+            if reward_index > record.last_reward_index {
+                record.last_reward_index = reward_index;
                 self.save_delegator_record(address, &record);
             }
             0
@@ -266,12 +263,13 @@ impl StakingModule {
 
     /// Get accrued rewards for a delegator (includes pending rewards)
     pub fn get_accrued_rewards(&self, address: &str) -> u128 {
+        let reward_index = self.reward_index();
         let record = self.load_delegator_record(address);
         let mut accrued = record.accrued_rewards;
 
         // Add pending rewards since last settlement
-        if record.stake_amount > 0 && self.reward_index > record.last_reward_index {
-            let delta_index = self.reward_index - record.last_reward_index;
+        if record.stake_amount > 0 && reward_index > record.last_reward_index {
+            let delta_index = reward_index - record.last_reward_index;
             let pending = (record.stake_amount * delta_index) / REWARD_SCALE;
             accrued = accrued.saturating_add(pending);
         }
@@ -296,65 +294,148 @@ impl StakingModule {
         claimed_amount
     }
 
-    // Private storage methods
-    fn save_total_stake(&self) {
-        let _ = self.storage.db.put(
-            "staking:total_stake",
-            bincode::serialize(&self.total_stake).unwrap(),
-        );
+    /// Stake bonded to `validator` across all delegators
+    pub fn validator_stake(&self, validator: &str) -> u128 {
+        self.load_u128(&format!("staking:validator:{validator}"))
     }
 
-    fn save_reward_index(&self) {
-        let _ = self.storage.db.put(
-            "staking:reward_index",
-            bincode::serialize(&self.reward_index).unwrap(),
-        );
+    /// Bonded stake of every delegator
+    pub fn delegator_stakes(&self) -> Vec<(String, u128)> {
+        self.kv
+            .kv_scan_prefix("staking:delegator:")
+            .into_iter()
+            .filter_map(|(k, v)| {
+                let record = bincode::deserialize::<DelegatorRewardRecord>(&v).ok()?;
+                let address = k.trim_start_matches("staking:delegator:").to_string();
+                Some((address, record.stake_amount)).filter(|(_, stake)| *stake > 0)
+            })
+            .collect()
     }
 
-    fn save_pending_emission(&self) {
-        let _ = self.storage.db.put(
-            "staking:pending_emission",
-            bincode::serialize(&self.pending_staking_emission).unwrap(),
-        );
+    /// Register `validator` so that stake can be delegated to it
+    pub fn register_validator(&mut self, validator: &str) {
+        if !self.is_validator(validator) {
+            self.set_validator_stake(validator, 0);
+        }
     }
 
-    fn save_reward_residual(&self) {
-        let _ = self.storage.db.put(
-            "staking:reward_residual",
-            bincode::serialize(&self.reward_index_residual).unwrap(),
-        );
+    /// Whether `validator` has been registered
+    pub fn is_validator(&self, validator: &str) -> bool {
+        self.kv
+            .kv_get(&format!("staking:validator:{validator}"))
+            .is_some()
+    }
+
+    /// Registered validators with bonded stake, ordered by address
+    pub fn validators(&self) -> Vec<(String, u128)> {
+        self.kv
+            .kv_scan_prefix("staking:validator:")
+            .into_iter()
+            .filter_map(|(k, v)| {
+                let stake = bincode::deserialize::<u128>(&v).ok().filter(|s| *s > 0)?;
+                Some((
+                    k.trim_start_matches("staking:validator:").to_string(),
+                    stake,
+                ))
+            })
+            .collect()
+    }
+
+    /// The entry stays at zero stake, so the validator remains registered
+    fn set_validator_stake(&mut self, validator: &str, stake: u128) {
+        let key = format!("staking:validator:{validator}");
+        let _ = self.kv.kv_put(&key, bincode::serialize(&stake).unwrap());
+    }
+
+    /// Stake `delegator` has bonded to `validator`
+    pub fn delegation(&self, delegator: &str, validator: &str) -> u128 {
+        self.load_u128(&format!("staking:delegation:{delegator}:{validator}"))
+    }
+
+    /// Validators `delegator` has stake with, ordered by validator address
+    pub fn delegations_of(&self, delegator: &str) -> Vec<(String, u128)> {
+        let prefix = format!("staking:delegation:{delegator}:");
+        self.kv
+            .kv_scan_prefix(&prefix)
+            .into_iter()
+            .filter_map(|(k, v)| {
+                let amount = bincode::deserialize::<u128>(&v).ok()?;
+                Some((k.trim_start_matches(&prefix).to_string(), amount))
+            })
+            .collect()
+    }
+
+    /// Every (delegator, validator, stake) delegation
+    pub fn delegations(&self) -> Vec<(String, String, u128)> {
+        self.kv
+            .kv_scan_prefix("staking:delegation:")
+            .into_iter()
+            .filter_map(|(k, v)| {
+                let amount = bincode::deserialize::<u128>(&v).ok()?;
+                let (delegator, validator) = k
+                    .trim_start_matches("staking:delegation:")
+                    .split_once(':')?;
+                Some((delegator.to_string(), validator.to_string(), amount))
+            })
+            .collect()
+    }
+
+    fn set_delegation(&mut self, delegator: &str, validator: &str, amount: u128) {
+        let key = format!("staking:delegation:{delegator}:{validator}");
+        let _ = if amount == 0 {
+            self.kv.kv_delete(&key)
+        } else {
+            self.kv.kv_put(&key, bincode::serialize(&amount).unwrap())
+        };
+    }
+
+    /// Attribute `amount` of `delegator`'s stake to `validator` (delegations and genesis).
+    /// The delegator record and total stake are maintained by the caller. Only registered
+    /// validators accumulate stake; a delegation to any other address is recorded alone.
+    pub fn add_validator_delegation(&mut self, delegator: &str, validator: &str, amount: u128) {
+        let delegated = self.delegation(delegator, validator);
+        self.set_delegation(delegator, validator, delegated.saturating_add(amount));
+        if self.is_validator(validator) {
+            let validator_stake = self.validator_stake(validator);
+            self.set_validator_stake(validator, validator_stake.saturating_add(amount));
+        }
     }
 
     /// Delegate tokens to a validator
     pub fn delegate(
         &mut self,
         delegator_addr: &str,
-        _validator_addr: &str,
+        validator_addr: &str,
         amount_udgt: u128,
     ) -> Result<(), String> {
         if amount_udgt == 0 {
             return Err("Cannot delegate zero amount".to_string());
         }
+        if !self.is_validator(validator_addr) {
+            return Err(format!("{validator_addr} is not a registered validator"));
+        }
 
         // Load existing delegator record and settle any pending rewards
+        let reward_index = self.reward_index();
         let mut record = self.load_delegator_record(delegator_addr);
 
         // Settle rewards before changing stake; guard against underflow if last index > current
         if record.stake_amount > 0 {
-            let delta_index = self.reward_index.saturating_sub(record.last_reward_index);
+            let delta_index = reward_index.saturating_sub(record.last_reward_index);
             let pending_rewards = (delta_index * record.stake_amount) / REWARD_SCALE;
             record.accrued_rewards = record.accrued_rewards.saturating_add(pending_rewards);
         }
 
         // Update stake
         record.stake_amount = record.stake_amount.saturating_add(amount_udgt);
-        record.last_reward_index = self.reward_index;
+        record.last_reward_index = reward_index;
 
         // Save updated record
         self.save_delegator_record(delegator_addr, &record);
 
-        // Update total stake
-        self.set_total_stake(self.total_stake.saturating_add(amount_udgt));
+        // Update total and validator stake
+        self.set_total_stake(self.total_stake().saturating_add(amount_udgt));
+        self.add_validator_delegation(delegator_addr, validator_addr, amount_udgt);
 
         Ok(())
     }
@@ -363,7 +444,7 @@ impl StakingModule {
     pub fn undelegate(
         &mut self,
         delegator_addr: &str,
-        _validator_addr: &str,
+        validator_addr: &str,
         amount_udgt: u128,
     ) -> Result<(), String> {
         if amount_udgt == 0 {
@@ -371,28 +452,40 @@ impl StakingModule {
         }
 
         // Load existing delegator record
+        let reward_index = self.reward_index();
         let mut record = self.load_delegator_record(delegator_addr);
 
         if record.stake_amount < amount_udgt {
             return Err("Insufficient delegated amount".to_string());
         }
+        let delegated = self.delegation(delegator_addr, validator_addr);
+        if delegated < amount_udgt {
+            return Err(format!(
+                "Insufficient amount delegated to {validator_addr}: {delegated} < {amount_udgt}"
+            ));
+        }
 
         // Settle rewards before changing stake; guard against underflow
         if record.stake_amount > 0 {
-            let delta_index = self.reward_index.saturating_sub(record.last_reward_index);
+            let delta_index = reward_index.saturating_sub(record.last_reward_index);
             let pending_rewards = (delta_index * record.stake_amount) / REWARD_SCALE;
             record.accrued_rewards = record.accrued_rewards.saturating_add(pending_rewards);
         }
 
         // Update stake
         record.stake_amount = record.stake_amount.saturating_sub(amount_udgt);
-        record.last_reward_index = self.reward_index;
+        record.last_reward_index = reward_index;
 
         // Save updated record
         self.save_delegator_record(delegator_addr, &record);
 
-        // Update total stake
-        self.set_total_stake(self.total_stake.saturating_sub(amount_udgt));
+        // Update total and validator stake
+        self.set_total_stake(self.total_stake().saturating_sub(amount_udgt));
+        self.set_delegation(delegator_addr, validator_addr, delegated - amount_udgt);
+        if self.is_validator(validator_addr) {
+            let validator_stake = self.validator_stake(validator_addr);
+            self.set_validator_stake(validator_addr, validator_stake.saturating_sub(amount_udgt));
+        }
 
         Ok(())
     }
@@ -418,13 +511,13 @@ mod tests {
 
         // No stake, should accumulate in pending
         staking.apply_external_emission(1000);
-        assert_eq!(staking.pending_staking_emission, 1000);
-        assert_eq!(staking.reward_index, 0);
+        assert_eq!(staking.pending_staking_emission(), 1000);
+        assert_eq!(staking.reward_index(), 0);
 
         // Add more emission
         staking.apply_external_emission(500);
-        assert_eq!(staking.pending_staking_emission, 1500);
-        assert_eq!(staking.reward_index, 0);
+        assert_eq!(staking.pending_staking_emission(), 1500);
+        assert_eq!(staking.reward_index(), 0);
     }
 
     #[test]
@@ -435,19 +528,19 @@ mod tests {
 
         // Accumulate pending emission
         staking.apply_external_emission(1000);
-        assert_eq!(staking.pending_staking_emission, 1000);
+        assert_eq!(staking.pending_staking_emission(), 1000);
 
         // Set stake - should apply pending
         staking.set_total_stake(1_000_000); // 1M uDGT
-        assert_eq!(staking.pending_staking_emission, 0);
-        let expected_reward_index = (1000 * REWARD_SCALE) / staking.total_stake;
-        assert_eq!(staking.reward_index, expected_reward_index);
+        assert_eq!(staking.pending_staking_emission(), 0);
+        let expected_reward_index = (1000 * REWARD_SCALE) / staking.total_stake();
+        assert_eq!(staking.reward_index(), expected_reward_index);
 
         // Add new emission with stake
         staking.apply_external_emission(2000);
-        let additional_reward = (2000 * REWARD_SCALE) / staking.total_stake;
+        let additional_reward = (2000 * REWARD_SCALE) / staking.total_stake();
         assert_eq!(
-            staking.reward_index,
+            staking.reward_index(),
             expected_reward_index + additional_reward
         );
     }
@@ -461,8 +554,8 @@ mod tests {
         staking.set_total_stake(1_000_000_000_000); // 1M DGT in uDGT
         staking.apply_external_emission(1_000_000); // 1 DRT in uDRT
 
-        let expected_reward_index = (1_000_000 * REWARD_SCALE) / staking.total_stake;
-        assert_eq!(staking.reward_index, expected_reward_index);
+        let expected_reward_index = (1_000_000 * REWARD_SCALE) / staking.total_stake();
+        assert_eq!(staking.reward_index(), expected_reward_index);
         assert_eq!(expected_reward_index, 1_000_000); // Should be 1e6 (1 DRT per 1M DGT)
     }
 
@@ -480,14 +573,14 @@ mod tests {
         staking.apply_external_emission(1_000_000); // 1 DRT in uDRT
 
         // Check that reward index was updated
-        let expected_reward_index = (1_000_000 * REWARD_SCALE) / staking.total_stake;
-        assert_eq!(staking.reward_index, expected_reward_index);
+        let expected_reward_index = (1_000_000 * REWARD_SCALE) / staking.total_stake();
+        assert_eq!(staking.reward_index(), expected_reward_index);
 
         // Apply another emission
         staking.apply_external_emission(2_000_000); // 2 DRT in uDRT
-        let additional_reward = (2_000_000 * REWARD_SCALE) / staking.total_stake;
+        let additional_reward = (2_000_000 * REWARD_SCALE) / staking.total_stake();
         assert_eq!(
-            staking.reward_index,
+            staking.reward_index(),
             expected_reward_index + additional_reward
         );
 
@@ -512,4 +605,42 @@ mod tests {
         let expected_new_rewards = 1_000_000 / 10; // 10% of 1 DRT
         assert_eq!(new_accrued, expected_new_rewards);
     }
+
+    #[test]
+    fn test_delegations_tracked_per_validator() {
+        let dir = tempdir().unwrap();
+        let storage = Arc::new(Storage::open(dir.path().join("test.db")).unwrap());
+        let mut staking = StakingModule::new(storage);
+
+        // Only registered validators take delegations
+        assert!(staking.delegate("alice", "val1", 500).is_err());
+        staking.register_validator("val1");
+        staking.register_validator("val2");
+        staking.delegate("alice", "val1", 500).unwrap();
+        staking.delegate("alice", "val2", 200).unwrap();
+        staking.delegate("bob", "val1", 100).unwrap();
+        assert_eq!(
+            staking.delegations_of("alice"),
+            vec![("val1".to_string(), 500), ("val2".to_string(), 200)]
+        );
+        assert_eq!(staking.validator_stake("val1"), 600);
+
+        // Stake can only be withdrawn from the validator it was delegated to
+        assert!(staking.undelegate("alice", "val2", 300).is_err());
+        staking.undelegate("alice", "val2", 200).unwrap();
+        staking.undelegate("alice", "val1", 100).unwrap();
+        assert_eq!(staking.delegation("alice", "val1"), 400);
+        assert_eq!(staking.delegation("alice", "val2"), 0);
+        assert_eq!(staking.get_total_stake("alice"), 400);
+        assert_eq!(
+            staking.delegations(),
+            vec![
+                ("alice".to_string(), "val1".to_string(), 400),
+                ("bob".to_string(), "val1".to_string(), 100),
+            ]
+        );
+        assert_eq!(staking.validators(), vec![("val1".to_string(), 500)]);
+        // A validator without stake stays registered
+        assert!(staking.is_validator("val2"));
+    }
 }
@@ -0,0 +1,244 @@
+use crate::runtime::governance::{Proposal, ProposalStatus};
+use crate::runtime::module_accounts::{
+    emission_account, module_address, module_balances, EMISSION_POOLS, GOV_DEPOSITS,
+    SCHEDULE_ESCROW, STAKING_BONDED,
+};
+use crate::runtime::schedule::ScheduleModule;
+use crate::runtime::staking::StakingModule;
+use crate::state::{KvStore, State};
+use crate::storage::state::Storage;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+/// Total supply per denom. Every path that creates tokens (genesis, faucets, emission,
+/// token factory mints) mints here and every path that destroys them burns here, so that
+/// `supply == sum(balances)` holds for each denom, module accounts included.
+pub struct SupplyModule<K = Arc<Storage>> {
+    kv: K,
+}
+
+// Key layout:
+//   supply:{denom}  -> u128 BE
+impl SupplyModule {
+    pub fn new(storage: Arc<Storage>) -> Self {
+        Self { kv: storage }
+    }
+}
+
+fn read_amount(bytes: &[u8]) -> u128 {
+    bytes.try_into().map(u128::from_be_bytes).unwrap_or(0)
+}
+
+impl<K: KvStore> SupplyModule<K> {
+    pub fn with_store(kv: K) -> Self {
+        Self { kv }
+    }
+
+    fn get_amount(&self, key: &str) -> u128 {
+        self.kv.kv_get(key).map(|v| read_amount(&v)).unwrap_or(0)
+    }
+
+    fn put_amount(&mut self, key: &str, amount: u128) -> Result<(), String> {
+        if amount == 0 {
+            self.kv.kv_delete(key)
+        } else {
+            self.kv.kv_put(key, amount.to_be_bytes().to_vec())
+        }
+    }
+
+    pub fn supply(&self, denom: &str) -> u128 {
+        self.get_amount(&format!("supply:{denom}"))
+    }
+
+    /// Total supply of every denom with a non-zero supply
+    pub fn all_supply(&self) -> BTreeMap<String, u128> {
+        self.kv
+            .kv_scan_prefix("supply:")
+            .into_iter()
+            .map(|(k, v)| (k.trim_start_matches("supply:").to_string(), read_amount(&v)))
+            .collect()
+    }
+
+    pub fn mint(&mut self, denom: &str, amount: u128) -> Result<(), String> {
+        let supply = self
+            .supply(denom)
+            .checked_add(amount)
+            .ok_or_else(|| format!("supply of {denom} overflows"))?;
+        self.put_amount(&format!("supply:{denom}"), supply)
+    }
+
+    pub fn burn(&mut self, denom: &str, amount: u128) -> Result<(), String> {
+        let supply = self.supply(denom);
+        if supply < amount {
+            return Err(format!("cannot burn {amount} {denom}: supply is {supply}"));
+        }
+        self.put_amount(&format!("supply:{denom}"), supply - amount)
+    }
+}
+
+/// Credit newly created tokens to `address` and add them to the supply
+pub fn mint_to(state: &mut State, address: &str, denom: &str, amount: u128) -> Result<(), String> {
+    SupplyModule::with_store(&mut *state).mint(denom, amount)?;
+    state.credit(address, denom, amount);
+    Ok(())
+}
+
+/// Supply of one denom against the tokens actually held
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct SupplyCheck {
+    pub denom: String,
+    pub supply: u128,
+    /// All account balances, module accounts included
+    pub balances: u128,
+    /// Part of `balances` held by module accounts
+    pub module_accounts: u128,
+}
+
+impl SupplyCheck {
+    pub fn holds(&self) -> bool {
+        self.balances == self.supply
+    }
+}
+
+fn balance_totals(storage: &Storage) -> BTreeMap<String, u128> {
+    let mut balances: BTreeMap<String, u128> = BTreeMap::new();
+    for (_, value) in storage.scan_prefix("acct:balances:") {
+        let account: BTreeMap<String, u128> = bincode::deserialize(&value).unwrap_or_default();
+        for (denom, amount) in account {
+            let total = balances.entry(denom).or_insert(0);
+            *total = total.saturating_add(amount);
+        }
+    }
+    balances
+}
+
+/// Databases created before supply tracking have balances but no supply records, and keep
+/// escrows, bonded stake, open proposals' deposits and emission pools outside any balance.
+/// Credit those to their module accounts and mint the resulting balances as the supply.
+/// Returns false if supply is already tracked.
+pub fn seed_from_state(storage: &Arc<Storage>) -> Result<bool, String> {
+    let mut module = SupplyModule::new(storage.clone());
+    if !module.all_supply().is_empty() {
+        return Ok(false);
+    }
+    let mut held: Vec<(String, String, u128)> = ScheduleModule::new(storage.clone())
+        .due(u64::MAX)
+        .into_iter()
+        .map(|s| (SCHEDULE_ESCROW.to_string(), s.denom, s.amount))
+        .collect();
+    let bonded = StakingModule::new(storage.clone()).total_stake();
+    held.push((STAKING_BONDED.to_string(), "udgt".to_string(), bonded));
+    let open_deposits = storage
+        .scan_prefix("gov:proposal:")
+        .into_iter()
+        .filter_map(|(_, v)| Proposal::decode(&v))
+        .filter(|p| matches!(p.status, ProposalStatus::DepositPeriod | ProposalStatus::VotingPeriod))
+        .map(|p| p.total_deposit)
+        .sum::<u128>();
+    held.push((GOV_DEPOSITS.to_string(), "udgt".to_string(), open_deposits));
+    for pool in EMISSION_POOLS {
+        let key = format!("emission:pool:{pool}");
+        if let Some(amount) = storage
+            .db
+            .get(&key)
+            .ok()
+            .flatten()
+            .and_then(|v| bincode::deserialize::<u128>(&v).ok())
+        {
+            held.push((emission_account(pool), "udrt".to_string(), amount));
+            storage.db.delete(&key).map_err(|e| e.to_string())?;
+        }
+    }
+    let mut state = State::new(storage.clone());
+    for (name, denom, amount) in held.into_iter().filter(|(_, _, amount)| *amount > 0) {
+        state.credit(&module_address(&name), &denom, amount);
+    }
+    let supply = balance_totals(storage);
+    if supply.is_empty() {
+        return Ok(false);
+    }
+    for (denom, amount) in supply {
+        module.mint(&denom, amount)?;
+    }
+    Ok(true)
+}
+
+/// Compare every denom's supply with the sum of all account balances
+pub fn check_invariants(storage: &Arc<Storage>) -> Vec<SupplyCheck> {
+    let supply = SupplyModule::new(storage.clone()).all_supply();
+    let balances = balance_totals(storage);
+    let mut module_accounts: BTreeMap<String, u128> = BTreeMap::new();
+    for (_, account) in module_balances(storage) {
+        for (denom, amount) in account {
+            let total = module_accounts.entry(denom).or_insert(0);
+            *total = total.saturating_add(amount);
+        }
+    }
+    let denoms: BTreeSet<&String> = supply.keys().chain(balances.keys()).collect();
+    denoms
+        .into_iter()
+        .map(|denom| SupplyCheck {
+            denom: denom.clone(),
+            supply: supply.get(denom).copied().unwrap_or(0),
+            balances: balances.get(denom).copied().unwrap_or(0),
+            module_accounts: module_accounts.get(denom).copied().unwrap_or(0),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_supply_invariant_tracks_mints_and_burns() {
+        let dir = tempdir().unwrap();
+        let storage = Arc::new(Storage::open(dir.path().join("test.db")).unwrap());
+        let mut state = State::new(storage.clone());
+
+        mint_to(&mut state, "alice", "udgt", 1_000).unwrap();
+        mint_to(&mut state, "bob", "udrt", 50).unwrap();
+        assert!(check_invariants(&storage).iter().all(SupplyCheck::holds));
+
+        state.set_balance("alice", "udgt", 900);
+        SupplyModule::with_store(&mut state).burn("udgt", 100).unwrap();
+        assert!(SupplyModule::with_store(&mut state).burn("udrt", 51).is_err());
+        assert_eq!(SupplyModule::new(storage.clone()).supply("udgt"), 900);
+        assert!(check_invariants(&storage).iter().all(SupplyCheck::holds));
+
+        // Tokens credited without minting break the invariant
+        state.credit("carol", "udgt", 1);
+        let udgt = check_invariants(&storage)
+            .into_iter()
+            .find(|c| c.denom == "udgt")
+            .unwrap();
+        assert!(!udgt.holds());
+        assert_eq!((udgt.supply, udgt.balances), (900, 901));
+    }
+
+    #[test]
+    fn test_seed_moves_legacy_pools_into_module_accounts() {
+        let dir = tempdir().unwrap();
+        let storage = Arc::new(Storage::open(dir.path().join("test.db")).unwrap());
+        let mut state = State::new(storage.clone());
+        state.credit("alice", "udgt", 700);
+        storage
+            .db
+            .put("emission:pool:staking_rewards", bincode::serialize(&250u128).unwrap())
+            .unwrap();
+        ScheduleModule::new(storage.clone())
+            .create("alice", "bob", "udgt", 300, 10, true, 1)
+            .unwrap();
+
+        assert!(seed_from_state(&storage).unwrap());
+        assert!(!seed_from_state(&storage).unwrap());
+        let supply = SupplyModule::new(storage.clone());
+        assert_eq!((supply.supply("udgt"), supply.supply("udrt")), (1_000, 250));
+        let mut state = State::new(storage.clone());
+        assert_eq!(state.balance_of(&module_address(SCHEDULE_ESCROW), "udgt"), 300);
+        assert_eq!(state.balance_of(&module_address(&emission_account("staking_rewards")), "udrt"), 250);
+        assert!(check_invariants(&storage).iter().all(SupplyCheck::holds));
+    }
+}
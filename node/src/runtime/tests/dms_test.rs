@@ -7,7 +7,7 @@ use tempfile::tempdir;
 fn test_dms_flow() {
     let dir = tempdir().unwrap();
     let storage = Arc::new(Storage::open(dir.path().join("test.db")).unwrap());
-    let dms = DeadManSwitchModule::new(storage);
+    let mut dms = DeadManSwitchModule::new(storage);
 
     let owner = "owner_addr";
     let beneficiary = "beneficiary_addr";
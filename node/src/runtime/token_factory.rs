@@ -0,0 +1,221 @@
+use crate::runtime::supply::SupplyModule;
+use crate::state::KvStore;
+use crate::storage::state::Storage;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Prefix of user-created denoms: `factory/{creator}/{subdenom}`
+pub const FACTORY_PREFIX: &str = "factory/";
+const MAX_SUBDENOM_LEN: usize = 44;
+const MAX_DECIMALS: u8 = 18;
+
+/// A denom created through the token factory
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FactoryDenom {
+    pub denom: String,
+    pub creator: String,
+    /// Only the admin may mint, burn or hand the denom over; an empty admin locks the supply
+    pub admin: String,
+    pub symbol: String,
+    pub decimals: u8,
+    /// Minted minus burned
+    pub supply: u128,
+    pub created_height: u64,
+}
+
+/// Full denom for `subdenom` created by `creator`
+pub fn factory_denom(creator: &str, subdenom: &str) -> String {
+    format!("{FACTORY_PREFIX}{creator}/{subdenom}")
+}
+
+/// Subdenoms are 1-44 characters of lowercase letters, digits, `.`, `-` and `_`
+pub fn validate_subdenom(subdenom: &str) -> Result<(), String> {
+    if subdenom.is_empty() || subdenom.len() > MAX_SUBDENOM_LEN {
+        return Err(format!(
+            "subdenom must be 1-{MAX_SUBDENOM_LEN} characters"
+        ));
+    }
+    if !subdenom
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '.' | '-' | '_'))
+    {
+        return Err(format!("invalid subdenom {subdenom}"));
+    }
+    Ok(())
+}
+
+/// Split a well-formed factory denom into `(creator, subdenom)`
+pub fn parse_factory_denom(denom: &str) -> Option<(&str, &str)> {
+    let (creator, subdenom) = denom.strip_prefix(FACTORY_PREFIX)?.split_once('/')?;
+    if creator.is_empty() || validate_subdenom(subdenom).is_err() {
+        return None;
+    }
+    Some((creator, subdenom))
+}
+
+/// Token factory module over a KV store (see `ScheduleModule`). It keeps denom records and
+/// their supply, mirrored in `SupplyModule`; the caller credits and debits balances.
+pub struct TokenFactoryModule<K = Arc<Storage>> {
+    kv: K,
+}
+
+// Key layout:
+//   tf_denom:{denom}               -> bincode(FactoryDenom)
+//   tf_creator:{creator}:{subdenom} -> [] (denoms by creator)
+impl TokenFactoryModule {
+    pub fn new(storage: Arc<Storage>) -> Self {
+        Self { kv: storage }
+    }
+}
+
+impl<K: KvStore> TokenFactoryModule<K> {
+    pub fn with_store(kv: K) -> Self {
+        Self { kv }
+    }
+
+    fn key(denom: &str) -> String {
+        format!("tf_denom:{denom}")
+    }
+
+    pub fn get(&self, denom: &str) -> Option<FactoryDenom> {
+        self.kv
+            .kv_get(&Self::key(denom))
+            .and_then(|v| bincode::deserialize(&v).ok())
+    }
+
+    pub fn is_registered(&self, denom: &str) -> bool {
+        self.kv.kv_get(&Self::key(denom)).is_some()
+    }
+
+    /// Every factory denom, ordered by denom
+    pub fn all(&self) -> Vec<FactoryDenom> {
+        self.kv
+            .kv_scan_prefix("tf_denom:")
+            .into_iter()
+            .filter_map(|(_, v)| bincode::deserialize(&v).ok())
+            .collect()
+    }
+
+    /// Denoms created by `creator`, ordered by subdenom
+    pub fn denoms_by_creator(&self, creator: &str) -> Vec<FactoryDenom> {
+        self.kv
+            .kv_scan_prefix(&format!("tf_creator:{creator}:"))
+            .into_iter()
+            .filter_map(|(k, _)| {
+                let subdenom = k.rsplit(':').next()?;
+                self.get(&factory_denom(creator, subdenom))
+            })
+            .collect()
+    }
+
+    fn put(&mut self, record: &FactoryDenom) -> Result<(), String> {
+        self.kv.kv_put(
+            &Self::key(&record.denom),
+            bincode::serialize(record).map_err(|e| e.to_string())?,
+        )
+    }
+
+    /// Register `factory/{creator}/{subdenom}` with `creator` as admin and zero supply
+    pub fn create_denom(
+        &mut self,
+        creator: &str,
+        subdenom: &str,
+        symbol: &str,
+        decimals: u8,
+        height: u64,
+    ) -> Result<FactoryDenom, String> {
+        validate_subdenom(subdenom)?;
+        if decimals > MAX_DECIMALS {
+            return Err(format!("decimals must be at most {MAX_DECIMALS}"));
+        }
+        let denom = factory_denom(creator, subdenom);
+        if self.is_registered(&denom) {
+            return Err(format!("denom {denom} already exists"));
+        }
+        let record = FactoryDenom {
+            denom,
+            creator: creator.to_string(),
+            admin: creator.to_string(),
+            symbol: symbol.to_string(),
+            decimals,
+            supply: 0,
+            created_height: height,
+        };
+        self.put(&record)?;
+        self.kv
+            .kv_put(&format!("tf_creator:{creator}:{subdenom}"), Vec::new())?;
+        Ok(record)
+    }
+
+    fn admin_record(&self, sender: &str, denom: &str) -> Result<FactoryDenom, String> {
+        let record = self
+            .get(denom)
+            .ok_or_else(|| format!("denom {denom} not found"))?;
+        if record.admin.is_empty() || record.admin != sender {
+            return Err(format!("{sender} is not the admin of {denom}"));
+        }
+        Ok(record)
+    }
+
+    /// Increase the supply; the caller credits the minted tokens
+    pub fn mint(&mut self, sender: &str, denom: &str, amount: u128) -> Result<FactoryDenom, String> {
+        let mut record = self.admin_record(sender, denom)?;
+        record.supply = record
+            .supply
+            .checked_add(amount)
+            .ok_or_else(|| format!("supply of {denom} overflows"))?;
+        self.put(&record)?;
+        SupplyModule::with_store(&mut self.kv).mint(denom, amount)?;
+        Ok(record)
+    }
+
+    /// Decrease the supply; the caller has debited the burned tokens
+    pub fn burn(&mut self, sender: &str, denom: &str, amount: u128) -> Result<FactoryDenom, String> {
+        let mut record = self.admin_record(sender, denom)?;
+        record.supply = record
+            .supply
+            .checked_sub(amount)
+            .ok_or_else(|| format!("cannot burn {amount}: supply of {denom} is {}", record.supply))?;
+        self.put(&record)?;
+        SupplyModule::with_store(&mut self.kv).burn(denom, amount)?;
+        Ok(record)
+    }
+
+    /// Hand the denom to `new_admin`; an empty `new_admin` renounces it for good
+    pub fn change_admin(&mut self, sender: &str, denom: &str, new_admin: &str) -> Result<FactoryDenom, String> {
+        let mut record = self.admin_record(sender, denom)?;
+        record.admin = new_admin.to_string();
+        self.put(&record)?;
+        Ok(record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_create_mint_burn_and_change_admin() {
+        let dir = tempdir().unwrap();
+        let storage = Arc::new(Storage::open(dir.path().join("test.db")).unwrap());
+        let mut factory = TokenFactoryModule::new(storage);
+
+        assert!(factory.create_denom("alice", "Gold", "GLD", 6, 1).is_err());
+        let gold = factory.create_denom("alice", "gold", "GLD", 6, 1).unwrap();
+        assert_eq!(gold.denom, "factory/alice/gold");
+        assert_eq!(parse_factory_denom(&gold.denom), Some(("alice", "gold")));
+        assert!(factory.create_denom("alice", "gold", "GLD", 6, 2).is_err());
+
+        assert_eq!(factory.mint("alice", &gold.denom, 1_000).unwrap().supply, 1_000);
+        assert!(factory.mint("bob", &gold.denom, 1).is_err());
+        assert!(factory.burn("alice", &gold.denom, 1_001).is_err());
+        assert_eq!(factory.burn("alice", &gold.denom, 400).unwrap().supply, 600);
+
+        factory.change_admin("alice", &gold.denom, "bob").unwrap();
+        assert!(factory.mint("alice", &gold.denom, 1).is_err());
+        factory.change_admin("bob", &gold.denom, "").unwrap();
+        assert!(factory.mint("bob", &gold.denom, 1).is_err());
+        assert_eq!(factory.denoms_by_creator("alice").len(), 1);
+    }
+}
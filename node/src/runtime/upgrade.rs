@@ -0,0 +1,171 @@
+use crate::state::KvStore;
+use crate::storage::state::Storage;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Upgrade scheduled by a passed `SoftwareUpgrade` proposal
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UpgradePlan {
+    pub name: String,
+    /// First height produced by the upgraded binary; the old one halts before it
+    pub height: u64,
+    pub info: String,
+    pub proposal_id: u64,
+}
+
+/// State migration run once by the binary that handles an upgrade, before it produces the
+/// upgrade height
+pub type UpgradeHandler = fn(&Arc<Storage>) -> Result<(), String>;
+
+/// Upgrades this binary can apply. A release that follows a `SoftwareUpgrade` proposal adds
+/// its handler here under the proposal's name (a no-op handler if no migration is needed).
+pub const UPGRADE_HANDLERS: &[(&str, UpgradeHandler)] = &[];
+
+/// Planned and applied chain upgrades
+pub struct UpgradeModule<K = Arc<Storage>> {
+    kv: K,
+}
+
+// Key layout:
+//   upgrade:plan          -> bincode(UpgradePlan) (at most one pending plan)
+//   upgrade:done:{name}   -> u64 BE height the upgrade was applied at
+const PLAN_KEY: &str = "upgrade:plan";
+
+impl UpgradeModule {
+    pub fn new(storage: Arc<Storage>) -> Self {
+        Self { kv: storage }
+    }
+}
+
+impl<K: KvStore> UpgradeModule<K> {
+    pub fn with_store(kv: K) -> Self {
+        Self { kv }
+    }
+
+    pub fn plan(&self) -> Option<UpgradePlan> {
+        self.kv
+            .kv_get(PLAN_KEY)
+            .and_then(|v| bincode::deserialize(&v).ok())
+    }
+
+    /// Record `plan`, replacing any pending plan
+    pub fn schedule(&mut self, plan: &UpgradePlan) -> Result<(), String> {
+        if self.applied_height(&plan.name).is_some() {
+            return Err(format!("upgrade {} has already been applied", plan.name));
+        }
+        let data = bincode::serialize(plan).map_err(|e| e.to_string())?;
+        self.kv.kv_put(PLAN_KEY, data)
+    }
+
+    /// Height at which the upgrade `name` was applied
+    pub fn applied_height(&self, name: &str) -> Option<u64> {
+        self.kv
+            .kv_get(&format!("upgrade:done:{name}"))
+            .and_then(|v| v.as_slice().try_into().ok().map(u64::from_be_bytes))
+    }
+
+    /// Applied upgrades as (name, height), by name
+    pub fn applied(&self) -> Vec<(String, u64)> {
+        self.kv
+            .kv_scan_prefix("upgrade:done:")
+            .into_iter()
+            .filter_map(|(k, v)| {
+                let height = u64::from_be_bytes(v.as_slice().try_into().ok()?);
+                Some((k.trim_start_matches("upgrade:done:").to_string(), height))
+            })
+            .collect()
+    }
+
+    /// The pending plan if block `next_height` must not be produced by this binary
+    pub fn halt_for(&self, next_height: u64) -> Option<UpgradePlan> {
+        self.plan().filter(|plan| next_height >= plan.height)
+    }
+
+    fn mark_applied(&mut self, name: &str, height: u64) -> Result<(), String> {
+        self.kv
+            .kv_put(&format!("upgrade:done:{name}"), height.to_be_bytes().to_vec())?;
+        self.kv.kv_delete(PLAN_KEY)
+    }
+}
+
+/// Startup check. If the chain halted for a planned upgrade, run this binary's handler for
+/// it and clear the plan; a binary without the handler must not continue the chain, so that
+/// is an error. Returns the name of the applied upgrade.
+pub fn apply_pending_upgrade(
+    storage: &Arc<Storage>,
+    next_height: u64,
+    handlers: &[(&str, UpgradeHandler)],
+) -> Result<Option<String>, String> {
+    let mut module = UpgradeModule::new(storage.clone());
+    let Some(plan) = module.halt_for(next_height) else {
+        return Ok(None);
+    };
+    let handler = handlers
+        .iter()
+        .find(|(name, _)| *name == plan.name)
+        .map(|(_, handler)| handler)
+        .ok_or_else(|| {
+            format!(
+                "chain halted at height {} for upgrade \"{}\" ({}) and this binary has no handler for it; start the upgraded binary",
+                plan.height, plan.name, plan.info
+            )
+        })?;
+    handler(storage).map_err(|e| format!("upgrade \"{}\" migration failed: {e}", plan.name))?;
+    module.mark_applied(&plan.name, plan.height)?;
+    Ok(Some(plan.name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn migrate_v2(storage: &Arc<Storage>) -> Result<(), String> {
+        storage
+            .db
+            .put("migrated:v2", [1u8])
+            .map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn test_upgrade_halts_and_requires_handler() {
+        let dir = tempdir().unwrap();
+        let storage = Arc::new(Storage::open(dir.path().join("test.db")).unwrap());
+        let mut upgrades = UpgradeModule::new(storage.clone());
+        upgrades
+            .schedule(&UpgradePlan {
+                name: "v2".to_string(),
+                height: 10,
+                info: String::new(),
+                proposal_id: 1,
+            })
+            .unwrap();
+        assert!(upgrades.halt_for(9).is_none());
+        assert_eq!(upgrades.halt_for(10).unwrap().name, "v2");
+
+        // Before the upgrade height any binary may run
+        assert_eq!(apply_pending_upgrade(&storage, 9, &[]).unwrap(), None);
+        // At the height, a binary without the handler refuses to start
+        assert!(apply_pending_upgrade(&storage, 10, &[]).is_err());
+        assert!(upgrades.plan().is_some());
+
+        let handlers: &[(&str, UpgradeHandler)] = &[("v2", migrate_v2)];
+        assert_eq!(
+            apply_pending_upgrade(&storage, 10, handlers).unwrap().as_deref(),
+            Some("v2")
+        );
+        assert!(storage.db.get("migrated:v2").unwrap().is_some());
+        assert!(upgrades.plan().is_none());
+        assert_eq!(upgrades.applied(), vec![("v2".to_string(), 10)]);
+        // Production continues and the same upgrade cannot be planned again
+        assert_eq!(apply_pending_upgrade(&storage, 10, handlers).unwrap(), None);
+        assert!(upgrades
+            .schedule(&UpgradePlan {
+                name: "v2".to_string(),
+                height: 20,
+                info: String::new(),
+                proposal_id: 2,
+            })
+            .is_err());
+    }
+}
@@ -0,0 +1,346 @@
+use crate::state::{KvStore, State};
+use crate::storage::state::Storage;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// One step of a periodic vesting schedule
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VestingPeriod {
+    /// Length of the period in blocks
+    pub length: u64,
+    /// Amount that vests at the end of the period
+    pub amount: u128,
+}
+
+/// How the original vesting amount unlocks over block heights
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum VestingSchedule {
+    /// Vests linearly between `start_height` and `end_height`
+    Continuous { start_height: u64, end_height: u64 },
+    /// Vests each period's amount at the end of that period, periods running back to
+    /// back from `start_height`
+    Periodic {
+        start_height: u64,
+        periods: Vec<VestingPeriod>,
+    },
+    /// Cliff: nothing vests before `end_height`, everything vests at it
+    Delayed { end_height: u64 },
+}
+
+/// Lockup on part of an account's balance, declared in genesis
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VestingAccount {
+    pub address: String,
+    pub denom: String,
+    pub original_vesting: u128,
+    pub schedule: VestingSchedule,
+    /// Still-vesting tokens currently bonded to staking
+    pub delegated_vesting: u128,
+    /// Vested (free) tokens currently bonded to staking
+    pub delegated_free: u128,
+}
+
+impl VestingAccount {
+    /// Amount of `original_vesting` that has vested at `height`
+    pub fn vested(&self, height: u64) -> u128 {
+        match &self.schedule {
+            VestingSchedule::Continuous {
+                start_height,
+                end_height,
+            } => {
+                if height <= *start_height {
+                    0
+                } else if height >= *end_height {
+                    self.original_vesting
+                } else {
+                    let span = (end_height - start_height) as u128;
+                    let elapsed = (height - start_height) as u128;
+                    // Split to avoid overflowing on large amounts
+                    self.original_vesting / span * elapsed
+                        + self.original_vesting % span * elapsed / span
+                }
+            }
+            VestingSchedule::Periodic {
+                start_height,
+                periods,
+            } => {
+                let mut period_end = *start_height;
+                let mut vested: u128 = 0;
+                for period in periods {
+                    period_end = period_end.saturating_add(period.length);
+                    if height < period_end {
+                        break;
+                    }
+                    vested = vested.saturating_add(period.amount);
+                }
+                vested.min(self.original_vesting)
+            }
+            VestingSchedule::Delayed { end_height } => {
+                if height >= *end_height {
+                    self.original_vesting
+                } else {
+                    0
+                }
+            }
+        }
+    }
+
+    /// Amount still vesting at `height`
+    pub fn vesting(&self, height: u64) -> u128 {
+        self.original_vesting - self.vested(height)
+    }
+
+    /// Part of the liquid balance that cannot be spent at `height`. Vesting tokens that
+    /// were delegated have already left the balance and do not count again.
+    pub fn locked(&self, height: u64) -> u128 {
+        self.vesting(height).saturating_sub(self.delegated_vesting)
+    }
+
+    /// Record a delegation of `amount`: still-vesting tokens are bonded first
+    pub fn track_delegation(&mut self, height: u64, amount: u128) {
+        let from_vesting = self.locked(height).min(amount);
+        self.delegated_vesting += from_vesting;
+        self.delegated_free += amount - from_vesting;
+    }
+
+    /// Record an undelegation of `amount`: free tokens are unbonded first
+    pub fn track_undelegation(&mut self, amount: u128) {
+        let from_free = self.delegated_free.min(amount);
+        self.delegated_free -= from_free;
+        self.delegated_vesting = self
+            .delegated_vesting
+            .saturating_sub(amount - from_free);
+    }
+
+    /// Parse the `vesting` object of a genesis account:
+    /// `{ "type": "continuous" | "periodic" | "delayed", "denom"?, "amount", ... }`.
+    /// Continuous schedules take `start_height`/`end_height`, delayed ones `end_height`, and
+    /// periodic ones `start_height` plus `periods: [{ "length", "amount" }]` summing to `amount`.
+    pub fn from_genesis(address: &str, value: &serde_json::Value) -> Result<Self, String> {
+        let height = |field: &str| {
+            value
+                .get(field)
+                .and_then(|v| v.as_u64())
+                .ok_or_else(|| format!("vesting for {address}: missing {field}"))
+        };
+        let amount = |v: &serde_json::Value| {
+            v.get("amount")
+                .and_then(|a| a.as_str())
+                .and_then(|a| a.parse::<u128>().ok())
+                .ok_or_else(|| format!("vesting for {address}: invalid amount"))
+        };
+        let original_vesting = amount(value)?;
+        let schedule = match value.get("type").and_then(|v| v.as_str()) {
+            Some("continuous") => {
+                let (start_height, end_height) = (height("start_height")?, height("end_height")?);
+                if end_height <= start_height {
+                    return Err(format!(
+                        "vesting for {address}: end_height must be above start_height"
+                    ));
+                }
+                VestingSchedule::Continuous {
+                    start_height,
+                    end_height,
+                }
+            }
+            Some("periodic") => {
+                let periods = value
+                    .get("periods")
+                    .and_then(|v| v.as_array())
+                    .ok_or_else(|| format!("vesting for {address}: missing periods"))?
+                    .iter()
+                    .map(|p| {
+                        Ok(VestingPeriod {
+                            length: p.get("length").and_then(|v| v.as_u64()).unwrap_or(0),
+                            amount: amount(p)?,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, String>>()?;
+                let total = periods.iter().map(|p| p.amount).sum::<u128>();
+                if total != original_vesting {
+                    return Err(format!(
+                        "vesting for {address}: periods sum to {total}, expected {original_vesting}"
+                    ));
+                }
+                VestingSchedule::Periodic {
+                    start_height: height("start_height")?,
+                    periods,
+                }
+            }
+            Some("delayed") => VestingSchedule::Delayed {
+                end_height: height("end_height")?,
+            },
+            other => {
+                return Err(format!(
+                    "vesting for {address}: unknown type {}",
+                    other.unwrap_or("<none>")
+                ))
+            }
+        };
+        Ok(Self {
+            address: address.to_string(),
+            denom: value
+                .get("denom")
+                .and_then(|v| v.as_str())
+                .unwrap_or("udgt")
+                .to_string(),
+            original_vesting,
+            schedule,
+            delegated_vesting: 0,
+            delegated_free: 0,
+        })
+    }
+}
+
+/// Vesting account module over a KV store (see `ScheduleModule`)
+pub struct VestingModule<K = Arc<Storage>> {
+    kv: K,
+}
+
+impl VestingModule {
+    pub fn new(storage: Arc<Storage>) -> Self {
+        Self { kv: storage }
+    }
+}
+
+impl<K: KvStore> VestingModule<K> {
+    pub fn with_store(kv: K) -> Self {
+        Self { kv }
+    }
+
+    fn key(address: &str) -> String {
+        format!("vesting:{address}")
+    }
+
+    pub fn get(&self, address: &str) -> Option<VestingAccount> {
+        self.kv
+            .kv_get(&Self::key(address))
+            .and_then(|v| bincode::deserialize(&v).ok())
+    }
+
+    pub fn set(&mut self, account: &VestingAccount) -> Result<(), String> {
+        self.kv.kv_put(
+            &Self::key(&account.address),
+            bincode::serialize(account).map_err(|e| e.to_string())?,
+        )
+    }
+
+    /// Balance of `denom` that `address` cannot spend at `height`
+    pub fn locked(&self, address: &str, denom: &str, height: u64) -> u128 {
+        self.get(address)
+            .filter(|a| a.denom == denom)
+            .map(|a| a.locked(height))
+            .unwrap_or(0)
+    }
+
+    /// Bonding may use locked tokens; record which part of `amount` was still vesting
+    pub fn track_delegation(&mut self, address: &str, height: u64, amount: u128) -> Result<(), String> {
+        match self.get(address) {
+            Some(mut account) if account.denom == "udgt" => {
+                account.track_delegation(height, amount);
+                self.set(&account)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn track_undelegation(&mut self, address: &str, amount: u128) -> Result<(), String> {
+        match self.get(address) {
+            Some(mut account) if account.denom == "udgt" => {
+                account.track_undelegation(amount);
+                self.set(&account)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Balance of `denom` that `address` can move at `height` (balance minus vesting lockup)
+pub fn spendable_balance(state: &mut State, address: &str, denom: &str, height: u64) -> u128 {
+    let locked = VestingModule::with_store(&mut *state).locked(address, denom, height);
+    state.balance_of(address, denom).saturating_sub(locked)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn account(schedule: VestingSchedule) -> VestingAccount {
+        VestingAccount {
+            address: "team".to_string(),
+            denom: "udgt".to_string(),
+            original_vesting: 1_000,
+            schedule,
+            delegated_vesting: 0,
+            delegated_free: 0,
+        }
+    }
+
+    #[test]
+    fn test_schedules_vest_by_height() {
+        let linear = account(VestingSchedule::Continuous {
+            start_height: 100,
+            end_height: 200,
+        });
+        assert_eq!(
+            [50, 100, 150, 200, 300].map(|h| linear.vested(h)),
+            [0, 0, 500, 1_000, 1_000]
+        );
+
+        let cliff = account(VestingSchedule::Delayed { end_height: 50 });
+        assert_eq!((cliff.locked(49), cliff.locked(50)), (1_000, 0));
+
+        let periodic = account(VestingSchedule::Periodic {
+            start_height: 10,
+            periods: vec![
+                VestingPeriod { length: 10, amount: 250 },
+                VestingPeriod { length: 10, amount: 750 },
+            ],
+        });
+        assert_eq!(
+            [19, 20, 29, 30].map(|h| periodic.vested(h)),
+            [0, 250, 250, 1_000]
+        );
+    }
+
+    #[test]
+    fn test_delegation_bonds_vesting_tokens_first() {
+        let mut acc = account(VestingSchedule::Delayed { end_height: 100 });
+        acc.track_delegation(10, 600);
+        assert_eq!((acc.delegated_vesting, acc.delegated_free), (600, 0));
+        assert_eq!(acc.locked(10), 400);
+
+        // Only 400 are still locked in the balance; the rest of this bond is free tokens
+        acc.track_delegation(10, 500);
+        assert_eq!((acc.delegated_vesting, acc.delegated_free), (1_000, 100));
+        assert_eq!(acc.locked(10), 0);
+
+        // Free tokens are unbonded first, then the vesting ones lock again
+        acc.track_undelegation(300);
+        assert_eq!((acc.delegated_vesting, acc.delegated_free), (800, 0));
+        assert_eq!(acc.locked(10), 200);
+    }
+
+    #[test]
+    fn test_from_genesis() {
+        let periodic = json!({
+            "type": "periodic",
+            "amount": "1000",
+            "start_height": 0,
+            "periods": [{ "length": 5, "amount": "400" }, { "length": 5, "amount": "600" }]
+        });
+        let acc = VestingAccount::from_genesis("team", &periodic).unwrap();
+        assert_eq!((acc.denom.as_str(), acc.original_vesting), ("udgt", 1_000));
+        assert_eq!(acc.vested(5), 400);
+
+        let bad_sum = json!({
+            "type": "periodic",
+            "amount": "999",
+            "start_height": 0,
+            "periods": [{ "length": 5, "amount": "1000" }]
+        });
+        assert!(VestingAccount::from_genesis("team", &bad_sum).is_err());
+        assert!(VestingAccount::from_genesis("team", &json!({ "type": "cliff", "amount": "1" })).is_err());
+    }
+}
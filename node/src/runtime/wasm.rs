@@ -83,6 +83,15 @@ impl WasmRuntime {
         }
     }
 
+    /// Copy for dry runs: deployments and contract state start as this runtime's and
+    /// change independently of it
+    pub fn fork(&self) -> Self {
+        let fork = Self::new();
+        *fork.deployed_contracts.lock().unwrap() = self.deployed_contracts.lock().unwrap().clone();
+        *fork.contract_state.lock().unwrap() = self.contract_state.lock().unwrap().clone();
+        fork
+    }
+
     /// Deploy a WASM contract
     pub fn deploy_contract(
         &self,
@@ -1,7 +1,10 @@
 use crate::storage::state::Storage;
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+
+pub mod overlay;
+pub use overlay::{ReadSet, StateChange, StateOverlay};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AccountState {
@@ -52,6 +55,12 @@ impl AccountState {
 pub struct State {
     pub accounts: HashMap<String, AccountState>,
     pub storage: Arc<Storage>,
+    // Forked states (see `fork`) never write through to storage
+    ephemeral: bool,
+    // Module KV writes buffered by ephemeral states (None = deleted)
+    kv_writes: BTreeMap<String, Option<Vec<u8>>>,
+    // Read log of tracked overlays, shared with forks (see `StateOverlay::tracked`)
+    reads: Option<Arc<Mutex<ReadSet>>>,
 }
 
 impl Default for State {
@@ -66,6 +75,9 @@ impl Default for State {
             Self {
                 accounts: HashMap::new(),
                 storage: Arc::new(storage),
+                ephemeral: false,
+                kv_writes: BTreeMap::new(),
+                reads: None,
             }
         }
         #[cfg(not(test))]
@@ -82,6 +94,9 @@ impl State {
         Self {
             accounts: HashMap::new(),
             storage,
+            ephemeral: false,
+            kv_writes: BTreeMap::new(),
+            reads: None,
         }
     }
 
@@ -100,10 +115,60 @@ impl State {
         Self {
             accounts: HashMap::new(),
             storage,
+            ephemeral: false,
+            kv_writes: BTreeMap::new(),
+            reads: None,
+        }
+    }
+
+    /// Copy-on-write fork for dry runs: reads fall through to storage,
+    /// writes stay in the in-memory cache and are dropped with the fork.
+    pub fn fork(&self) -> Self {
+        Self {
+            accounts: self.accounts.clone(),
+            storage: self.storage.clone(),
+            ephemeral: true,
+            kv_writes: self.kv_writes.clone(),
+            reads: self.reads.clone(),
+        }
+    }
+
+    /// True for states created by `fork`
+    pub fn is_ephemeral(&self) -> bool {
+        self.ephemeral
+    }
+
+    fn record_read(&self, record: impl FnOnce(&mut ReadSet)) {
+        if let Some(reads) = &self.reads {
+            if let Ok(mut reads) = reads.lock() {
+                record(&mut reads);
+            }
+        }
+    }
+
+    fn persist_balances(&self, addr: &str, balances: &BTreeMap<String, u128>) {
+        if !self.ephemeral {
+            let _ = self.storage.set_balances_db(addr, balances);
         }
     }
 
+    fn persist_nonce(&self, addr: &str, nonce: u64) {
+        if !self.ephemeral {
+            let _ = self.storage.set_nonce_db(addr, nonce);
+        }
+    }
+
+    /// Replace a whole account (balances and nonce)
+    fn put_account(&mut self, addr: &str, account: AccountState) {
+        self.persist_balances(addr, &account.balances);
+        self.persist_nonce(addr, account.nonce);
+        self.accounts.insert(addr.to_string(), account);
+    }
+
     pub fn get_account(&mut self, addr: &str) -> AccountState {
+        self.record_read(|r| {
+            r.accounts.insert(addr.to_string());
+        });
         // lazy load DB
         if let Some(a) = self.accounts.get(addr) {
             return a.clone();
@@ -118,6 +183,9 @@ impl State {
     /// Lightweight read-only snapshot of an account state without mutating caches.
     /// Uses in-memory cache if present, otherwise reads directly from storage.
     pub fn snapshot_account(&self, addr: &str) -> AccountState {
+        self.record_read(|r| {
+            r.accounts.insert(addr.to_string());
+        });
         if let Some(a) = self.accounts.get(addr) {
             a.clone()
         } else {
@@ -181,9 +249,9 @@ impl State {
         self.accounts.insert(to.to_string(), recv.clone());
 
         // Persist to storage
-        let _ = self.storage.set_balances_db(from, &sender.balances);
-        let _ = self.storage.set_nonce_db(from, sender.nonce);
-        let _ = self.storage.set_balances_db(to, &recv.balances);
+        self.persist_balances(from, &sender.balances);
+        self.persist_nonce(from, sender.nonce);
+        self.persist_balances(to, &recv.balances);
 
         Ok(())
     }
@@ -199,7 +267,7 @@ impl State {
         let mut a = self.get_account(addr);
         a.add_balance(denom, amount);
         self.accounts.insert(addr.to_string(), a.clone());
-        let _ = self.storage.set_balances_db(addr, &a.balances);
+        self.persist_balances(addr, &a.balances);
     }
 
     /// Legacy credit for backward compatibility (uses udgt as default)
@@ -212,7 +280,7 @@ impl State {
         let mut a = self.get_account(addr);
         a.set_balance(denom, amount);
         self.accounts.insert(addr.to_string(), a.clone());
-        let _ = self.storage.set_balances_db(addr, &a.balances);
+        self.persist_balances(addr, &a.balances);
     }
 
     /// Increment nonce for an address (used by execution engine)
@@ -220,7 +288,7 @@ impl State {
         let mut a = self.get_account(addr);
         a.nonce += 1;
         self.accounts.insert(addr.to_string(), a.clone());
-        let _ = self.storage.set_nonce_db(addr, a.nonce);
+        self.persist_nonce(addr, a.nonce);
     }
 
     /// Test helper: set account balance for testing (uses default denomination)
@@ -237,3 +305,103 @@ impl State {
         }
     }
 }
+
+/// Key/value access for runtime module stores (dead man switch, fee grants, ...).
+/// `Arc<Storage>` writes straight to RocksDB; `State` writes through unless it is
+/// ephemeral (a fork or overlay), in which case writes are buffered in memory.
+pub trait KvStore {
+    fn kv_get(&self, key: &str) -> Option<Vec<u8>>;
+    fn kv_put(&mut self, key: &str, value: Vec<u8>) -> Result<(), String>;
+    fn kv_delete(&mut self, key: &str) -> Result<(), String>;
+    /// Every entry whose key starts with `prefix`, in key order
+    fn kv_scan_prefix(&self, prefix: &str) -> Vec<(String, Vec<u8>)>;
+}
+
+impl KvStore for Arc<Storage> {
+    fn kv_get(&self, key: &str) -> Option<Vec<u8>> {
+        self.db.get(key).ok().flatten()
+    }
+
+    fn kv_put(&mut self, key: &str, value: Vec<u8>) -> Result<(), String> {
+        self.db.put(key, value).map_err(|e| e.to_string())
+    }
+
+    fn kv_delete(&mut self, key: &str) -> Result<(), String> {
+        self.db.delete(key).map_err(|e| e.to_string())
+    }
+
+    fn kv_scan_prefix(&self, prefix: &str) -> Vec<(String, Vec<u8>)> {
+        self.scan_prefix(prefix)
+    }
+}
+
+impl KvStore for State {
+    fn kv_get(&self, key: &str) -> Option<Vec<u8>> {
+        self.record_read(|r| {
+            r.kv_keys.insert(key.to_string());
+        });
+        match self.kv_writes.get(key) {
+            Some(buffered) => buffered.clone(),
+            None => self.storage.kv_get(key),
+        }
+    }
+
+    fn kv_put(&mut self, key: &str, value: Vec<u8>) -> Result<(), String> {
+        if self.ephemeral {
+            self.kv_writes.insert(key.to_string(), Some(value));
+            Ok(())
+        } else {
+            self.storage.kv_put(key, value)
+        }
+    }
+
+    fn kv_delete(&mut self, key: &str) -> Result<(), String> {
+        if self.ephemeral {
+            self.kv_writes.insert(key.to_string(), None);
+            Ok(())
+        } else {
+            self.storage.kv_delete(key)
+        }
+    }
+
+    fn kv_scan_prefix(&self, prefix: &str) -> Vec<(String, Vec<u8>)> {
+        self.record_read(|r| {
+            r.kv_prefixes.insert(prefix.to_string());
+        });
+        let mut merged: BTreeMap<String, Vec<u8>> =
+            self.storage.kv_scan_prefix(prefix).into_iter().collect();
+        let buffered = self
+            .kv_writes
+            .range(prefix.to_string()..)
+            .take_while(|(k, _)| k.starts_with(prefix));
+        for (key, value) in buffered {
+            match value {
+                Some(v) => {
+                    merged.insert(key.clone(), v.clone());
+                }
+                None => {
+                    merged.remove(key);
+                }
+            }
+        }
+        merged.into_iter().collect()
+    }
+}
+
+impl<K: KvStore + ?Sized> KvStore for &mut K {
+    fn kv_get(&self, key: &str) -> Option<Vec<u8>> {
+        (**self).kv_get(key)
+    }
+
+    fn kv_put(&mut self, key: &str, value: Vec<u8>) -> Result<(), String> {
+        (**self).kv_put(key, value)
+    }
+
+    fn kv_delete(&mut self, key: &str) -> Result<(), String> {
+        (**self).kv_delete(key)
+    }
+
+    fn kv_scan_prefix(&self, prefix: &str) -> Vec<(String, Vec<u8>)> {
+        (**self).kv_scan_prefix(prefix)
+    }
+}
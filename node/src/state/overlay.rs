@@ -0,0 +1,267 @@
+use super::{KvStore, State};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Mutex};
+
+/// One entry of the journal produced when an overlay is committed
+#[derive(Debug, Clone, PartialEq)]
+pub enum StateChange {
+    Balance {
+        address: String,
+        denom: String,
+        old: u128,
+        new: u128,
+    },
+    Nonce {
+        address: String,
+        old: u64,
+        new: u64,
+    },
+    /// Module KV entry (`None` = absent)
+    Kv {
+        key: String,
+        old: Option<Vec<u8>>,
+        new: Option<Vec<u8>>,
+    },
+}
+
+/// Everything a tracked overlay (and any fork of it) has read
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReadSet {
+    pub accounts: BTreeSet<String>,
+    pub kv_keys: BTreeSet<String>,
+    /// Prefixes passed to `kv_scan_prefix`
+    pub kv_prefixes: BTreeSet<String>,
+}
+
+/// Write-buffering layer over a `State`. Message handlers use it as a normal `State`
+/// (it derefs to one); balances, nonces and module KV writes stay in memory until
+/// `commit` applies them to the base. Dropping the overlay discards all of them.
+pub struct StateOverlay {
+    inner: State,
+}
+
+impl StateOverlay {
+    pub fn new(base: &State) -> Self {
+        // A persistent base has flushed every write, so reads can fall through to storage.
+        // An ephemeral base keeps its writes in memory and has to be copied.
+        let inner = if base.is_ephemeral() {
+            base.fork()
+        } else {
+            State {
+                accounts: HashMap::new(),
+                storage: base.storage.clone(),
+                ephemeral: true,
+                kv_writes: BTreeMap::new(),
+                reads: None,
+            }
+        };
+        Self { inner }
+    }
+
+    /// Overlay that also records every account and KV key it reads, including reads made
+    /// through forks of it (nested overlays); used for conflict detection
+    pub fn tracked(base: &State) -> Self {
+        let mut overlay = Self::new(base);
+        overlay.inner.reads = Some(Arc::new(Mutex::new(ReadSet::default())));
+        overlay
+    }
+
+    /// Reads recorded so far (empty for untracked overlays)
+    pub fn read_set(&self) -> ReadSet {
+        self.inner
+            .reads
+            .as_ref()
+            .and_then(|reads| reads.lock().ok().map(|r| r.clone()))
+            .unwrap_or_default()
+    }
+
+    /// Journal of what `commit` would change in `base`, in deterministic order
+    pub fn changes(&self, base: &State) -> Vec<StateChange> {
+        let mut changes = Vec::new();
+        let mut addresses: Vec<&String> = self.inner.accounts.keys().collect();
+        addresses.sort();
+        for address in addresses {
+            let before = base.snapshot_account(address);
+            let after = &self.inner.accounts[address];
+            let denoms: BTreeSet<&String> = before
+                .balances
+                .keys()
+                .chain(after.balances.keys())
+                .collect();
+            for denom in denoms {
+                let (old, new) = (before.balance_of(denom), after.balance_of(denom));
+                if old != new {
+                    changes.push(StateChange::Balance {
+                        address: address.clone(),
+                        denom: denom.clone(),
+                        old,
+                        new,
+                    });
+                }
+            }
+            if before.nonce != after.nonce {
+                changes.push(StateChange::Nonce {
+                    address: address.clone(),
+                    old: before.nonce,
+                    new: after.nonce,
+                });
+            }
+        }
+        for (key, new) in &self.inner.kv_writes {
+            let old = base.kv_get(key);
+            if old != *new {
+                changes.push(StateChange::Kv {
+                    key: key.clone(),
+                    old,
+                    new: new.clone(),
+                });
+            }
+        }
+        changes
+    }
+
+    /// Apply the buffered writes to `base` and return the journal of what changed
+    pub fn commit(self, base: &mut State) -> Vec<StateChange> {
+        let changes = self.changes(base);
+        let mut touched = BTreeSet::new();
+        for change in &changes {
+            match change {
+                StateChange::Balance { address, .. } | StateChange::Nonce { address, .. } => {
+                    touched.insert(address.clone());
+                }
+                StateChange::Kv { key, new, .. } => {
+                    // Like account persistence, storage write errors are not fatal here
+                    let _ = match new {
+                        Some(value) => base.kv_put(key, value.clone()),
+                        None => base.kv_delete(key),
+                    };
+                }
+            }
+        }
+        let mut inner = self.inner;
+        for address in touched {
+            if let Some(account) = inner.accounts.remove(&address) {
+                base.put_account(&address, account);
+            }
+        }
+        changes
+    }
+}
+
+impl Deref for StateOverlay {
+    type Target = State;
+
+    fn deref(&self) -> &State {
+        &self.inner
+    }
+}
+
+impl DerefMut for StateOverlay {
+    fn deref_mut(&mut self) -> &mut State {
+        &mut self.inner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::state::Storage;
+    use std::sync::Arc;
+
+    fn state() -> (tempfile::TempDir, State) {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = Arc::new(Storage::open(dir.path().join("state.db")).unwrap());
+        (dir, State::new(storage))
+    }
+
+    #[test]
+    fn test_overlay_commit_applies_and_journals() {
+        let (_dir, mut base) = state();
+        base.set_balance("alice", "udgt", 100);
+
+        let mut overlay = StateOverlay::new(&base);
+        overlay.set_balance("alice", "udgt", 60);
+        overlay.set_balance("bob", "udgt", 40);
+        overlay.increment_nonce("alice");
+        overlay.kv_put("mod:key", b"v".to_vec()).unwrap();
+
+        // Nothing visible in the base before commit
+        assert_eq!(base.balance_of("bob", "udgt"), 0);
+        assert_eq!(base.kv_get("mod:key"), None);
+
+        let changes = overlay.commit(&mut base);
+        assert_eq!(changes.len(), 4);
+        assert!(changes.contains(&StateChange::Nonce {
+            address: "alice".to_string(),
+            old: 0,
+            new: 1,
+        }));
+        assert_eq!(base.balance_of("alice", "udgt"), 60);
+        assert_eq!(base.nonce_of("alice"), 1);
+
+        // Committed writes reached storage
+        let mut reloaded = State::new(base.storage.clone());
+        assert_eq!(reloaded.balance_of("bob", "udgt"), 40);
+        assert_eq!(reloaded.kv_get("mod:key"), Some(b"v".to_vec()));
+    }
+
+    #[test]
+    fn test_dropped_overlay_discards_everything() {
+        let (_dir, mut base) = state();
+        base.set_balance("alice", "udgt", 100);
+        base.kv_put("mod:a", b"1".to_vec()).unwrap();
+
+        {
+            let mut overlay = StateOverlay::new(&base);
+            overlay.set_balance("alice", "udgt", 0);
+            overlay.kv_delete("mod:a").unwrap();
+            overlay.kv_put("mod:b", b"2".to_vec()).unwrap();
+            assert_eq!(overlay.kv_scan_prefix("mod:").len(), 1);
+        }
+
+        assert_eq!(base.balance_of("alice", "udgt"), 100);
+        assert_eq!(base.kv_scan_prefix("mod:").len(), 1);
+        assert_eq!(base.storage.kv_get("mod:b"), None);
+    }
+
+    #[test]
+    fn test_overlay_over_fork_sees_fork_writes() {
+        let (_dir, base) = state();
+        let mut fork = base.fork();
+        fork.set_balance("alice", "udgt", 5);
+        fork.kv_put("mod:x", b"x".to_vec()).unwrap();
+
+        let mut overlay = StateOverlay::new(&fork);
+        assert_eq!(overlay.balance_of("alice", "udgt"), 5);
+        assert_eq!(overlay.kv_get("mod:x"), Some(b"x".to_vec()));
+        overlay.set_balance("alice", "udgt", 7);
+        overlay.commit(&mut fork);
+
+        assert_eq!(fork.balance_of("alice", "udgt"), 7);
+        // The persistent storage never saw the fork's writes
+        assert_eq!(base.storage.kv_get("mod:x"), None);
+        assert_eq!(base.storage.get_balances_db("alice").get("udgt"), None);
+    }
+
+    #[test]
+    fn test_tracked_overlay_records_reads_of_nested_forks() {
+        let (_dir, base) = state();
+        let mut overlay = StateOverlay::tracked(&base);
+        overlay.balance_of("alice", "udgt");
+        overlay.kv_get("mod:a");
+        {
+            // A nested overlay that is dropped still leaves its reads behind
+            let mut nested = StateOverlay::new(&overlay);
+            nested.nonce_of("bob");
+            nested.kv_scan_prefix("mod:b:");
+        }
+
+        let reads = overlay.read_set();
+        assert_eq!(reads.accounts.len(), 2);
+        assert!(reads.accounts.contains("bob"));
+        assert!(reads.kv_keys.contains("mod:a"));
+        assert!(reads.kv_prefixes.contains("mod:b:"));
+        assert_eq!(StateOverlay::new(&base).read_set(), ReadSet::default());
+    }
+}
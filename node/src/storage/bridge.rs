@@ -1,3 +1,4 @@
+use crate::state::KvStore;
 use rocksdb::DB;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
@@ -70,6 +71,12 @@ impl<'a> BridgeStore<'a> {
             .put(Self::key_halted(), if halted { b"1" } else { b"0" })?;
         Ok(())
     }
+    /// Set the halt flag through a module KV store, e.g. a state overlay that is only
+    /// committed if the caller succeeds
+    pub fn set_halted_in(kv: &mut impl KvStore, halted: bool) -> Result<(), String> {
+        let value = if halted { b"1" } else { b"0" };
+        kv.kv_put(Self::key_halted(), value.to_vec())
+    }
 
     pub fn get_validators(&self) -> Vec<BridgeValidator> {
         self.db
@@ -175,6 +182,7 @@ use ed25519_dalek::{PublicKey, Signature, Verifier};
 pub fn verify_bridge_message(
     msg: &BridgeMessage,
     validators: &[BridgeValidator],
+    quorum: (u64, u64),
 ) -> Result<(), String> {
     if msg.signatures.len() != msg.signers.len() {
         return Err("SignersSignaturesLengthMismatch".into());
@@ -213,8 +221,9 @@ pub fn verify_bridge_message(
             valid_count += 1;
         }
     }
-    let total = validators.len() as f32;
-    let needed = ((total * 2.0) / 3.0).ceil() as u32; // >= 2/3
+    // At least `numerator/denominator` of the validators, rounded up
+    let (numerator, denominator) = quorum;
+    let needed = (validators.len() as u64 * numerator).div_ceil(denominator.max(1)) as u32;
     if valid_count >= needed {
         Ok(())
     } else {
@@ -0,0 +1,333 @@
+use super::receipts::TxReceipt;
+use super::state::Storage;
+use serde::{Deserialize, Serialize};
+
+/// Typed event emitted during transaction execution, e.g.
+/// `{"type":"transfer","attributes":[{"key":"from","value":"dyt1..."}]}`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Event {
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub attributes: Vec<EventAttribute>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EventAttribute {
+    pub key: String,
+    pub value: String,
+}
+
+impl Event {
+    pub fn new(event_type: &str) -> Self {
+        Self {
+            event_type: event_type.to_string(),
+            attributes: Vec::new(),
+        }
+    }
+
+    /// Append an attribute (builder style)
+    pub fn attr(mut self, key: &str, value: impl ToString) -> Self {
+        self.attributes.push(EventAttribute {
+            key: key.to_string(),
+            value: value.to_string(),
+        });
+        self
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|a| a.key == key)
+            .map(|a| a.value.as_str())
+    }
+}
+
+/// An event together with its position on chain, as returned by `/events`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct IndexedEvent {
+    pub block_height: u64,
+    pub tx_hash: String,
+    pub tx_index: u32,
+    pub event_index: u32,
+    #[serde(flatten)]
+    pub event: Event,
+}
+
+/// Event query; all set conditions must match
+#[derive(Debug, Clone, Default)]
+pub struct EventFilter {
+    pub event_type: Option<String>,
+    pub attributes: Vec<(String, String)>,
+    pub from_height: Option<u64>,
+    pub to_height: Option<u64>,
+    pub limit: usize,
+}
+
+impl EventFilter {
+    fn matches(&self, e: &IndexedEvent) -> bool {
+        self.event_type
+            .as_ref()
+            .is_none_or(|t| *t == e.event.event_type)
+            && self.from_height.is_none_or(|h| e.block_height >= h)
+            && self.to_height.is_none_or(|h| e.block_height <= h)
+            && self
+                .attributes
+                .iter()
+                .all(|(k, v)| e.event.get(k) == Some(v.as_str()))
+    }
+}
+
+/// `tx_index` of events emitted by block hooks rather than transactions (their `tx_hash`
+/// is empty)
+pub const BLOCK_EVENT_TX_INDEX: u32 = u32::MAX;
+
+/// Default and maximum number of events returned by one query
+pub const DEFAULT_EVENT_QUERY_LIMIT: usize = 100;
+pub const MAX_EVENT_QUERY_LIMIT: usize = 1000;
+
+// Index layout:
+//   evt:{type}:{height:016x}:{tx_index:08x}:{event_index:04x} -> json(IndexedEvent)
+//   evt_attr:{type}:{hex(key)}:{hex(value)}:{height:016x}:{tx_index:08x}:{event_index:04x}
+//     -> primary `evt:` key
+//   evt_h:{height:016x}:{tx_index:08x}:{event_index:04x} -> primary `evt:` key
+// Attribute keys/values are hex encoded so arbitrary strings cannot break the prefix layout.
+fn position(height: u64, tx_index: u32, event_index: u32) -> String {
+    format!("{height:016x}:{tx_index:08x}:{event_index:04x}")
+}
+
+fn attr_prefix(event_type: &str, key: &str, value: &str) -> String {
+    format!(
+        "evt_attr:{event_type}:{}:{}:",
+        hex::encode(key),
+        hex::encode(value)
+    )
+}
+
+impl Storage {
+    /// Index the events of committed receipts (failed receipts carry no events)
+    pub fn index_events(&self, receipts: &[TxReceipt]) -> anyhow::Result<()> {
+        for r in receipts {
+            let (Some(height), Some(tx_index)) = (r.block_height, r.index) else {
+                continue;
+            };
+            for (event_index, event) in r.events.iter().enumerate() {
+                self.index_event(IndexedEvent {
+                    block_height: height,
+                    tx_hash: r.tx_hash.clone(),
+                    tx_index,
+                    event_index: event_index as u32,
+                    event: event.clone(),
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Index events emitted by block hooks (e.g. scheduled transfer releases)
+    pub fn index_block_events(&self, height: u64, events: &[Event]) -> anyhow::Result<()> {
+        for (event_index, event) in events.iter().enumerate() {
+            self.index_event(IndexedEvent {
+                block_height: height,
+                tx_hash: String::new(),
+                tx_index: BLOCK_EVENT_TX_INDEX,
+                event_index: event_index as u32,
+                event: event.clone(),
+            })?;
+        }
+        Ok(())
+    }
+
+    fn index_event(&self, indexed: IndexedEvent) -> anyhow::Result<()> {
+        let event = &indexed.event;
+        let pos = position(indexed.block_height, indexed.tx_index, indexed.event_index);
+        let key = format!("evt:{}:{pos}", event.event_type);
+        for a in &event.attributes {
+            let attr_key = format!(
+                "{}{pos}",
+                attr_prefix(&event.event_type, &a.key, &a.value)
+            );
+            self.db.put(attr_key, key.as_bytes())?;
+        }
+        self.db.put(format!("evt_h:{pos}"), key.as_bytes())?;
+        self.db.put(&key, serde_json::to_vec(&indexed)?)?;
+        Ok(())
+    }
+
+    /// Query indexed events in chain order. With a type and an attribute condition the
+    /// attribute index is used; with only a type the per-type index is scanned from
+    /// `from_height`; otherwise the height index is scanned from `from_height`.
+    pub fn query_events(&self, filter: &EventFilter) -> Vec<IndexedEvent> {
+        let limit = match filter.limit {
+            0 => DEFAULT_EVENT_QUERY_LIMIT,
+            n => n.min(MAX_EVENT_QUERY_LIMIT),
+        };
+        let from = filter.from_height.unwrap_or(0);
+        let load = |key: &[u8]| {
+            self.db
+                .get(key)
+                .ok()
+                .flatten()
+                .and_then(|v| serde_json::from_slice::<IndexedEvent>(&v).ok())
+        };
+
+        let mut out = Vec::new();
+        match (&filter.event_type, filter.attributes.first()) {
+            (Some(t), Some((k, v))) => {
+                let prefix = attr_prefix(t, k, v);
+                let start = format!("{prefix}{from:016x}");
+                for (_, primary) in self.range_from(&prefix, &start) {
+                    let Some(e) = load(&primary[..]) else { continue };
+                    if filter.to_height.is_some_and(|h| e.block_height > h) {
+                        break;
+                    }
+                    if filter.matches(&e) {
+                        out.push(e);
+                        if out.len() >= limit {
+                            break;
+                        }
+                    }
+                }
+            }
+            (Some(t), None) => {
+                let prefix = format!("evt:{t}:");
+                let start = format!("{prefix}{from:016x}");
+                for (_, raw) in self.range_from(&prefix, &start) {
+                    let Ok(e) = serde_json::from_slice::<IndexedEvent>(&raw) else {
+                        continue;
+                    };
+                    if filter.to_height.is_some_and(|h| e.block_height > h) {
+                        break;
+                    }
+                    if filter.matches(&e) {
+                        out.push(e);
+                        if out.len() >= limit {
+                            break;
+                        }
+                    }
+                }
+            }
+            (None, _) => {
+                let start = format!("evt_h:{from:016x}");
+                for (_, primary) in self.range_from("evt_h:", &start) {
+                    let Some(e) = load(&primary[..]) else { continue };
+                    if filter.to_height.is_some_and(|h| e.block_height > h) {
+                        break;
+                    }
+                    if filter.matches(&e) {
+                        out.push(e);
+                        if out.len() >= limit {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+        out
+    }
+
+    fn range_from<'a>(
+        &'a self,
+        prefix: &'a str,
+        start: &'a str,
+    ) -> impl Iterator<Item = (Box<[u8]>, Box<[u8]>)> + 'a {
+        use rocksdb::{Direction, IteratorMode};
+        self.db
+            .iterator(IteratorMode::From(start.as_bytes(), Direction::Forward))
+            .filter_map(|r| r.ok())
+            .take_while(move |(k, _)| k.starts_with(prefix.as_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::receipts::{TxStatus, RECEIPT_FORMAT_VERSION};
+    use tempfile::tempdir;
+
+    fn receipt(hash: &str, height: u64, events: Vec<Event>) -> TxReceipt {
+        TxReceipt {
+            receipt_version: RECEIPT_FORMAT_VERSION,
+            tx_hash: hash.to_string(),
+            status: TxStatus::Success,
+            block_height: Some(height),
+            index: Some(0),
+            from: "a".to_string(),
+            to: "b".to_string(),
+            amount: 1,
+            fee: 1,
+            nonce: 0,
+            error: None,
+            gas_used: 0,
+            gas_limit: 0,
+            gas_price: 0,
+            gas_refund: 0,
+            success: true,
+            events,
+            gas_schedule_version: 1,
+        }
+    }
+
+    fn transfer(from: &str, to: &str) -> Event {
+        Event::new("transfer").attr("from", from).attr("to", to)
+    }
+
+    #[test]
+    fn test_query_by_type_attribute_and_height() {
+        let dir = tempdir().unwrap();
+        let storage = Storage::open(dir.path().join("test.db")).unwrap();
+        storage
+            .index_events(&[
+                receipt("0x1", 1, vec![transfer("alice", "bob"), Event::new("burn")]),
+                receipt("0x2", 2, vec![transfer("carol", "bob")]),
+                receipt("0x3", 3, vec![transfer("alice", "carol")]),
+            ])
+            .unwrap();
+
+        let by_type = storage.query_events(&EventFilter {
+            event_type: Some("transfer".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(by_type.len(), 3);
+
+        let from_alice = storage.query_events(&EventFilter {
+            event_type: Some("transfer".to_string()),
+            attributes: vec![("from".to_string(), "alice".to_string())],
+            ..Default::default()
+        });
+        let hashes: Vec<_> = from_alice.iter().map(|e| e.tx_hash.as_str()).collect();
+        assert_eq!(hashes, vec!["0x1", "0x3"]);
+
+        let ranged = storage.query_events(&EventFilter {
+            event_type: Some("transfer".to_string()),
+            attributes: vec![("to".to_string(), "bob".to_string())],
+            from_height: Some(2),
+            to_height: Some(2),
+            ..Default::default()
+        });
+        assert_eq!(ranged.len(), 1);
+        assert_eq!(ranged[0].tx_hash, "0x2");
+
+        let all = storage.query_events(&EventFilter {
+            to_height: Some(1),
+            ..Default::default()
+        });
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[1].event.event_type, "burn");
+
+        // Without a type, events come from the height index in chain order
+        let from_bob = storage.query_events(&EventFilter {
+            attributes: vec![("to".to_string(), "bob".to_string())],
+            from_height: Some(1),
+            limit: 1,
+            ..Default::default()
+        });
+        assert_eq!(from_bob.len(), 1);
+        assert_eq!(from_bob[0].tx_hash, "0x1");
+        let later = storage.query_events(&EventFilter {
+            from_height: Some(2),
+            ..Default::default()
+        });
+        let hashes: Vec<_> = later.iter().map(|e| e.tx_hash.as_str()).collect();
+        assert_eq!(hashes, vec!["0x2", "0x3"]);
+    }
+}
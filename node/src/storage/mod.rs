@@ -1,5 +1,6 @@
 pub mod blocks;
 pub mod bridge;
+pub mod events;
 pub mod oracle;
 pub mod receipts;
 pub mod state;
@@ -1,4 +1,6 @@
+use super::events::Event;
 use super::tx::Transaction;
+use crate::gas::GAS_TABLE_VERSION;
 use serde::de::{self, Visitor};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
@@ -57,8 +59,19 @@ pub struct TxReceipt {
     pub gas_used: u64,   // Actual gas consumed during execution
     pub gas_limit: u64,  // Gas limit from the transaction
     pub gas_price: u64,  // Gas price from the transaction (in datt)
-    pub gas_refund: u64, // Gas refund (always 0 for now, stub for future)
+    pub gas_refund: u64, // Unused gas refunded to the fee payer (0 for failed txs)
     pub success: bool,   // Whether the transaction succeeded
+    /// Events emitted by a successful execution (empty for failed or pending txs)
+    #[serde(default)]
+    pub events: Vec<Event>,
+    /// Gas schedule version that priced the transaction (receipts written before schedules
+    /// were versioned used the built-in one)
+    #[serde(default = "built_in_gas_schedule_version")]
+    pub gas_schedule_version: u32,
+}
+
+fn built_in_gas_schedule_version() -> u32 {
+    GAS_TABLE_VERSION
 }
 
 impl TxReceipt {
@@ -80,6 +93,8 @@ impl TxReceipt {
             gas_price: 0,  // Will be set from SignedTx when available
             gas_refund: 0, // Always 0 for now
             success: false,
+            events: Vec::new(),
+            gas_schedule_version: GAS_TABLE_VERSION,
         }
     }
 
@@ -109,6 +124,8 @@ impl TxReceipt {
             gas_price,
             gas_refund: 0, // Always 0 for now
             success: true,
+            events: Vec::new(),
+            gas_schedule_version: GAS_TABLE_VERSION,
         }
     }
 
@@ -139,12 +156,16 @@ impl TxReceipt {
             gas_price,
             gas_refund: 0, // Always 0 for now
             success: false,
+            events: Vec::new(),
+            gas_schedule_version: GAS_TABLE_VERSION,
         }
     }
 
-    /// Calculate the total fee charged in datt (gas_limit * gas_price)
+    /// Calculate the total fee charged in datt ((gas_limit - gas_refund) * gas_price)
     /// Note: In case of failure, full gas_limit is charged as per specification
     pub fn fee_charged_datt(&self) -> u64 {
-        self.gas_limit.saturating_mul(self.gas_price)
+        self.gas_limit
+            .saturating_sub(self.gas_refund)
+            .saturating_mul(self.gas_price)
     }
 }
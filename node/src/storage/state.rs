@@ -1,7 +1,7 @@
 use super::blocks::Block;
 use super::receipts::TxReceipt;
 use super::tx::Transaction;
-use rocksdb::{Options, DB};
+use rocksdb::{Direction, IteratorMode, Options, DB};
 use std::collections::BTreeMap;
 use std::path::PathBuf;
 
@@ -61,6 +61,7 @@ impl Storage {
             self.db
                 .put(format!("rcpt:{}", r.tx_hash), serde_json::to_vec(r)?)?;
         }
+        self.index_events(receipts)?;
         Ok(())
     }
     pub fn height(&self) -> u64 {
@@ -240,4 +241,14 @@ impl Storage {
             .put(format!("acct:nonce:{addr}"), bincode::serialize(&nonce)?)?;
         Ok(())
     }
+
+    /// Collect every key/value pair whose key starts with `prefix`, in key order
+    pub fn scan_prefix(&self, prefix: &str) -> Vec<(String, Vec<u8>)> {
+        self.db
+            .iterator(IteratorMode::From(prefix.as_bytes(), Direction::Forward))
+            .filter_map(Result::ok)
+            .take_while(|(k, _)| k.starts_with(prefix.as_bytes()))
+            .map(|(k, v)| (String::from_utf8_lossy(&k).into_owned(), v.to_vec()))
+            .collect()
+    }
 }
@@ -1,3 +1,6 @@
+use crate::runtime::governance::{ProposalTrack, ProposalType, VoteOption, WeightedVoteOption};
+use crate::types::multisig::MultisigAuth;
+use crate::types::tx::FeePayerSignature;
 use serde::{Deserialize, Serialize};
 // Use canonical SignatureAlgorithm from dytallix_pqc crate
 pub use dytallix_pqc::SignatureAlgorithm;
@@ -52,6 +55,15 @@ pub struct Transaction {
     // This allows execution engine to process all messages, not just the first one
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub messages: Option<Vec<TxMessage>>,
+    // Multisig accounts carry k-of-n signatures instead of `signature`/`public_key`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub multisig: Option<MultisigAuth>,
+    // Sponsored transactions: the upfront fee is charged to `fee_payer`, which either
+    // co-signs or has granted `from` a fee allowance
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_payer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_payer_signature: Option<FeePayerSignature>,
 }
 
 /// Serializable message format for storage
@@ -83,9 +95,112 @@ pub enum TxMessage {
         from: String,
         owner: String,
     },
-    // Future message types can be added here:
-    // Delegate { validator: String, amount: u128 },
-    // Vote { proposal_id: u64, option: VoteOption },
+    // Staking messages (amounts in udgt, rewards paid in udrt)
+    Delegate {
+        from: String,
+        validator: String,
+        #[serde(with = "u128_serde")]
+        amount: u128,
+    },
+    Undelegate {
+        from: String,
+        validator: String,
+        #[serde(with = "u128_serde")]
+        amount: u128,
+    },
+    ClaimRewards {
+        from: String,
+    },
+    // Governance messages
+    SubmitProposal {
+        from: String,
+        title: String,
+        description: String,
+        proposal: ProposalType,
+        // Omitted for standard proposals so existing signed messages keep their bytes
+        #[serde(default, skip_serializing_if = "ProposalTrack::is_standard")]
+        track: ProposalTrack,
+    },
+    Deposit {
+        from: String,
+        proposal_id: u64,
+        #[serde(with = "u128_serde")]
+        amount: u128,
+    },
+    Vote {
+        from: String,
+        proposal_id: u64,
+        option: VoteOption,
+    },
+    WeightedVote {
+        from: String,
+        proposal_id: u64,
+        options: Vec<WeightedVoteOption>,
+    },
+    // Contract messages (code and args are hex encoded)
+    ContractDeploy {
+        from: String,
+        code: String,
+    },
+    ContractCall {
+        from: String,
+        contract: String,
+        method: String,
+        args: String,
+    },
+    // Fee grant messages
+    GrantFeeAllowance {
+        from: String,
+        grantee: String,
+        #[serde(with = "u128_serde")]
+        spend_limit: u128,
+        #[serde(default)]
+        expiration_height: Option<u64>,
+    },
+    RevokeFeeAllowance {
+        from: String,
+        grantee: String,
+    },
+    // Scheduled transfers (amount escrowed until release_height)
+    ScheduleTransfer {
+        from: String,
+        to: String,
+        denom: String,
+        #[serde(with = "u128_serde")]
+        amount: u128,
+        release_height: u64,
+        #[serde(default)]
+        cancellable: bool,
+    },
+    CancelSchedule {
+        from: String,
+        id: u64,
+    },
+    CreateDenom {
+        from: String,
+        subdenom: String,
+        symbol: String,
+        decimals: u8,
+    },
+    Mint {
+        from: String,
+        denom: String,
+        #[serde(with = "u128_serde")]
+        amount: u128,
+        #[serde(default)]
+        to: Option<String>,
+    },
+    Burn {
+        from: String,
+        denom: String,
+        #[serde(with = "u128_serde")]
+        amount: u128,
+    },
+    ChangeAdmin {
+        from: String,
+        denom: String,
+        new_admin: String,
+    },
 }
 
 fn default_denom() -> String {
@@ -116,6 +231,9 @@ impl Transaction {
             memo: String::new(),
             denom: "udgt".to_string(),
             messages: None,
+            multisig: None,
+            fee_payer: None,
+            fee_payer_signature: None,
         }
     }
 
@@ -167,6 +285,28 @@ impl Transaction {
         self
     }
 
+    pub fn with_fee_payer(mut self, fee_payer: impl Into<String>) -> Self {
+        self.fee_payer = Some(fee_payer.into());
+        self
+    }
+
+    /// Account charged the upfront fee
+    pub fn fee_payer_or_sender(&self) -> &str {
+        self.fee_payer.as_deref().unwrap_or(&self.from)
+    }
+
+    pub fn with_multisig(mut self, multisig: MultisigAuth) -> Self {
+        self.multisig = Some(multisig);
+        self
+    }
+
+    /// Signatures beyond the first, charged at `per_additional_signature`
+    pub fn additional_signatures(&self) -> usize {
+        self.multisig
+            .as_ref()
+            .map_or(0, MultisigAuth::additional_signatures)
+    }
+
     /// Get canonical transaction fields for signature verification
     pub fn canonical_fields(&self) -> CanonicalTransaction {
         CanonicalTransaction {
@@ -177,14 +317,24 @@ impl Transaction {
             nonce: self.nonce,
             chain_id: self.chain_id.clone(),
             memo: self.memo.clone(),
+            fee_payer: self.fee_payer.clone(),
         }
     }
 
+    /// Size in bytes of the canonical JSON encoding of the full transaction
+    /// (messages, memo, public key and signature included).
+    /// This is the single size used for `max_tx_bytes`, mempool byte limits
+    /// and `per_byte` intrinsic gas. A transaction that cannot be encoded
+    /// reports `usize::MAX` so both limits reject it.
+    pub fn encoded_size(&self) -> usize {
+        crate::crypto::canonical_json(self).map_or(usize::MAX, |bytes| bytes.len())
+    }
+
     /// Extract the signature algorithm from the transaction
     /// For now, we assume all transactions use Dilithium5 as the default
     /// In a full implementation, this would be stored in the transaction metadata
     pub fn signature_algorithm(&self) -> Option<SignatureAlgorithm> {
-        if self.signature.is_some() {
+        if self.signature.is_some() || self.multisig.is_some() {
             Some(SignatureAlgorithm::Dilithium5)
         } else {
             None
@@ -203,4 +353,6 @@ pub struct CanonicalTransaction {
     pub nonce: u64,
     pub chain_id: String,
     pub memo: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_payer: Option<String>,
 }
@@ -1,3 +1,5 @@
+pub mod multisig;
 pub mod tx;
 
+pub use multisig::{MultisigAuth, MultisigPubKey, MultisigSignature};
 pub use tx::{Msg, SignedTx, ValidationError};
@@ -0,0 +1,208 @@
+use crate::addr::get_address;
+use crate::crypto::{verify, PQCAlgorithm};
+use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose::STANDARD as B64, Engine};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// Upper bound on keys in a multisig account (keeps verification cost bounded)
+pub const MAX_MULTISIG_KEYS: usize = 16;
+
+/// Domain separator so a multisig address can never collide with a single-key address
+const MULTISIG_ADDRESS_DOMAIN: &[u8] = b"dytallix/multisig/v1";
+
+/// k-of-n PQC public key set. The account address is derived from the threshold and the
+/// sorted key set, so the same signers and threshold always map to the same address.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct MultisigPubKey {
+    pub threshold: u32,
+    pub public_keys: Vec<String>, // base64, sorted ascending, unique
+}
+
+/// One member signature; `index` points into `MultisigPubKey::public_keys`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct MultisigSignature {
+    pub index: u32,
+    pub signature: String, // base64
+}
+
+/// Multisig authorization attached to a transaction in place of a single key/signature
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct MultisigAuth {
+    pub public_key: MultisigPubKey,
+    pub signatures: Vec<MultisigSignature>,
+}
+
+impl MultisigPubKey {
+    /// Build a key set from base64 public keys in any order
+    pub fn new(threshold: u32, mut public_keys: Vec<String>) -> Result<Self> {
+        public_keys.sort();
+        let key = Self {
+            threshold,
+            public_keys,
+        };
+        key.validate()?;
+        Ok(key)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        let n = self.public_keys.len();
+        if n == 0 {
+            return Err(anyhow!("multisig requires at least one public key"));
+        }
+        if n > MAX_MULTISIG_KEYS {
+            return Err(anyhow!(
+                "multisig supports at most {} keys, got {}",
+                MAX_MULTISIG_KEYS,
+                n
+            ));
+        }
+        if self.threshold == 0 || self.threshold as usize > n {
+            return Err(anyhow!(
+                "invalid multisig threshold {} for {} keys",
+                self.threshold,
+                n
+            ));
+        }
+        if self.public_keys.windows(2).any(|w| w[0] >= w[1]) {
+            return Err(anyhow!("multisig public keys must be sorted and unique"));
+        }
+        for pk in &self.public_keys {
+            B64.decode(pk)
+                .map_err(|e| anyhow!("invalid multisig public key encoding: {}", e))?;
+        }
+        Ok(())
+    }
+
+    /// Deterministic account address for this key set and threshold
+    pub fn address(&self) -> String {
+        let mut preimage = MULTISIG_ADDRESS_DOMAIN.to_vec();
+        preimage.extend_from_slice(&self.threshold.to_be_bytes());
+        for pk in &self.public_keys {
+            let bytes = B64.decode(pk).unwrap_or_default();
+            preimage.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+            preimage.extend_from_slice(&bytes);
+        }
+        get_address(&preimage)
+    }
+}
+
+impl MultisigSignature {
+    pub fn new(index: u32, signature: &[u8]) -> Self {
+        Self {
+            index,
+            signature: B64.encode(signature),
+        }
+    }
+}
+
+impl MultisigAuth {
+    /// Signatures beyond the first, charged at `per_additional_signature`
+    pub fn additional_signatures(&self) -> usize {
+        self.signatures.len().saturating_sub(1)
+    }
+
+    /// Verify at least `threshold` distinct members signed `msg`. Every supplied
+    /// signature must be valid; duplicates and out-of-range indices are rejected.
+    pub fn verify(&self, msg: &[u8], algorithm: PQCAlgorithm) -> Result<()> {
+        self.public_key.validate()?;
+        let mut seen = BTreeSet::new();
+        for sig in &self.signatures {
+            let pk = self
+                .public_key
+                .public_keys
+                .get(sig.index as usize)
+                .ok_or_else(|| anyhow!("multisig signature index {} out of range", sig.index))?;
+            if !seen.insert(sig.index) {
+                return Err(anyhow!("duplicate multisig signature for index {}", sig.index));
+            }
+            let pk = B64
+                .decode(pk)
+                .map_err(|e| anyhow!("invalid public key encoding: {}", e))?;
+            let sig_bytes = B64
+                .decode(&sig.signature)
+                .map_err(|e| anyhow!("invalid signature encoding: {}", e))?;
+            verify(&pk, msg, &sig_bytes, algorithm.clone())
+                .map_err(|e| anyhow!("multisig signature {} failed: {}", sig.index, e))?;
+        }
+        if seen.len() < self.public_key.threshold as usize {
+            return Err(anyhow!(
+                "multisig threshold not met: {} of {} required signatures",
+                seen.len(),
+                self.public_key.threshold
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(n: u8) -> Vec<String> {
+        (0..n).map(|i| B64.encode([i + 1; 32])).collect()
+    }
+
+    #[test]
+    fn test_address_independent_of_key_order() {
+        let mut reversed = keys(3);
+        reversed.reverse();
+        let a = MultisigPubKey::new(2, keys(3)).unwrap();
+        let b = MultisigPubKey::new(2, reversed).unwrap();
+        assert_eq!(a, b);
+        assert_eq!(a.address(), b.address());
+        assert!(crate::addr::validate_address(&a.address()));
+    }
+
+    #[test]
+    fn test_address_depends_on_threshold() {
+        let a = MultisigPubKey::new(1, keys(3)).unwrap();
+        let b = MultisigPubKey::new(2, keys(3)).unwrap();
+        assert_ne!(a.address(), b.address());
+    }
+
+    #[test]
+    fn test_invalid_key_sets_rejected() {
+        assert!(MultisigPubKey::new(0, keys(2)).is_err());
+        assert!(MultisigPubKey::new(3, keys(2)).is_err());
+        assert!(MultisigPubKey::new(1, vec![]).is_err());
+        let mut dup = keys(2);
+        dup.push(dup[0].clone());
+        assert!(MultisigPubKey::new(1, dup).is_err());
+        assert!(MultisigPubKey::new(1, keys(MAX_MULTISIG_KEYS as u8 + 1)).is_err());
+    }
+
+    #[test]
+    fn test_verify_rejects_below_threshold_and_duplicates() {
+        use crate::crypto::{ActivePQC, PQC};
+
+        let auth = MultisigAuth {
+            public_key: MultisigPubKey::new(2, keys(3)).unwrap(),
+            signatures: vec![],
+        };
+        assert!(auth.verify(b"msg", PQCAlgorithm::default()).is_err());
+
+        let out_of_range = MultisigAuth {
+            signatures: vec![MultisigSignature::new(7, b"sig")],
+            ..auth.clone()
+        };
+        let err = out_of_range.verify(b"msg", PQCAlgorithm::default()).unwrap_err();
+        assert!(err.to_string().contains("out of range"));
+
+        // Two valid signatures from one member would meet the threshold of 2 only if
+        // counted twice
+        let members: Vec<(Vec<u8>, Vec<u8>)> = (0..3).map(|_| ActivePQC::keypair()).collect();
+        let key = MultisigPubKey::new(2, members.iter().map(|(_, pk)| B64.encode(pk)).collect())
+            .unwrap();
+        let (sk, pk) = &members[0];
+        let index = key.public_keys.iter().position(|k| *k == B64.encode(pk)).unwrap() as u32;
+        let sig = MultisigSignature::new(index, &ActivePQC::sign(sk, b"msg"));
+        let duplicated = MultisigAuth {
+            public_key: key,
+            signatures: vec![sig.clone(), sig],
+        };
+        let err = duplicated.verify(b"msg", PQCAlgorithm::default()).unwrap_err();
+        assert!(err.to_string().contains("duplicate multisig signature"));
+    }
+}
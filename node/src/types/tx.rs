@@ -1,4 +1,8 @@
 use crate::crypto::{canonical_json, sha3_256, verify, ActivePQC, PQCAlgorithm, PQC};
+use crate::runtime::denoms::DenomRegistry;
+use crate::runtime::governance::{ProposalTrack, ProposalType, VoteOption, WeightedVoteOption};
+use crate::runtime::token_factory::{parse_factory_denom, validate_subdenom};
+use crate::types::multisig::{MultisigAuth, MultisigPubKey, MultisigSignature};
 use anyhow::{anyhow, Result};
 use base64::{engine::general_purpose::STANDARD as B64, Engine};
 use serde::{Deserialize, Serialize};
@@ -43,6 +47,108 @@ pub enum Msg {
         from: String,
         owner: String,
     },
+    Delegate {
+        from: String,
+        validator: String,
+        #[serde(with = "as_str_u128")]
+        amount: u128,
+    },
+    Undelegate {
+        from: String,
+        validator: String,
+        #[serde(with = "as_str_u128")]
+        amount: u128,
+    },
+    ClaimRewards {
+        from: String,
+    },
+    SubmitProposal {
+        from: String,
+        title: String,
+        description: String,
+        proposal: ProposalType,
+        // Omitted for standard proposals so existing signed messages keep their bytes
+        #[serde(default, skip_serializing_if = "ProposalTrack::is_standard")]
+        track: ProposalTrack,
+    },
+    Deposit {
+        from: String,
+        proposal_id: u64,
+        #[serde(with = "as_str_u128")]
+        amount: u128,
+    },
+    Vote {
+        from: String,
+        proposal_id: u64,
+        option: VoteOption,
+    },
+    WeightedVote {
+        from: String,
+        proposal_id: u64,
+        options: Vec<WeightedVoteOption>,
+    },
+    ContractDeploy {
+        from: String,
+        code: String, // hex-encoded WASM
+    },
+    ContractCall {
+        from: String,
+        contract: String,
+        method: String,
+        args: String, // hex-encoded
+    },
+    GrantFeeAllowance {
+        from: String, // granter
+        grantee: String,
+        #[serde(with = "as_str_u128")]
+        spend_limit: u128,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        expiration_height: Option<u64>,
+    },
+    RevokeFeeAllowance {
+        from: String, // granter
+        grantee: String,
+    },
+    ScheduleTransfer {
+        from: String,
+        to: String,
+        denom: String,
+        #[serde(with = "as_str_u128")]
+        amount: u128,
+        release_height: u64,
+        #[serde(default)]
+        cancellable: bool,
+    },
+    CancelSchedule {
+        from: String,
+        id: u64,
+    },
+    CreateDenom {
+        from: String,
+        subdenom: String,
+        symbol: String,
+        decimals: u8,
+    },
+    Mint {
+        from: String,
+        denom: String,
+        #[serde(with = "as_str_u128")]
+        amount: u128,
+        /// Recipient of the minted tokens; defaults to the admin
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        to: Option<String>,
+    },
+    Burn {
+        from: String,
+        denom: String,
+        #[serde(with = "as_str_u128")]
+        amount: u128,
+    },
+    ChangeAdmin {
+        from: String,
+        denom: String,
+        new_admin: String,
+    },
 }
 
 impl Msg {
@@ -64,13 +170,11 @@ impl Msg {
                 if to.is_empty() {
                     return Err(anyhow!("to address cannot be empty"));
                 }
-                // Accept both micro-denominations (udgt, udrt) and whole tokens (DGT, DRT)
-                let up = denom.to_ascii_uppercase();
-                eprintln!("[DEBUG msg validate] denom uppercase: '{}', checking if DGT, DRT, UDGT, or UDRT", up);
-                if up != "DGT" && up != "DRT" && up != "UDGT" && up != "UDRT" {
-                    eprintln!("[DEBUG msg validate] ❌ DENOM VALIDATION FAILED: got '{}', expected DGT, DRT, udgt, or udrt", denom);
-                    return Err(anyhow!("unsupported denom: {}; valid: DGT, DRT, udgt, udrt", denom));
-                }
+                // Accept base units only (udgt, udrt, factory/...); token factory denoms are
+                // checked against state by the mempool
+                DenomRegistry::native()
+                    .check_signed(denom)
+                    .map_err(|e| anyhow!(e))?;
                 eprintln!("[DEBUG msg validate] ✅ Message validation passed");
             }
             Msg::Data { from, data } => {
@@ -109,6 +213,187 @@ impl Msg {
                     return Err(anyhow!("owner address cannot be empty"));
                 }
             }
+            Msg::Delegate {
+                from,
+                validator,
+                amount,
+            }
+            | Msg::Undelegate {
+                from,
+                validator,
+                amount,
+            } => {
+                if from.is_empty() {
+                    return Err(anyhow!("from address cannot be empty"));
+                }
+                if validator.is_empty() {
+                    return Err(anyhow!("validator address cannot be empty"));
+                }
+                if *amount == 0 {
+                    return Err(anyhow!("amount cannot be zero"));
+                }
+            }
+            Msg::ClaimRewards { from } => {
+                if from.is_empty() {
+                    return Err(anyhow!("from address cannot be empty"));
+                }
+            }
+            Msg::SubmitProposal { from, title, .. } => {
+                if from.is_empty() {
+                    return Err(anyhow!("from address cannot be empty"));
+                }
+                if title.trim().is_empty() {
+                    return Err(anyhow!("proposal title cannot be empty"));
+                }
+            }
+            Msg::Deposit { from, amount, .. } => {
+                if from.is_empty() {
+                    return Err(anyhow!("from address cannot be empty"));
+                }
+                if *amount == 0 {
+                    return Err(anyhow!("amount cannot be zero"));
+                }
+            }
+            Msg::Vote { from, .. } => {
+                if from.is_empty() {
+                    return Err(anyhow!("from address cannot be empty"));
+                }
+            }
+            Msg::WeightedVote { from, options, .. } => {
+                if from.is_empty() {
+                    return Err(anyhow!("from address cannot be empty"));
+                }
+                if options.is_empty() {
+                    return Err(anyhow!("weighted vote needs at least one option"));
+                }
+            }
+            Msg::ContractDeploy { from, code } => {
+                if from.is_empty() {
+                    return Err(anyhow!("from address cannot be empty"));
+                }
+                if code.is_empty() {
+                    return Err(anyhow!("contract code cannot be empty"));
+                }
+                hex::decode(code.trim_start_matches("0x"))
+                    .map_err(|e| anyhow!("contract code must be hex: {}", e))?;
+            }
+            Msg::ContractCall {
+                from,
+                contract,
+                method,
+                args,
+            } => {
+                if from.is_empty() {
+                    return Err(anyhow!("from address cannot be empty"));
+                }
+                if contract.is_empty() {
+                    return Err(anyhow!("contract address cannot be empty"));
+                }
+                if method.is_empty() {
+                    return Err(anyhow!("method cannot be empty"));
+                }
+                hex::decode(args.trim_start_matches("0x"))
+                    .map_err(|e| anyhow!("args must be hex: {}", e))?;
+            }
+            Msg::GrantFeeAllowance {
+                from,
+                grantee,
+                spend_limit,
+                ..
+            } => {
+                if from.is_empty() {
+                    return Err(anyhow!("from address cannot be empty"));
+                }
+                if grantee.is_empty() {
+                    return Err(anyhow!("grantee address cannot be empty"));
+                }
+                if from == grantee {
+                    return Err(anyhow!("cannot grant a fee allowance to yourself"));
+                }
+                if *spend_limit == 0 {
+                    return Err(anyhow!("spend_limit cannot be zero"));
+                }
+            }
+            Msg::RevokeFeeAllowance { from, grantee } => {
+                if from.is_empty() {
+                    return Err(anyhow!("from address cannot be empty"));
+                }
+                if grantee.is_empty() {
+                    return Err(anyhow!("grantee address cannot be empty"));
+                }
+            }
+            Msg::ScheduleTransfer {
+                from,
+                to,
+                denom,
+                amount,
+                release_height,
+                ..
+            } => {
+                if from.is_empty() {
+                    return Err(anyhow!("from address cannot be empty"));
+                }
+                if to.is_empty() {
+                    return Err(anyhow!("to address cannot be empty"));
+                }
+                if from == to {
+                    return Err(anyhow!("cannot schedule a transfer to yourself"));
+                }
+                if *amount == 0 {
+                    return Err(anyhow!("amount cannot be zero"));
+                }
+                DenomRegistry::native()
+                    .check_signed(denom)
+                    .map_err(|e| anyhow!(e))?;
+                if *release_height == 0 {
+                    return Err(anyhow!("release_height cannot be zero"));
+                }
+            }
+            Msg::CancelSchedule { from, .. } => {
+                if from.is_empty() {
+                    return Err(anyhow!("from address cannot be empty"));
+                }
+            }
+            Msg::CreateDenom {
+                from,
+                subdenom,
+                symbol,
+                ..
+            } => {
+                if from.is_empty() {
+                    return Err(anyhow!("from address cannot be empty"));
+                }
+                validate_subdenom(subdenom).map_err(|e| anyhow!(e))?;
+                if symbol.trim().is_empty() {
+                    return Err(anyhow!("symbol cannot be empty"));
+                }
+            }
+            Msg::Mint {
+                from, denom, amount, ..
+            }
+            | Msg::Burn {
+                from,
+                denom,
+                amount,
+            } => {
+                if from.is_empty() {
+                    return Err(anyhow!("from address cannot be empty"));
+                }
+                if parse_factory_denom(denom).is_none() {
+                    return Err(anyhow!("not a token factory denom: {}", denom));
+                }
+                if *amount == 0 {
+                    return Err(anyhow!("amount cannot be zero"));
+                }
+            }
+            Msg::ChangeAdmin { from, denom, .. } => {
+                if from.is_empty() {
+                    return Err(anyhow!("from address cannot be empty"));
+                }
+                if parse_factory_denom(denom).is_none() {
+                    return Err(anyhow!("not a token factory denom: {}", denom));
+                }
+            }
         }
         Ok(())
     }
@@ -121,6 +406,23 @@ impl Msg {
             Msg::DmsRegister { from, .. } => from,
             Msg::DmsPing { from, .. } => from,
             Msg::DmsClaim { from, .. } => from,
+            Msg::Delegate { from, .. } => from,
+            Msg::Undelegate { from, .. } => from,
+            Msg::ClaimRewards { from } => from,
+            Msg::SubmitProposal { from, .. } => from,
+            Msg::Deposit { from, .. } => from,
+            Msg::Vote { from, .. } => from,
+            Msg::WeightedVote { from, .. } => from,
+            Msg::ContractDeploy { from, .. } => from,
+            Msg::ContractCall { from, .. } => from,
+            Msg::GrantFeeAllowance { from, .. } => from,
+            Msg::RevokeFeeAllowance { from, .. } => from,
+            Msg::ScheduleTransfer { from, .. } => from,
+            Msg::CancelSchedule { from, .. } => from,
+            Msg::CreateDenom { from, .. } => from,
+            Msg::Mint { from, .. } => from,
+            Msg::Burn { from, .. } => from,
+            Msg::ChangeAdmin { from, .. } => from,
         }
     }
 }
@@ -133,6 +435,14 @@ pub struct Tx {
     #[serde(with = "as_str_u128")]
     pub fee: u128,
     pub memo: String,
+    /// Signed gas limit (e.g. filled from `/tx/simulate`); omitted from the
+    /// canonical encoding when unset so existing signatures stay valid
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_limit: Option<u64>,
+    /// Sponsor charged the upfront fee instead of the sender; must co-sign
+    /// or have granted the sender a fee allowance
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_payer: Option<String>,
 }
 
 impl Tx {
@@ -162,9 +472,21 @@ impl Tx {
             msgs,
             fee,
             memo: memo.into(),
+            gas_limit: None,
+            fee_payer: None,
         })
     }
 
+    pub fn with_gas_limit(mut self, gas_limit: u64) -> Self {
+        self.gas_limit = Some(gas_limit);
+        self
+    }
+
+    pub fn with_fee_payer(mut self, fee_payer: impl Into<String>) -> Self {
+        self.fee_payer = Some(fee_payer.into());
+        self
+    }
+
     pub fn validate(&self, expected_chain_id: &str) -> Result<()> {
         eprintln!("[DEBUG validate] Comparing chain IDs:");
         eprintln!("[DEBUG validate]   Expected: '{}' (len: {})", expected_chain_id, expected_chain_id.len());
@@ -187,6 +509,14 @@ impl Tx {
         for msg in &self.msgs {
             msg.validate()?;
         }
+        if let Some(fee_payer) = &self.fee_payer {
+            if fee_payer.is_empty() {
+                return Err(anyhow!("fee_payer cannot be empty"));
+            }
+            if self.msgs.iter().any(|m| m.sender() == fee_payer) {
+                return Err(anyhow!("fee_payer must differ from the sender"));
+            }
+        }
         Ok(())
     }
 
@@ -204,10 +534,25 @@ impl Tx {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct SignedTx {
     pub tx: Tx,
-    pub public_key: String, // base64
-    pub signature: String,  // base64
+    #[serde(default)]
+    pub public_key: String, // base64 (empty for multisig)
+    #[serde(default)]
+    pub signature: String,  // base64 (empty for multisig)
     pub algorithm: String,  // ActivePQC::ALG
     pub version: u32,       // 1
+    /// Multisig variant: k-of-n signatures replace `public_key`/`signature`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub multisig: Option<MultisigAuth>,
+    /// Fee payer's co-signature; when absent a sponsored tx relies on a fee allowance
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_payer_signature: Option<FeePayerSignature>,
+}
+
+/// Fee payer's signature over the same canonical tx hash the sender signs
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct FeePayerSignature {
+    pub public_key: String, // base64
+    pub signature: String,  // base64
 }
 
 impl SignedTx {
@@ -221,9 +566,54 @@ impl SignedTx {
             signature: B64.encode(sig),
             algorithm: ActivePQC::ALG.to_string(),
             version: 1,
+            multisig: None,
+            fee_payer_signature: None,
         })
     }
 
+    /// Produce one member's signature over `tx` for a multisig account
+    pub fn sign_multisig_member(tx: &Tx, index: u32, sk: &[u8]) -> Result<MultisigSignature> {
+        let hash = tx.canonical_hash()?;
+        Ok(MultisigSignature::new(index, &ActivePQC::sign(sk, &hash)))
+    }
+
+    /// Assemble a multisig-signed transaction from collected member signatures
+    pub fn from_multisig(
+        tx: Tx,
+        public_key: MultisigPubKey,
+        signatures: Vec<MultisigSignature>,
+    ) -> Self {
+        Self {
+            tx,
+            public_key: String::new(),
+            signature: String::new(),
+            algorithm: ActivePQC::ALG.to_string(),
+            version: 1,
+            multisig: Some(MultisigAuth {
+                public_key,
+                signatures,
+            }),
+            fee_payer_signature: None,
+        }
+    }
+
+    /// Attach the fee payer's co-signature (the payer signs the same `Tx`, `fee_payer` included)
+    pub fn cosign_fee_payer(mut self, sk: &[u8], pk: &[u8]) -> Result<Self> {
+        let hash = self.tx.canonical_hash()?;
+        self.fee_payer_signature = Some(FeePayerSignature {
+            public_key: B64.encode(pk),
+            signature: B64.encode(ActivePQC::sign(sk, &hash)),
+        });
+        Ok(self)
+    }
+
+    /// Signatures beyond the first, charged at `per_additional_signature`
+    pub fn additional_signatures(&self) -> usize {
+        self.multisig
+            .as_ref()
+            .map_or(0, MultisigAuth::additional_signatures)
+    }
+
     pub fn verify(&self) -> Result<()> {
         // Parse the algorithm from the string
         let algorithm = PQCAlgorithm::from_str(&self.algorithm)
@@ -237,9 +627,24 @@ impl SignedTx {
         }
         let bytes = canonical_json(&self.tx)?;
         let hash = sha3_256(&bytes);
+
+        self.verify_fee_payer(&hash, algorithm.clone())?;
+
+        if let Some(multisig) = &self.multisig {
+            // Every message must act on behalf of the multisig account itself
+            let address = multisig.public_key.address();
+            if let Some(msg) = self.tx.msgs.iter().find(|m| m.sender() != address) {
+                return Err(anyhow!(
+                    "multisig sender mismatch: expected {}, got {}",
+                    address,
+                    msg.sender()
+                ));
+            }
+            return multisig.verify(&hash, algorithm);
+        }
         
         eprintln!("[DEBUG verify] Canonical JSON: {}", String::from_utf8_lossy(&bytes));
-        eprintln!("[DEBUG verify] Hash: {}", hex::encode(&hash));
+        eprintln!("[DEBUG verify] Hash: {}", hex::encode(hash));
         eprintln!("[DEBUG verify] Signature (base64 len): {}", self.signature.len());
         eprintln!("[DEBUG verify] Public key (base64 len): {}", self.public_key.len());
         
@@ -250,6 +655,16 @@ impl SignedTx {
             .decode(&self.public_key)
             .map_err(|e| anyhow!("invalid public key encoding: {}", e))?;
 
+        // Every message must act on behalf of the signing key
+        let address = crate::addr::get_address(&pk);
+        if let Some(msg) = self.tx.msgs.iter().find(|m| m.sender() != address) {
+            return Err(anyhow!(
+                "sender mismatch: signed by {}, message from {}",
+                address,
+                msg.sender()
+            ));
+        }
+
         eprintln!("[DEBUG verify] Signature (decoded len): {}", sig.len());
         eprintln!("[DEBUG verify] Public key (decoded len): {}", pk.len());
         eprintln!("[DEBUG verify] Algorithm: {:?}", algorithm);
@@ -267,6 +682,29 @@ impl SignedTx {
         }
     }
 
+    /// A fee payer co-signature must come from the key behind `tx.fee_payer`
+    fn verify_fee_payer(&self, hash: &[u8], algorithm: PQCAlgorithm) -> Result<()> {
+        let Some(payer_sig) = &self.fee_payer_signature else {
+            return Ok(());
+        };
+        let fee_payer = self
+            .tx
+            .fee_payer
+            .as_deref()
+            .ok_or_else(|| anyhow!("fee payer signature without fee_payer"))?;
+        let pk = B64
+            .decode(&payer_sig.public_key)
+            .map_err(|e| anyhow!("invalid fee payer public key encoding: {}", e))?;
+        if crate::addr::get_address(&pk) != fee_payer {
+            return Err(anyhow!("fee payer public key does not match {}", fee_payer));
+        }
+        let sig = B64
+            .decode(&payer_sig.signature)
+            .map_err(|e| anyhow!("invalid fee payer signature encoding: {}", e))?;
+        verify(&pk, hash, &sig, algorithm)
+            .map_err(|e| anyhow!("fee payer signature verification failed: {}", e))
+    }
+
     pub fn tx_hash(&self) -> Result<String> {
         self.tx.tx_hash()
     }
@@ -443,7 +881,7 @@ mod tests {
         let valid_msg = Msg::Send {
             from: "alice".into(),
             to: "bob".into(),
-            denom: "DGT".into(),
+            denom: "udgt".into(),
             amount: 100,
         };
         assert!(valid_msg.validate().is_ok());
@@ -451,10 +889,28 @@ mod tests {
         let zero_amount = Msg::Send {
             from: "alice".into(),
             to: "bob".into(),
-            denom: "DGT".into(),
+            denom: "udgt".into(),
             amount: 0,
         };
         assert!(zero_amount.validate().is_err());
+
+        // Display units are not scaled in signed payloads but rejected
+        let display = Msg::Send {
+            from: "alice".into(),
+            to: "bob".into(),
+            denom: "DGT".into(),
+            amount: 100,
+        };
+        assert!(display.validate().unwrap_err().to_string().contains("use udgt"));
+
+        // Other spellings of a base unit are ambiguous
+        let lowercase_display = Msg::Send {
+            from: "alice".into(),
+            to: "bob".into(),
+            denom: "dgt".into(),
+            amount: 100,
+        };
+        assert!(lowercase_display.validate().is_err());
     }
 
     #[test]
@@ -462,7 +918,7 @@ mod tests {
         let msg = Msg::Send {
             from: "alice".into(),
             to: "bob".into(),
-            denom: "DGT".into(),
+            denom: "udgt".into(),
             amount: 1000000000000000000u128,
         };
         let json = serde_json::to_string(&msg).unwrap();
@@ -471,4 +927,90 @@ mod tests {
         let parsed: Msg = serde_json::from_str(&json).unwrap();
         assert_eq!(parsed, msg);
     }
+
+    #[test]
+    fn test_multisig_threshold_verification() {
+        let members: Vec<(Vec<u8>, Vec<u8>)> = (0..3).map(|_| ActivePQC::keypair()).collect();
+        let key = MultisigPubKey::new(2, members.iter().map(|(_, pk)| B64.encode(pk)).collect())
+            .unwrap();
+        let index_of = |pk: &[u8]| {
+            key.public_keys
+                .iter()
+                .position(|k| *k == B64.encode(pk))
+                .unwrap() as u32
+        };
+        let tx = Tx::new(
+            "dyt-local-1",
+            0,
+            vec![Msg::Send {
+                from: key.address(),
+                to: "bob".into(),
+                denom: "udgt".into(),
+                amount: 10,
+            }],
+            1,
+            "",
+        )
+        .unwrap();
+        let sig = |i: usize| {
+            let (sk, pk) = &members[i];
+            SignedTx::sign_multisig_member(&tx, index_of(pk), sk).unwrap()
+        };
+
+        let one = SignedTx::from_multisig(tx.clone(), key.clone(), vec![sig(0)]);
+        assert!(one.verify().is_err());
+
+        let two = SignedTx::from_multisig(tx.clone(), key.clone(), vec![sig(0), sig(2)]);
+        assert!(two.verify().is_ok());
+        assert_eq!(two.additional_signatures(), 1);
+
+        // Signature envelope round-trips through JSON
+        let parsed: SignedTx = serde_json::from_str(&serde_json::to_string(&two).unwrap()).unwrap();
+        assert_eq!(parsed, two);
+
+        // Messages must be sent from the multisig address
+        let mut wrong_sender = tx.clone();
+        wrong_sender.msgs = vec![Msg::Data {
+            from: "alice".into(),
+            data: "x".into(),
+        }];
+        let signatures = vec![
+            SignedTx::sign_multisig_member(&wrong_sender, index_of(&members[0].1), &members[0].0)
+                .unwrap(),
+            SignedTx::sign_multisig_member(&wrong_sender, index_of(&members[1].1), &members[1].0)
+                .unwrap(),
+        ];
+        let err = SignedTx::from_multisig(wrong_sender, key, signatures)
+            .verify()
+            .unwrap_err();
+        assert!(err.to_string().contains("sender mismatch"));
+    }
+
+    #[test]
+    fn test_single_key_signer_must_be_sender() {
+        let (sk, pk) = ActivePQC::keypair();
+        let signer = crate::addr::get_address(&pk);
+        let delegate = |from: String| {
+            Tx::new(
+                "dyt-local-1",
+                0,
+                vec![Msg::Delegate {
+                    from,
+                    validator: "val1".into(),
+                    amount: 10,
+                }],
+                1,
+                "",
+            )
+            .unwrap()
+        };
+
+        let own = SignedTx::sign(delegate(signer), &sk, &pk).unwrap();
+        assert!(own.verify().is_ok());
+
+        // A valid signature over someone else's message is rejected
+        let other = SignedTx::sign(delegate("dyt1victim".into()), &sk, &pk).unwrap();
+        let err = other.verify().unwrap_err();
+        assert!(err.to_string().contains("sender mismatch"));
+    }
 }
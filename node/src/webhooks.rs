@@ -0,0 +1,133 @@
+use reqwest::Client;
+use std::time::Duration;
+
+/// Posts JSON payloads to the configured webhook URLs. Each delivery runs in its own task
+/// and is retried with exponential backoff; failures are logged and never hold up block
+/// production.
+#[derive(Clone)]
+pub struct WebhookDispatcher {
+    urls: Vec<String>,
+    max_attempts: u32,
+    initial_backoff: Duration,
+    client: Client,
+}
+
+impl WebhookDispatcher {
+    pub fn new(urls: Vec<String>, max_attempts: u32, initial_backoff: Duration) -> Self {
+        Self {
+            urls,
+            max_attempts: max_attempts.max(1),
+            initial_backoff,
+            client: Client::new(),
+        }
+    }
+
+    /// Configured by `DYT_GOV_WEBHOOKS` (comma separated URLs, none by default),
+    /// `DYT_GOV_WEBHOOK_ATTEMPTS` (default 5) and `DYT_GOV_WEBHOOK_BACKOFF_MS` (first retry
+    /// delay, doubled for each further retry; default 500)
+    pub fn from_env() -> Self {
+        let urls = std::env::var("DYT_GOV_WEBHOOKS")
+            .map(|v| {
+                v.split(',')
+                    .map(str::trim)
+                    .filter(|u| !u.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+        let max_attempts = std::env::var("DYT_GOV_WEBHOOK_ATTEMPTS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(5);
+        let backoff_ms = std::env::var("DYT_GOV_WEBHOOK_BACKOFF_MS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(500);
+        Self::new(urls, max_attempts, Duration::from_millis(backoff_ms))
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.urls.is_empty()
+    }
+
+    /// Deliver `payload` to every URL in the background
+    pub fn dispatch(&self, payload: serde_json::Value) {
+        for url in &self.urls {
+            let dispatcher = self.clone();
+            let url = url.clone();
+            let payload = payload.clone();
+            tokio::spawn(async move {
+                if let Err(e) = dispatcher.deliver(&url, &payload).await {
+                    eprintln!("webhook delivery to {url} failed: {e}");
+                }
+            });
+        }
+    }
+
+    /// Post `payload` to `url` until it is accepted (2xx status) or every attempt is used.
+    /// Returns the number of attempts made.
+    pub async fn deliver(&self, url: &str, payload: &serde_json::Value) -> Result<u32, String> {
+        let mut backoff = self.initial_backoff;
+        let mut last_error = String::new();
+        for attempt in 1..=self.max_attempts {
+            let response = self
+                .client
+                .post(url)
+                .json(payload)
+                .timeout(Duration::from_secs(10))
+                .send()
+                .await;
+            match response {
+                Ok(r) if r.status().is_success() => return Ok(attempt),
+                Ok(r) => last_error = format!("status {}", r.status()),
+                Err(e) => last_error = e.to_string(),
+            }
+            if attempt < self.max_attempts {
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+            }
+        }
+        Err(format!("{last_error} after {} attempts", self.max_attempts))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{http::StatusCode, routing::post, Router};
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_delivery_retries_until_accepted() {
+        let calls = Arc::new(AtomicU32::new(0));
+        let counter = calls.clone();
+        // Fails the first two deliveries
+        let app = Router::new().route(
+            "/hook",
+            post(move || {
+                let counter = counter.clone();
+                async move {
+                    if counter.fetch_add(1, Ordering::SeqCst) < 2 {
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    } else {
+                        StatusCode::OK
+                    }
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let payload = serde_json::json!({"height": 1, "events": []});
+        let dispatcher = WebhookDispatcher::new(vec![url.clone()], 3, Duration::from_millis(1));
+        assert_eq!(dispatcher.deliver(&url, &payload).await.unwrap(), 3);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        let dispatcher = WebhookDispatcher::new(vec![url.clone()], 2, Duration::from_millis(1));
+        calls.store(0, Ordering::SeqCst);
+        assert!(dispatcher.deliver(&url, &payload).await.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
@@ -6,21 +6,45 @@ use axum::{
 use futures::{SinkExt, StreamExt};
 use std::sync::{Arc, Mutex};
 
+/// A connected client and the message types it subscribed to (every type when empty)
+struct Subscriber {
+    tx: tokio::sync::mpsc::UnboundedSender<Message>,
+    topics: Arc<Mutex<Vec<String>>>,
+}
+
 #[derive(Clone, Default)]
 pub struct WsHub {
-    inner: Arc<Mutex<Vec<tokio::sync::mpsc::UnboundedSender<Message>>>>,
+    inner: Arc<Mutex<Vec<Subscriber>>>,
 }
 impl WsHub {
     pub fn new() -> Self {
         Self::default()
     }
+    /// Send `v` to every client subscribed to its `type`
     pub fn broadcast_json(&self, v: &serde_json::Value) {
+        let msg_type = v.get("type").and_then(|t| t.as_str()).unwrap_or("");
         let txt = Message::Text(v.to_string());
         let mut guard = self.inner.lock().unwrap();
-        guard.retain(|tx| tx.send(txt.clone()).is_ok());
+        guard.retain(|sub| {
+            if topic_matches(&sub.topics.lock().unwrap(), msg_type) {
+                sub.tx.send(txt.clone()).is_ok()
+            } else {
+                !sub.tx.is_closed()
+            }
+        });
     }
 }
 
+/// Whether a message of type `msg_type` goes to a client subscribed to `topics`. A topic
+/// ending in `*` matches by prefix, so `gov_*` selects every governance event.
+pub fn topic_matches(topics: &[String], msg_type: &str) -> bool {
+    topics.is_empty()
+        || topics.iter().any(|t| match t.strip_suffix('*') {
+            Some(prefix) => msg_type.starts_with(prefix),
+            None => t == msg_type,
+        })
+}
+
 pub async fn ws_handler(
     Extension(hub): Extension<WsHub>,
     ws: WebSocketUpgrade,
@@ -31,8 +55,12 @@ pub async fn ws_handler(
 async fn handle_socket(hub: WsHub, socket: WebSocket) {
     let (mut tx_ws, mut rx_ws) = socket.split();
     let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+    let topics = Arc::new(Mutex::new(Vec::new()));
     {
-        hub.inner.lock().unwrap().push(tx);
+        hub.inner.lock().unwrap().push(Subscriber {
+            tx,
+            topics: topics.clone(),
+        });
     }
     let mut rx = tokio_stream::wrappers::UnboundedReceiverStream::new(rx);
     // Forward outbound messages manually
@@ -44,5 +72,18 @@ async fn handle_socket(hub: WsHub, socket: WebSocket) {
             }
         }
     });
-    while let Some(_msg) = rx_ws.next().await { /* ignore client messages */ }
+    // Clients choose what they receive with {"subscribe": ["new_block", "gov_*"]}; an
+    // empty list subscribes to everything again
+    while let Some(Ok(msg)) = rx_ws.next().await {
+        let Message::Text(text) = msg else { continue };
+        let Ok(request) = serde_json::from_str::<serde_json::Value>(&text) else {
+            continue;
+        };
+        if let Some(list) = request.get("subscribe").and_then(|v| v.as_array()) {
+            *topics.lock().unwrap() = list
+                .iter()
+                .filter_map(|t| t.as_str().map(str::to_string))
+                .collect();
+        }
+    }
 }
@@ -0,0 +1,109 @@
+//! Governance lifecycle example: submit a proposal, fund its deposit, vote and wait
+//! for the result
+//!
+//! Start a local node with governance enabled and short periods, e.g.
+//!
+//!   DYT_ENABLE_GOVERNANCE=1 DYT_GOV_MIN_DEPOSIT=500000 DYT_GOV_VOTING_PERIOD=10 dytallix-fast-node
+//!
+//! Run: cargo run --example governance
+//!
+//! `DYT_RPC_URL` (default http://localhost:3030), `DYT_CHAIN_ID` (default dyt-local-1) and
+//! `DYT_GOV_MIN_DEPOSIT` (udgt, default 500000) must match the node.
+
+use dytallix_sdk::{Client, ProposalStatus, ProposalTrack, ProposalType, TransactionReceipt, VoteOption, Wallet};
+use std::time::Duration;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    println!("Dytallix SDK - Governance Example");
+    println!("=================================\n");
+
+    let rpc_url = std::env::var("DYT_RPC_URL").unwrap_or_else(|_| "http://localhost:3030".to_string());
+    let chain_id = std::env::var("DYT_CHAIN_ID").unwrap_or_else(|_| "dyt-local-1".to_string());
+    let min_deposit: u128 = std::env::var("DYT_GOV_MIN_DEPOSIT")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(500_000);
+
+    let client = Client::new(&rpc_url, &chain_id);
+    let status = client.get_status().await?;
+    println!("✓ Connected to {} at height {}", rpc_url, status.block_height);
+
+    // 1. Fund a fresh wallet
+    let wallet = Wallet::generate()?;
+    println!("\nWallet: {}", wallet.address());
+    let faucet = client.request_faucet(wallet.address(), &["DGT", "DRT"]).await?;
+    if !faucet.success {
+        anyhow::bail!("faucet failed: {:?}", faucet.error);
+    }
+    println!("✓ Funded: {:?}", faucet.dispensed.iter().map(|d| format!("{} {}", d.amount, d.symbol)).collect::<Vec<_>>());
+
+    // 2. Submit a signalling proposal
+    println!("\nSubmitting proposal...");
+    let hash = client
+        .submit_proposal(
+            &wallet,
+            "SDK governance example",
+            "Signalling proposal submitted by the Rust SDK example",
+            ProposalType::Text,
+            ProposalTrack::Standard,
+        )
+        .await?;
+    let receipt = confirm(&client, &hash).await?;
+    let proposal_id: u64 = receipt
+        .event_attribute("submit_proposal", "proposal_id")
+        .and_then(|id| id.parse().ok())
+        .ok_or_else(|| anyhow::anyhow!("receipt has no proposal id"))?;
+    println!("✓ Proposal {} submitted ({})", proposal_id, hash);
+
+    // 3. Fund the deposit, which opens the voting period
+    println!("\nDepositing {} udgt...", min_deposit);
+    let hash = client.deposit(&wallet, proposal_id, min_deposit).await?;
+    confirm(&client, &hash).await?;
+    let proposal = client.get_proposal(proposal_id).await?;
+    println!("✓ Status: {:?} (deposit {} udgt)", proposal.status, proposal.total_deposit);
+    if proposal.status != ProposalStatus::VotingPeriod {
+        anyhow::bail!("deposit did not open voting; is DYT_GOV_MIN_DEPOSIT the node's minimum?");
+    }
+
+    // 4. Vote
+    let power = client.get_voting_power(wallet.address()).await?;
+    println!("\nVoting yes with {} voting power...", power);
+    let hash = client.vote(&wallet, proposal_id, VoteOption::Yes).await?;
+    confirm(&client, &hash).await?;
+    let tally = client.get_tally(proposal_id).await?;
+    println!(
+        "✓ Tally: yes {} / no {} / veto {} / abstain {}",
+        tally.yes, tally.no, tally.no_with_veto, tally.abstain
+    );
+
+    // 5. Wait for the voting period to end
+    println!("\nWaiting for voting to end at height {}...", proposal.voting_end_height);
+    let proposal = loop {
+        let proposal = client.get_proposal(proposal_id).await?;
+        if proposal.status != ProposalStatus::VotingPeriod {
+            break proposal;
+        }
+        tokio::time::sleep(Duration::from_secs(2)).await;
+    };
+    println!("✓ Final status: {:?}", proposal.status);
+    if let Some(tally) = proposal.tally {
+        println!("  Final tally: yes {} of {} participating", tally.yes, tally.total_voting_power);
+    }
+
+    let listed = client.list_proposals().await?;
+    if let Some(summary) = listed.iter().find(|p| p.id == proposal_id) {
+        println!("  Listed as {} [{:?}]", summary.proposal_type, summary.status);
+    }
+
+    Ok(())
+}
+
+/// Wait for a transaction and fail unless it succeeded
+async fn confirm(client: &Client, hash: &str) -> anyhow::Result<TransactionReceipt> {
+    let receipt = client.wait_for_transaction(hash, 60).await?;
+    if !receipt.status.eq_ignore_ascii_case("success") {
+        anyhow::bail!("transaction {} {}", hash, receipt.status);
+    }
+    Ok(receipt)
+}
@@ -1,12 +1,16 @@
 //! RPC Client for Dytallix blockchain
 
 use crate::error::{Result, SdkError};
+use crate::wallet::Wallet;
 use crate::{TESTNET_RPC, TESTNET_CHAIN_ID};
 use reqwest::Client as HttpClient;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::time::Duration;
 
+/// Fee (udgt) attached to transactions the client builds and signs itself
+pub const DEFAULT_TX_FEE: u128 = 1_000;
+
 /// Dytallix RPC Client
 #[derive(Clone)]
 pub struct Client {
@@ -72,6 +76,11 @@ impl Client {
 
     /// Submit a signed transaction
     pub async fn submit_transaction(&self, signed_tx: &SignedTransaction) -> Result<String> {
+        self.submit_signed(serde_json::to_value(signed_tx)?).await
+    }
+
+    /// Post a `signed_tx` envelope to `/submit` and return the transaction hash
+    async fn submit_signed(&self, signed_tx: serde_json::Value) -> Result<String> {
         let resp = self
             .http
             .post(format!("{}/submit", self.base_url))
@@ -94,6 +103,67 @@ impl Client {
         Ok(data.tx_hash.or(data.hash).unwrap_or_default())
     }
 
+    /// Dry-run a transaction against current state via `/tx/simulate` (nothing is persisted).
+    /// The signature is only sent (and verified) when `signature.sig` is non-empty.
+    pub async fn simulate_transaction(&self, tx: &SignedTransaction) -> Result<SimulationResult> {
+        let mut body = serde_json::json!({
+            "tx": {
+                "chain_id": tx.chain_id,
+                "nonce": tx.nonce,
+                "msgs": tx.msgs,
+                "fee": tx.fee,
+                "memo": tx.memo,
+            }
+        });
+        if let Some(gas_limit) = tx.gas_limit {
+            body["tx"]["gas_limit"] = serde_json::json!(gas_limit);
+        }
+        if !tx.signature.sig.is_empty() {
+            body["signature"] = serde_json::json!(tx.signature.sig);
+            body["public_key"] = serde_json::json!(tx.signature.pub_key);
+            body["algorithm"] = serde_json::json!(tx.signature.algorithm);
+        }
+
+        let resp = self
+            .http
+            .post(format!("{}/tx/simulate", self.base_url))
+            .json(&body)
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            let err_text = resp.text().await?;
+            return Err(SdkError::Api(err_text));
+        }
+
+        let result = resp.json::<SimulationResult>().await?;
+        Ok(result)
+    }
+
+    /// Estimate a gas limit from a simulation, scaled by `multiplier` (e.g. 1.2 for 20% headroom)
+    pub async fn estimate_gas(&self, tx: &SignedTransaction, multiplier: f64) -> Result<u64> {
+        // Let the node pick the largest affordable limit for the dry run
+        let mut probe = tx.clone();
+        probe.gas_limit = None;
+
+        let sim = self.simulate_transaction(&probe).await?;
+        if !sim.success {
+            return Err(SdkError::Api(format!(
+                "Simulation failed: {}",
+                sim.error.unwrap_or_default()
+            )));
+        }
+        Ok((sim.gas_used as f64 * multiplier.max(1.0)).ceil() as u64)
+    }
+
+    /// Fill `tx.gas_limit` from a simulation. Call before signing: the gas limit is part of
+    /// the signed payload.
+    pub async fn fill_gas_limit(&self, tx: &mut SignedTransaction, multiplier: f64) -> Result<u64> {
+        let gas_limit = self.estimate_gas(tx, multiplier).await?;
+        tx.gas_limit = Some(gas_limit);
+        Ok(gas_limit)
+    }
+
     /// Wait for transaction confirmation
     pub async fn wait_for_transaction(&self, hash: &str, timeout_secs: u64) -> Result<TransactionReceipt> {
         let start = std::time::Instant::now();
@@ -301,6 +371,9 @@ impl Client {
 
     /// Deploy a WASM smart contract
     ///
+    /// Uses the node's unauthenticated dev endpoint, mounted only with
+    /// `DYT_ENABLE_LEGACY_TX_ENDPOINTS=1`.
+    ///
     /// # Arguments
     /// * `code` - Hex-encoded WASM bytecode
     /// * `deployer` - Address of the deployer
@@ -358,7 +431,7 @@ impl Client {
         })
     }
 
-    /// Call a method on a deployed smart contract
+    /// Call a method on a deployed smart contract (dev endpoint, see `deploy_contract`)
     ///
     /// # Arguments
     /// * `address` - Contract address
@@ -438,6 +511,167 @@ impl Client {
         Ok(data.value)
     }
 
+    // ===== Governance Methods =====
+
+    /// Submit a governance proposal signed by `wallet`. Returns the transaction hash; the
+    /// new proposal's id is the `proposal_id` attribute of the receipt's `submit_proposal`
+    /// event.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use dytallix_sdk::{Client, ProposalTrack, ProposalType, Wallet};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::new("http://localhost:3030", "dyt-local-1");
+    ///     let wallet = Wallet::load("wallet.json")?;
+    ///
+    ///     let hash = client
+    ///         .submit_proposal(
+    ///             &wallet,
+    ///             "Lengthen the voting period",
+    ///             "Gives voters 200 blocks",
+    ///             ProposalType::ParameterChange {
+    ///                 key: "governance.voting_period".to_string(),
+    ///                 value: "200".to_string(),
+    ///             },
+    ///             ProposalTrack::Standard,
+    ///         )
+    ///         .await?;
+    ///     let receipt = client.wait_for_transaction(&hash, 30).await?;
+    ///     println!("Proposal id: {:?}", receipt.event_attribute("submit_proposal", "proposal_id"));
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn submit_proposal(
+        &self,
+        wallet: &Wallet,
+        title: &str,
+        description: &str,
+        proposal: ProposalType,
+        track: ProposalTrack,
+    ) -> Result<String> {
+        let msg = GovernanceMsg::SubmitProposal {
+            from: wallet.address().to_string(),
+            title: title.to_string(),
+            description: description.to_string(),
+            proposal,
+            track,
+        };
+        self.sign_and_submit(wallet, msg).await
+    }
+
+    /// Deposit `amount` udgt on a proposal in its deposit period
+    pub async fn deposit(&self, wallet: &Wallet, proposal_id: u64, amount: u128) -> Result<String> {
+        let msg = GovernanceMsg::Deposit {
+            from: wallet.address().to_string(),
+            proposal_id,
+            amount: amount.to_string(),
+        };
+        self.sign_and_submit(wallet, msg).await
+    }
+
+    /// Vote on a proposal in its voting period
+    pub async fn vote(&self, wallet: &Wallet, proposal_id: u64, option: VoteOption) -> Result<String> {
+        let msg = GovernanceMsg::Vote {
+            from: wallet.address().to_string(),
+            proposal_id,
+            option,
+        };
+        self.sign_and_submit(wallet, msg).await
+    }
+
+    /// Get a proposal by id
+    pub async fn get_proposal(&self, proposal_id: u64) -> Result<Proposal> {
+        let resp = self
+            .http
+            .get(format!("{}/gov/proposal/{}", self.base_url, proposal_id))
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(SdkError::Api(format!("Proposal query failed: {}", resp.status())));
+        }
+
+        let proposal = resp.json::<Proposal>().await?;
+        Ok(proposal)
+    }
+
+    /// List all proposals with their current tallies
+    pub async fn list_proposals(&self) -> Result<Vec<ProposalSummary>> {
+        let resp = self
+            .http
+            .get(format!("{}/api/governance/proposals", self.base_url))
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(SdkError::Api(format!("Proposals query failed: {}", resp.status())));
+        }
+
+        #[derive(Deserialize)]
+        struct ProposalsResponse {
+            #[serde(default)]
+            proposals: Vec<ProposalSummary>,
+        }
+
+        let data = resp.json::<ProposalsResponse>().await?;
+        Ok(data.proposals)
+    }
+
+    /// Get the current tally of a proposal
+    pub async fn get_tally(&self, proposal_id: u64) -> Result<TallyResult> {
+        let resp = self
+            .http
+            .get(format!("{}/gov/tally/{}", self.base_url, proposal_id))
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(SdkError::Api(format!("Tally query failed: {}", resp.status())));
+        }
+
+        let tally = resp.json::<TallyResult>().await?;
+        Ok(tally)
+    }
+
+    /// Get the governance voting power of an address
+    pub async fn get_voting_power(&self, address: &str) -> Result<u128> {
+        let resp = self
+            .http
+            .get(format!("{}/api/governance/voting-power/{}", self.base_url, address))
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(SdkError::Api(format!("Voting power query failed: {}", resp.status())));
+        }
+
+        #[derive(Deserialize)]
+        struct VotingPowerResponse {
+            voting_power: String,
+        }
+
+        let data = resp.json::<VotingPowerResponse>().await?;
+        data.voting_power
+            .parse()
+            .map_err(|_| SdkError::Api(format!("Invalid voting power: {}", data.voting_power)))
+    }
+
+    /// Sign a single-message transaction at the sender's current nonce and submit it
+    async fn sign_and_submit(&self, wallet: &Wallet, msg: GovernanceMsg) -> Result<String> {
+        let account = self.get_account(wallet.address()).await?;
+        let tx = serde_json::json!({
+            "chain_id": self.chain_id,
+            "nonce": account.nonce,
+            "msgs": [msg],
+            "fee": DEFAULT_TX_FEE.to_string(),
+            "memo": "",
+        });
+        let signed_tx = wallet.sign_transaction(&tx)?;
+        self.submit_signed(signed_tx).await
+    }
+
     // ===== Genesis & Network Info =====
 
     /// Get the genesis configuration for this chain
@@ -563,6 +797,9 @@ pub struct SignedTransaction {
     pub memo: String,
     pub msgs: Vec<TransactionMessage>,
     pub signature: TransactionSignature,
+    /// Gas limit (see `Client::fill_gas_limit`); node default applies when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_limit: Option<u64>,
 }
 
 /// Transaction message
@@ -597,6 +834,46 @@ pub struct TransactionReceipt {
     pub events: Vec<serde_json::Value>,
 }
 
+impl TransactionReceipt {
+    /// Value of `key` on the first event of type `event_type`
+    pub fn event_attribute(&self, event_type: &str, key: &str) -> Option<String> {
+        self.events
+            .iter()
+            .filter(|e| e["type"] == event_type)
+            .flat_map(|e| e["attributes"].as_array().cloned().unwrap_or_default())
+            .find(|a| a["key"] == key)
+            .and_then(|a| a["value"].as_str().map(str::to_string))
+    }
+}
+
+/// Result of a `/tx/simulate` dry run
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SimulationResult {
+    pub success: bool,
+    #[serde(default)]
+    pub error: Option<String>,
+    pub gas_used: u64,
+    #[serde(default)]
+    pub gas_limit: u64,
+    /// Gas per operation (intrinsic, kv reads/writes, ...)
+    #[serde(default)]
+    pub gas_breakdown: HashMap<String, u64>,
+    #[serde(default)]
+    pub events: Vec<serde_json::Value>,
+    #[serde(default)]
+    pub balance_diff: Vec<BalanceChange>,
+}
+
+/// Balance change for one address/denom; amounts are decimal strings, `delta` is signed
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BalanceChange {
+    pub address: String,
+    pub denom: String,
+    pub before: String,
+    pub after: String,
+    pub delta: String,
+}
+
 /// Block information
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Block {
@@ -664,6 +941,164 @@ pub struct ContractCallResponse {
     pub logs: Vec<String>,
 }
 
+// ===== Governance Types =====
+
+/// Governance message, encoded as the node's signed `Msg`
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum GovernanceMsg {
+    SubmitProposal {
+        from: String,
+        title: String,
+        description: String,
+        proposal: ProposalType,
+        // The node omits the standard track from the signed bytes too
+        #[serde(skip_serializing_if = "ProposalTrack::is_standard")]
+        track: ProposalTrack,
+    },
+    Deposit {
+        from: String,
+        proposal_id: u64,
+        amount: String,
+    },
+    Vote {
+        from: String,
+        proposal_id: u64,
+        option: VoteOption,
+    },
+}
+
+/// Governance proposal
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Proposal {
+    pub id: u64,
+    pub title: String,
+    pub description: String,
+    pub proposal_type: ProposalType,
+    pub status: ProposalStatus,
+    /// Deposited udgt
+    pub total_deposit: u128,
+    pub submit_height: u64,
+    pub deposit_end_height: u64,
+    pub voting_start_height: u64,
+    pub voting_end_height: u64,
+    /// Final tally, set once voting has ended
+    #[serde(default)]
+    pub tally: Option<TallyResult>,
+    #[serde(default)]
+    pub track: ProposalTrack,
+}
+
+/// What a proposal does when it passes
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub enum ProposalType {
+    /// Set a governed parameter such as `governance.voting_period`
+    ParameterChange { key: String, value: String },
+    /// Signalling only
+    Text,
+    /// Pay `amount` from the community pool to `recipient`
+    CommunitySpend {
+        recipient: String,
+        amount: u128,
+        denom: String,
+    },
+    /// Halt the chain at `height` for the upgrade `name`
+    SoftwareUpgrade {
+        name: String,
+        height: u64,
+        info: String,
+    },
+    /// Halt (or resume) bridge message ingestion
+    HaltBridge { halted: bool },
+    /// Pause (or unpause) a module
+    PauseModule { module: String, paused: bool },
+}
+
+/// Proposal lifecycle status
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub enum ProposalStatus {
+    DepositPeriod,
+    VotingPeriod,
+    Passed,
+    Rejected,
+    Failed,
+    Executed,
+    FailedExecution,
+}
+
+/// How a proposal is voted on
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposalTrack {
+    #[default]
+    Standard,
+    /// Higher deposit and a shorter voting period and higher threshold
+    Expedited,
+    /// Bridge halts and module pauses, passed by a validator supermajority
+    Emergency,
+}
+
+impl ProposalTrack {
+    /// Whether this is the standard track
+    pub fn is_standard(&self) -> bool {
+        *self == ProposalTrack::Standard
+    }
+}
+
+/// Vote option
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub enum VoteOption {
+    Yes,
+    No,
+    NoWithVeto,
+    Abstain,
+}
+
+/// Voting power counted per option
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct TallyResult {
+    pub yes: u128,
+    pub no: u128,
+    pub no_with_veto: u128,
+    pub abstain: u128,
+    /// Voting power that took part
+    pub total_voting_power: u128,
+}
+
+/// Entry of `Client::list_proposals`
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProposalSummary {
+    pub id: u64,
+    /// Proposal type label, e.g. `ParameterChange(governance.voting_period)`
+    #[serde(rename = "type")]
+    pub proposal_type: String,
+    #[serde(default)]
+    pub track: ProposalTrack,
+    pub title: String,
+    pub status: ProposalStatus,
+    #[serde(alias = "submit_time")]
+    pub submit_height: u64,
+    #[serde(alias = "deposit_end")]
+    pub deposit_end_height: u64,
+    #[serde(alias = "voting_end")]
+    pub voting_end_height: u64,
+    #[serde(default)]
+    pub current_tally: Option<TallySummary>,
+}
+
+/// Current tally of a listed proposal; amounts are decimal strings
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TallySummary {
+    pub yes: String,
+    pub no: String,
+    pub abstain: String,
+    pub no_with_veto: String,
+    /// Voting power quorum is measured against
+    pub total_voting_power: String,
+    pub participating_voting_power: String,
+    pub quorum_met: bool,
+}
+
 // ===== Genesis Types =====
 
 /// Genesis configuration
@@ -751,4 +1186,76 @@ mod tests {
         assert_eq!(info.dgt_balance(), 10.0);
         assert_eq!(info.drt_balance(), 5.0);
     }
+
+    #[test]
+    fn test_governance_msgs_match_node_encoding() {
+        let submit = GovernanceMsg::SubmitProposal {
+            from: "dyt1test".to_string(),
+            title: "Pause oracle".to_string(),
+            description: String::new(),
+            proposal: ProposalType::PauseModule {
+                module: "oracle".to_string(),
+                paused: true,
+            },
+            track: ProposalTrack::Emergency,
+        };
+        let json = serde_json::to_value(&submit).unwrap();
+        assert_eq!(json["type"], "submit_proposal");
+        assert_eq!(json["proposal"]["PauseModule"]["module"], "oracle");
+        assert_eq!(json["track"], "emergency");
+
+        let standard = GovernanceMsg::SubmitProposal {
+            from: "dyt1test".to_string(),
+            title: "Signal".to_string(),
+            description: String::new(),
+            proposal: ProposalType::Text,
+            track: ProposalTrack::Standard,
+        };
+        let json = serde_json::to_value(&standard).unwrap();
+        assert_eq!(json["proposal"], "Text");
+        assert!(json.get("track").is_none());
+
+        let vote = GovernanceMsg::Vote {
+            from: "dyt1test".to_string(),
+            proposal_id: 3,
+            option: VoteOption::NoWithVeto,
+        };
+        let json = serde_json::to_value(&vote).unwrap();
+        assert_eq!(json["type"], "vote");
+        assert_eq!(json["option"], "NoWithVeto");
+    }
+
+    #[test]
+    fn test_proposal_from_node_json() {
+        let proposal: Proposal = serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "title": "Spend",
+            "description": "",
+            "proposal_type": {"CommunitySpend": {"recipient": "dyt1r", "amount": 500, "denom": "udgt"}},
+            "status": "Passed",
+            "total_deposit": 1000000000,
+            "submit_height": 10,
+            "deposit_end_height": 110,
+            "voting_start_height": 20,
+            "voting_end_height": 120,
+            "tally": {"yes": 7, "no": 1, "no_with_veto": 0, "abstain": 2, "total_voting_power": 10},
+            "track": "expedited"
+        }))
+        .unwrap();
+        assert_eq!(proposal.status, ProposalStatus::Passed);
+        assert_eq!(proposal.track, ProposalTrack::Expedited);
+        assert_eq!(proposal.tally.unwrap().yes, 7);
+
+        let receipt: TransactionReceipt = serde_json::from_value(serde_json::json!({
+            "hash": "0xabc",
+            "status": "success",
+            "events": [{"type": "submit_proposal", "attributes": [
+                {"key": "proposer", "value": "dyt1test"},
+                {"key": "proposal_id", "value": "4"}
+            ]}]
+        }))
+        .unwrap();
+        assert_eq!(receipt.event_attribute("submit_proposal", "proposal_id").as_deref(), Some("4"));
+        assert_eq!(receipt.event_attribute("vote", "proposal_id"), None);
+    }
 }
@@ -4,6 +4,7 @@
 //! - PQC wallet generation (ML-DSA / Dilithium3)
 //! - Transaction signing
 //! - RPC client for chain interaction
+//! - Governance: proposals, deposits and votes
 //!
 //! # Quick Start
 //!
@@ -30,7 +31,7 @@ mod client;
 mod error;
 
 pub use wallet::Wallet;
-pub use client::{Client, ChainStatus, AccountInfo, FaucetResponse, FaucetDispensed, Block, StakingRewards, RewardsBalance, TransactionReceipt};
+pub use client::{Client, ChainStatus, AccountInfo, FaucetResponse, FaucetDispensed, Block, StakingRewards, RewardsBalance, TransactionReceipt, SignedTransaction, TransactionMessage, TransactionSignature, SimulationResult, BalanceChange, Proposal, ProposalType, ProposalStatus, ProposalTrack, VoteOption, TallyResult, ProposalSummary, TallySummary, DEFAULT_TX_FEE};
 pub use error::{SdkError, Result};
 
 /// Testnet RPC endpoint
@@ -1,9 +1,11 @@
 //! PQC Wallet implementation using Dilithium3 (ML-DSA-65)
 
 use crate::error::{Result, SdkError};
+use base64::{engine::general_purpose::STANDARD as B64, Engine};
 use pqcrypto_dilithium::dilithium3;
 use pqcrypto_traits::sign::{PublicKey, SecretKey, SignedMessage};
 use sha2::{Digest, Sha256};
+use sha3::Sha3_256;
 use zeroize::ZeroizeOnDrop;
 use std::path::Path;
 use std::fs;
@@ -54,7 +56,7 @@ impl Wallet {
     /// Save wallet to JSON file
     pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
         let stored = StoredWallet {
-            algorithm: "dilithium3".to_string(),
+            algorithm: self.algorithm().to_string(),
             public_key: hex::encode(&self.public_key),
             secret_key: hex::encode(&self.secret_key),
             address: self.address.clone(),
@@ -90,6 +92,11 @@ impl Wallet {
         hex::encode(&self.public_key)
     }
 
+    /// Signature algorithm identifier
+    pub fn algorithm(&self) -> &'static str {
+        "dilithium3"
+    }
+
     /// Sign a message
     pub fn sign(&self, message: &[u8]) -> Result<Signature> {
         let sk = dilithium3::SecretKey::from_bytes(&self.secret_key)
@@ -108,6 +115,25 @@ impl Wallet {
         })
     }
 
+    /// Sign a node transaction body (`chain_id`, `nonce`, `msgs`, `fee`, `memo`).
+    ///
+    /// The signature covers the SHA3-256 hash of the body's canonical JSON (keys sorted,
+    /// no whitespace), as the node verifies it. Returns the `signed_tx` envelope that
+    /// `/submit` accepts.
+    pub fn sign_transaction(&self, tx: &serde_json::Value) -> Result<serde_json::Value> {
+        let bytes = serde_json::to_vec(&sort_keys(tx.clone()))?;
+        let hash = Sha3_256::digest(&bytes);
+        let signature = self.sign(&hash)?;
+
+        Ok(serde_json::json!({
+            "tx": tx,
+            "public_key": B64.encode(&self.public_key),
+            "signature": B64.encode(&signature.data),
+            "algorithm": self.algorithm(),
+            "version": 1,
+        }))
+    }
+
     /// Verify a signature
     pub fn verify(&self, message: &[u8], signature: &Signature) -> Result<bool> {
         let pk = dilithium3::PublicKey::from_bytes(&signature.public_key)
@@ -153,6 +179,26 @@ impl Wallet {
     }
 }
 
+/// Recursively sort object keys so the encoding does not depend on insertion order
+fn sort_keys(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries: Vec<_> = map.into_iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            serde_json::Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(k, v)| (k, sort_keys(v)))
+                    .collect(),
+            )
+        }
+        serde_json::Value::Array(items) => {
+            serde_json::Value::Array(items.into_iter().map(sort_keys).collect())
+        }
+        other => other,
+    }
+}
+
 /// Signature with public key
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Signature {
@@ -211,4 +257,31 @@ mod tests {
         
         assert!(!valid);
     }
+
+    #[test]
+    fn test_sign_transaction_covers_canonical_hash() {
+        let wallet = Wallet::generate().unwrap();
+        let tx = serde_json::json!({
+            "nonce": 0,
+            "chain_id": "dyt-local-1",
+            "msgs": [{"type": "vote", "proposal_id": 1, "option": "Yes", "from": wallet.address()}],
+            "memo": "",
+            "fee": "1000",
+        });
+
+        let signed = wallet.sign_transaction(&tx).unwrap();
+        assert_eq!(signed["tx"], tx);
+        assert_eq!(signed["algorithm"], "dilithium3");
+
+        let canonical = format!(
+            r#"{{"chain_id":"dyt-local-1","fee":"1000","memo":"","msgs":[{{"from":"{}","option":"Yes","proposal_id":1,"type":"vote"}}],"nonce":0}}"#,
+            wallet.address()
+        );
+        let hash = Sha3_256::digest(canonical.as_bytes());
+        let signature = Signature {
+            data: B64.decode(signed["signature"].as_str().unwrap()).unwrap(),
+            public_key: wallet.public_key().to_vec(),
+        };
+        assert!(wallet.verify(&hash, &signature).unwrap());
+    }
 }